@@ -1,6 +1,217 @@
 use std::{collections::HashMap, mem::forget};
 
-use crate::models::Label;
+use crate::assembler::AssembleError;
+use crate::models::{Label, Section, SymbolKind};
+
+/// The width of the address stored at [`Relocation::offset`], and whether
+/// it is absolute or relative to the address of the stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[must_use]
+pub enum RelocationKind {
+    /// 64-bit address, e.g. [`Instruction::LabelAddress64`][`crate::models::Instruction::LabelAddress64`].
+    Absolute64 = 1,
+
+    /// 32-bit address, e.g. [`Memory::label`][`crate::models::Memory::label`] in 32-bit code.
+    Absolute32,
+
+    /// 16-bit address, e.g. [`Memory::label`][`crate::models::Memory::label`] in 16-bit code.
+    Absolute16,
+
+    /// 32-bit relative displacement, e.g. of a call to an external label.
+    Relative32,
+
+    /// 16-bit relative displacement, e.g. of a call to an external label in 16-bit code.
+    Relative16,
+}
+
+impl RelocationKind {
+    /// Returns the number of bytes of the address.
+    #[inline]
+    #[must_use]
+    pub const fn size(self) -> usize {
+        match self {
+            RelocationKind::Absolute64 => 8,
+            RelocationKind::Absolute32 | RelocationKind::Relative32 => 4,
+            RelocationKind::Absolute16 | RelocationKind::Relative16 => 2,
+        }
+    }
+}
+
+/// Represents an address of a label stored in the emitted code, which
+/// depends on where the code is placed.
+///
+/// The stored value is the address of the symbol plus [`Relocation::addend`],
+/// minus the address of the stored value itself for relative kinds. The symbol
+/// is the beginning of the code for labels set in the code, so these have to be
+/// updated when the code is moved, see [`EmissionData::apply_relocations`]. For
+/// external labels, see [`Relocation::is_external`], the symbol is the target
+/// label itself and the value is left zeroed for linking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct Relocation {
+    offset: i32,
+    kind: RelocationKind,
+    target: Label,
+    addend: i64,
+    is_external: bool,
+}
+
+impl Relocation {
+    #[inline(always)]
+    pub(crate) const fn new(offset: i32, kind: RelocationKind, target: Label, addend: i64) -> Self {
+        Self {
+            offset,
+            kind,
+            target,
+            addend,
+            is_external: false,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn external(offset: i32, kind: RelocationKind, target: Label, addend: i64) -> Self {
+        Self {
+            offset,
+            kind,
+            target,
+            addend,
+            is_external: true,
+        }
+    }
+
+    /// Returns the position of the stored address in the emitted code.
+    #[inline(always)]
+    #[must_use]
+    pub const fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    #[inline(always)]
+    pub const fn kind(&self) -> RelocationKind {
+        self.kind
+    }
+
+    /// Returns the label the address points to.
+    #[inline(always)]
+    pub const fn target(&self) -> Label {
+        self.target
+    }
+
+    /// Returns the value added to the address of the symbol. For labels set
+    /// in the code this is the position of [`Relocation::target`] in the
+    /// emitted code. For relative kinds of external labels, this is minus
+    /// the distance from the stored value to the end of the instruction.
+    #[inline(always)]
+    #[must_use]
+    pub const fn addend(&self) -> i64 {
+        self.addend
+    }
+
+    /// Returns `true` if the [`Relocation::target`] is an external label, see
+    /// [`X86_64Assembler::declare_external`][`crate::assembler::X86_64Assembler::declare_external`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_external(&self) -> bool {
+        self.is_external
+    }
+}
+
+/// Represents the position of a [`Section`] in the emitted code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct SectionPlacement {
+    section: Section,
+    offset: i32,
+    length: i32,
+}
+
+impl SectionPlacement {
+    #[inline(always)]
+    pub(crate) const fn new(section: Section, offset: i32, length: i32) -> Self {
+        Self {
+            section,
+            offset,
+            length,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn section(&self) -> Section {
+        self.section
+    }
+
+    /// Returns the position of the section in the emitted code, after
+    /// the padding of its alignment.
+    #[inline(always)]
+    #[must_use]
+    pub const fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Returns the number of bytes of the section.
+    #[inline(always)]
+    #[must_use]
+    pub const fn length(&self) -> i32 {
+        self.length
+    }
+}
+
+/// Represents a [`Symbol`][`crate::models::Symbol`] resolved to its
+/// position in the emitted code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct ResolvedSymbol {
+    label: Label,
+    kind: SymbolKind,
+    offset: i32,
+    size: Option<u32>,
+}
+
+impl ResolvedSymbol {
+    #[inline(always)]
+    pub(crate) const fn new(label: Label, kind: SymbolKind, offset: i32, size: Option<u32>) -> Self {
+        Self {
+            label,
+            kind,
+            offset,
+            size,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn label(&self) -> Label {
+        self.label
+    }
+
+    #[inline(always)]
+    pub const fn kind(&self) -> SymbolKind {
+        self.kind
+    }
+
+    /// Returns the symbol moved `distance` bytes further in the code.
+    #[inline(always)]
+    pub(crate) const fn moved_by(self, distance: i32) -> Self {
+        Self {
+            offset: self.offset + distance,
+            ..self
+        }
+    }
+
+    /// Returns the position of the symbol in the emitted code.
+    #[inline(always)]
+    #[must_use]
+    pub const fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Returns the size of the symbol in bytes, or `None` if it is unknown.
+    #[inline(always)]
+    #[must_use]
+    pub const fn size(&self) -> Option<u32> {
+        self.size
+    }
+}
 
 /// The classical Rust struct that represents the summary of the assembled code.
 /// It allows the data to be mutated and moved around.
@@ -8,6 +219,10 @@ use crate::models::Label;
 pub struct DeconstructedEmissionData {
     pub emitted_bytes: i32,
     pub public_labels_positions: HashMap<Label, i32>,
+    pub relocations: Vec<Relocation>,
+    pub base_address: Option<u64>,
+    pub sections: Vec<SectionPlacement>,
+    pub symbols: HashMap<String, ResolvedSymbol>,
 }
 
 /// The summary of the assembled code. This struct is immutable.
@@ -18,14 +233,29 @@ pub struct DeconstructedEmissionData {
 pub struct EmissionData {
     emitted_bytes: i32,
     public_labels_positions: HashMap<Label, i32>,
+    relocations: Vec<Relocation>,
+    base_address: Option<u64>,
+    sections: Vec<SectionPlacement>,
+    symbols: HashMap<String, ResolvedSymbol>,
 }
 
 impl EmissionData {
     #[inline(always)]
-    pub(crate) fn new(emitted_bytes: i32, public_labels_positions: HashMap<Label, i32>) -> Self {
+    pub(crate) fn new(
+        emitted_bytes: i32,
+        public_labels_positions: HashMap<Label, i32>,
+        relocations: Vec<Relocation>,
+        base_address: Option<u64>,
+        sections: Vec<SectionPlacement>,
+        symbols: HashMap<String, ResolvedSymbol>,
+    ) -> Self {
         Self {
             emitted_bytes,
             public_labels_positions,
+            relocations,
+            base_address,
+            sections,
+            symbols,
         }
     }
 
@@ -33,14 +263,41 @@ impl EmissionData {
     #[inline(always)]
     pub const fn deconstruct(self) -> DeconstructedEmissionData {
         let emitted_bytes = self.emitted_bytes;
+        let base_address = self.base_address;
         let labels_to_position_map = unsafe { std::ptr::read(&self.public_labels_positions) };
+        let relocations = unsafe { std::ptr::read(&self.relocations) };
+        let sections = unsafe { std::ptr::read(&self.sections) };
+        let symbols = unsafe { std::ptr::read(&self.symbols) };
         forget(self);
         DeconstructedEmissionData {
             emitted_bytes,
             public_labels_positions: labels_to_position_map,
+            relocations,
+            base_address,
+            sections,
+            symbols,
         }
     }
 
+    /// Returns the base address the code was assembled for, if it was set with
+    /// [`X86_64AssemblerBuilder::with_base_address`][`crate::assembler::X86_64AssemblerBuilder::with_base_address`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn base_address(&self) -> Option<u64> {
+        self.base_address
+    }
+
+    /// Returns the absolute address of the public label, i.e. the base address
+    /// plus the label position. Returns `None` if the label is not public, or
+    /// if the base address wasn't set.
+    #[inline]
+    #[must_use]
+    pub fn public_label_address(&self, label: &Label) -> Option<u64> {
+        let base_address = self.base_address?;
+        let position = *self.public_labels_positions.get(label)?;
+        Some(base_address.wrapping_add_signed(i64::from(position)))
+    }
+
     /// Returns a map of public labels to their positions in the emitted code,
     /// relative to the beginning of the code, not to the passed stream.
     #[inline(always)]
@@ -55,4 +312,92 @@ impl EmissionData {
     pub const fn emitted_bytes(&self) -> i32 {
         self.emitted_bytes
     }
+
+    /// Returns the label addresses stored in the emitted code, ordered by
+    /// their offsets. Addresses of labels set in the code are already resolved
+    /// against the base address of the assembler, or against zero if it wasn't
+    /// set. Use sites of external labels are left for linking.
+    #[inline(always)]
+    pub fn relocations(&self) -> &[Relocation] {
+        &self.relocations
+    }
+
+    /// Returns the placements of the sections, in the order they were emitted.
+    /// Each used section is reported, even if it is empty.
+    #[inline(always)]
+    pub fn sections(&self) -> &[SectionPlacement] {
+        &self.sections
+    }
+
+    /// Returns the placement of the `section`, or `None` if it wasn't used.
+    #[inline]
+    #[must_use]
+    pub fn section(&self, section: Section) -> Option<&SectionPlacement> {
+        self.sections.iter().find(|placement| placement.section == section)
+    }
+
+    /// Returns the symbol table, i.e. the emitted [`Symbol`][`crate::models::Symbol`]s
+    /// keyed by their names.
+    #[inline(always)]
+    #[must_use]
+    pub const fn symbols(&self) -> &HashMap<String, ResolvedSymbol> {
+        &self.symbols
+    }
+
+    /// Returns the symbol with the given `name`, if any.
+    #[inline]
+    #[must_use]
+    pub fn symbol(&self, name: &str) -> Option<&ResolvedSymbol> {
+        self.symbols.get(name)
+    }
+
+    /// Rewrites the absolute addresses in `code`, which is the emitted code,
+    /// so that they are valid when the code is placed at `base_address`.
+    /// External relocations are skipped.
+    ///
+    /// For code assembled with [`X86_64AssemblerBuilder::with_start_offset`][`crate::assembler::X86_64AssemblerBuilder::with_start_offset`],
+    /// `code` and `base_address` refer to the whole buffer.
+    ///
+    /// Direct calls and jumps to absolute addresses, see
+    /// [`Instruction::Call_Absolute`][`crate::models::Instruction::Call_Absolute`],
+    /// are resolved against the base address set in the builder and are not
+    /// relocated, so such code has to be placed at that base address.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AssembleError::AddressOutOfRange`] if the address doesn't
+    /// fit in its [`RelocationKind`]. The `code` is partially relocated then.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `code` is shorter than the emitted code.
+    pub fn apply_relocations(&self, code: &mut [u8], base_address: u64) -> Result<(), AssembleError> {
+        for relocation in &self.relocations {
+            if relocation.is_external {
+                continue;
+            }
+            let address = i128::from(base_address) + i128::from(relocation.addend);
+            let out_of_range = || AssembleError::AddressOutOfRange(relocation.target);
+            #[allow(clippy::cast_sign_loss)]
+            let offset = relocation.offset as usize;
+            let destination = &mut code[offset..offset + relocation.kind.size()];
+            match relocation.kind {
+                RelocationKind::Absolute64 => {
+                    let address = u64::try_from(address).map_err(|_| out_of_range())?;
+                    destination.copy_from_slice(&address.to_le_bytes());
+                }
+                RelocationKind::Absolute32 => {
+                    let address = u32::try_from(address).map_err(|_| out_of_range())?;
+                    destination.copy_from_slice(&address.to_le_bytes());
+                }
+                RelocationKind::Absolute16 => {
+                    let address = u16::try_from(address).map_err(|_| out_of_range())?;
+                    destination.copy_from_slice(&address.to_le_bytes());
+                }
+                // The distance between two labels in the code doesn't depend on its placement.
+                RelocationKind::Relative32 | RelocationKind::Relative16 => {}
+            }
+        }
+        Ok(())
+    }
 }
@@ -8,8 +8,35 @@ pub enum EmitError {
     /// The operands in the instruction have incorrect size.
     OperandSizeMismatch,
 
+    /// The operands can't be encoded together, e.g. `AH` with
+    /// a register that requires REX prefix.
+    IncompatibleOperands,
+
+    /// The operand that is implicit in the encoding, e.g. `xmm0`
+    /// of `pblendvb`, was set to a different register.
+    InvalidImplicitOperand,
+
+    /// The instruction requires APX, i.e. it uses one of `R16` to `R31`
+    /// registers or the new data destination form, but APX support was
+    /// not enabled in the builder.
+    ApxNotEnabled,
+
+    /// The memory operand address size doesn't match the code mode,
+    /// e.g. `[eax]` in 64-bit code or `[rax]` in 32-bit code.
+    AddressSizeMismatch,
+
+    /// The instruction is not supported in the selected code mode.
+    NotSupportedInCodeMode,
+
+    /// The instruction requires the base address of the code, e.g. a direct
+    /// call to an absolute address in 32-bit code.
+    BaseAddressNotSet,
+
     /// Tried to emit the same lable twice.
     LabelAlreadyDefined(Label),
+
+    /// Tried to emit two symbols with the same name.
+    SymbolAlreadyDefined(String),
 }
 
 /// Errors returned during final assembly.
@@ -22,6 +49,27 @@ pub enum AssembleError {
 
     /// The code contains references to labels that were not set.
     LabelNotSet(Label),
+
+    /// The label is out of reach of the instruction that refers to it,
+    /// e.g. its absolute address doesn't fit in 16 bits in 16-bit code.
+    AddressOutOfRange(Label),
+
+    /// The distance between two labels, e.g. an entry of a jump table, refers to
+    /// an external label. Such distances are neither resolved nor relocated.
+    ExternalLabelDifference(Label),
+
+    /// The end label of the symbol size is set before the symbol.
+    InvalidSymbolSize(Label),
+
+    /// More than one linked module defines the same public label.
+    LabelAlreadyDefined(Label),
+
+    /// More than one linked module defines a symbol with the same name.
+    SymbolAlreadyDefined(String),
+
+    /// The linked module was assembled with a base address, so it can't be
+    /// moved, e.g. its direct calls to absolute addresses are not relocated.
+    ModuleWithBaseAddress,
 }
 
 impl From<std::io::Error> for AssembleError {
@@ -0,0 +1,141 @@
+use crate::assembler::EmitError;
+use crate::models::{GPR, Instruction, Memory, Size};
+
+/// The processor mode the emitted code is meant to run in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[must_use]
+pub enum CodeMode {
+    /// 64-bit long mode, the default.
+    Bit64 = 1,
+
+    /// 32-bit protected mode, e.g. 32-bit processes or boot stages.
+    ///
+    /// # Notes
+    ///
+    /// The same [`Instruction`]s are used, but with the following rules:
+    ///
+    /// * Memory operands have to use 32-bit base and index registers.
+    /// * REX prefix is not available, so 64-bit operands, `R8` to `R15`
+    ///   (and APX registers), `SPL`, `BPL`, `SIL`, `DIL` and `XMM8` to
+    ///   `XMM15` are rejected. So are instructions that are REX.W
+    ///   encoded by nature, e.g. `iretq`.
+    /// * Instructions that operate on 64-bit registers in 64-bit mode,
+    ///   e.g. `push reg` or `mov cr, reg`, take 32-bit registers instead.
+    /// * [`Memory::label`][`crate::models::Memory::label`] is encoded as
+    ///   absolute address, i.e. the label offset from the beginning of
+    ///   the emitted code plus the base address. Jumps and calls to
+    ///   labels stay relative.
+    Bit32,
+
+    /// 16-bit real mode, e.g. boot sectors or SMP AP trampolines.
+    ///
+    /// # Notes
+    ///
+    /// The same rules as for [`CodeMode::Bit32`] apply, with the following
+    /// differences:
+    ///
+    /// * The default operand size is 16-bit. Operand size prefix is inserted
+    ///   when 32-bit registers are used, e.g. `push eax` or `in eax, dx`.
+    /// * Memory operands use one of 16-bit addressing forms, e.g. `[bx + si + 8]`.
+    ///   32-bit addresses are accepted as well, with address size prefix.
+    /// * [`Memory::label`][`crate::models::Memory::label`] is encoded as
+    ///   16-bit absolute address and jumps and calls to labels use rel16.
+    /// * Only general purpose and system instructions are supported.
+    Bit16,
+}
+
+/// Verifies that the memory operand of `instruction`, if any,
+/// uses the address size of `mode`.
+pub(super) fn check_address_size(mode: CodeMode, instruction: &Instruction) -> Result<(), EmitError> {
+    let Some(address_size) = instruction.memory_operand().and_then(Memory::address_size) else {
+        return Ok(());
+    };
+
+    let expected = match mode {
+        CodeMode::Bit64 => Size::Bit64,
+        CodeMode::Bit32 => Size::Bit32,
+        CodeMode::Bit16 => Size::Bit16,
+    };
+
+    if address_size != expected {
+        return Err(EmitError::AddressSizeMismatch);
+    }
+
+    Ok(())
+}
+
+/// Rewrites instructions whose operands default to 64-bit in 64-bit mode,
+/// to the same encoding with 32-bit operands. Returns `None` if the instruction
+/// doesn't need rewriting.
+pub(super) fn translate_bit32_instruction(instruction: &Instruction) -> Result<Option<Instruction>, EmitError> {
+    let translated = match instruction {
+        Instruction::Push_Reg { src } => Instruction::Push_Reg {
+            src: bit32_to_bit64(*src)?,
+        },
+        Instruction::Pop_Reg { src } => Instruction::Pop_Reg {
+            src: bit32_to_bit64(*src)?,
+        },
+        Instruction::Jump_Reg { dst } => Instruction::Jump_Reg {
+            dst: bit32_to_bit64(*dst)?,
+        },
+        Instruction::Call_Reg { dst } => Instruction::Call_Reg {
+            dst: bit32_to_bit64(*dst)?,
+        },
+        Instruction::Mov_CrReg { dst, src } => Instruction::Mov_CrReg {
+            dst: *dst,
+            src: bit32_to_bit64(*src)?,
+        },
+        Instruction::Mov_RegCr { dst, src } => Instruction::Mov_RegCr {
+            dst: bit32_to_bit64(*dst)?,
+            src: *src,
+        },
+        Instruction::Mov_DrReg { dst, src } => Instruction::Mov_DrReg {
+            dst: *dst,
+            src: bit32_to_bit64(*src)?,
+        },
+        Instruction::Mov_RegDr { dst, src } => Instruction::Mov_RegDr {
+            dst: bit32_to_bit64(*dst)?,
+            src: *src,
+        },
+        Instruction::Swapgs => return Err(EmitError::IncompatibleOperands),
+        _ => return Ok(None),
+    };
+    Ok(Some(translated))
+}
+
+fn bit32_to_bit64(gpr: GPR) -> Result<GPR, EmitError> {
+    if gpr.size() != Size::Bit32 {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+    Ok(gpr.as_bit64())
+}
+
+/// Verifies that the 64-bit mode encoding in `bytes` decodes the same in 32-bit mode.
+///
+/// This is the case unless the encoding uses REX, REX2 or EVEX prefix, or
+/// VEX prefix with any of the register extension bits set.
+pub(super) fn check_bit32_encoding(bytes: &[u8]) -> Result<(), EmitError> {
+    const LEGACY_PREFIXES: &[u8] = &[0x66, 0x67, 0xF0, 0xF2, 0xF3, 0x26, 0x2E, 0x36, 0x3E, 0x64, 0x65];
+    const VEX3: u8 = 0xC4;
+    const VEX2: u8 = 0xC5;
+
+    let Some(start) = bytes.iter().position(|byte| !LEGACY_PREFIXES.contains(byte)) else {
+        return Ok(());
+    };
+
+    let is_valid = match bytes[start..] {
+        [0x40..=0x4F | 0xD5 | 0x62, ..] => false,
+        // Inverted R, X, B in the first payload byte and inverted V3 in the second.
+        [VEX3, first, second, ..] => first & 0xE0 == 0xE0 && second & 0x40 == 0x40,
+        // Inverted R and V3 in the only payload byte.
+        [VEX2, first, ..] => first & 0xC0 == 0xC0,
+        _ => true,
+    };
+
+    if !is_valid {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,268 @@
+//! 16-bit code is emitted by encoding the instruction as 64-bit code
+//! and rewriting the result. The rewrite adjusts the operand size and
+//! address size prefixes, and replaces the `ModRM` byte and displacement
+//! of the memory operand with a 16-bit addressing form.
+//!
+//! To find the memory operand in the 64-bit encoding, it is replaced
+//! with a placeholder label, which is always encoded as `[rip + disp32]`
+//! with a known displacement offset.
+
+use crate::assembler::EmitError;
+use crate::models::{GPR, Instruction, Label, Memory, Size};
+
+use super::code_mode;
+use super::{InstructionCapture, PatchKind, PatchableImm32Instruction, X86_64Assembler};
+
+const OPERAND_SIZE_PREFIX: u8 = 0x66;
+const ADDRESS_SIZE_PREFIX: u8 = 0x67;
+const NOP: u8 = 0x90;
+const CALL_REL: u8 = 0xE8;
+const MODRM_REG_MASK: u8 = 0b0011_1000;
+const RIP_RELATIVE_DISPLACEMENT_LENGTH: usize = 4;
+
+/// How the operand size prefix of the 64-bit encoding is adjusted.
+#[derive(Debug, Clone, Copy)]
+enum OperandSize {
+    /// The encoding doesn't depend on the default operand size.
+    Fixed,
+
+    /// The operand size prefix switches between 16-bit and 32-bit operands,
+    /// with the default being 32-bit in 64-bit code and 16-bit in 16-bit code.
+    /// The size is the operand size of the instruction.
+    Switched(Size),
+}
+
+pub(super) fn emit_bit16_instruction(asm: &mut X86_64Assembler, instruction: &Instruction) -> Result<(), EmitError> {
+    match instruction {
+        Instruction::SetPrivate_Label { .. }
+        | Instruction::SetPublic_Label { .. }
+        | Instruction::Jump_Label { .. }
+        | Instruction::CondJump_Label { .. }
+        | Instruction::Loop_Label { .. }
+        | Instruction::Jrcxz_Label { .. }
+        | Instruction::Align { .. }
+        | Instruction::LabelDifference32 { .. }
+        | Instruction::LabelAddress32 { .. }
+        | Instruction::LabelAddress64 { .. }
+        | Instruction::SwitchSection { .. } => return asm._dispatch_instruction(instruction),
+        Instruction::Nop { length } => {
+            // Multi-byte nops have 32-bit memory operand, which is longer in 16-bit code.
+            let nops = vec![NOP; length.get() as usize];
+            asm._write_bytes_internal(&nops);
+            return Ok(());
+        }
+        Instruction::Call_Label { dst } => {
+            let instr = [CALL_REL, 0, 0];
+            let patchable_instruction = PatchableImm32Instruction {
+                instruction_position: asm._instruction_position(),
+                instruction_length: instr.len() as u8,
+                imm32_offset: 1,
+                kind: PatchKind::Relative16,
+            };
+            asm._push_patchable_instruction(*dst, patchable_instruction);
+            asm._write_bytes_internal(&instr);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let (mut encodable, operand_size) = translate_instruction(instruction)?;
+
+    let memory = instruction.memory_operand();
+    let mut bit16_modrm = None;
+    let mut with_address_size_prefix = false;
+    if let Some(memory) = memory {
+        match memory.address_size() {
+            Some(Size::Bit32) => with_address_size_prefix = true,
+            Some(Size::Bit16) | None => {
+                bit16_modrm = memory.as_bit16_modrm();
+                debug_assert!(bit16_modrm.is_some(), "16-bit address is validated on creation.");
+                let placeholder = encodable
+                    .memory_operand_mut()
+                    .expect("Translated instruction has to keep the memory operand.");
+                *placeholder = Memory::label(Label::placeholder());
+            }
+            _ => return Err(EmitError::AddressSizeMismatch),
+        }
+    }
+
+    asm.capture = Some(InstructionCapture::default());
+    let result = asm._dispatch_instruction(&encodable);
+    let InstructionCapture {
+        mut bytes,
+        displacement_offset,
+    } = asm.capture.take().expect("Capture is set above.");
+    result?;
+    code_mode::check_bit32_encoding(&bytes)?;
+
+    let mut label_displacement_offset = None;
+    if let Some(bit16_modrm) = bit16_modrm {
+        let offset = displacement_offset.expect("Label memory operand has to produce RIP-relative displacement.");
+        let modrm = &mut bytes[offset - 1];
+        *modrm = (bit16_modrm.modrm_mod << 6) | (*modrm & MODRM_REG_MASK) | bit16_modrm.rm;
+        bytes.splice(
+            offset..offset + RIP_RELATIVE_DISPLACEMENT_LENGTH,
+            bit16_modrm.displacement().iter().copied(),
+        );
+        label_displacement_offset = Some(offset);
+    }
+
+    let mut prefixes = Vec::with_capacity(2);
+    if with_address_size_prefix {
+        prefixes.push(ADDRESS_SIZE_PREFIX);
+    }
+    if let OperandSize::Switched(size) = operand_size {
+        if bytes.first() == Some(&OPERAND_SIZE_PREFIX) {
+            bytes.remove(0);
+            label_displacement_offset = label_displacement_offset.map(|offset| offset - 1);
+        } else if size == Size::Bit32 {
+            prefixes.push(OPERAND_SIZE_PREFIX);
+        }
+    }
+    label_displacement_offset = label_displacement_offset.map(|offset| offset + prefixes.len());
+    prefixes.extend_from_slice(&bytes);
+    let bytes = prefixes;
+
+    if let (Some(label), Some(offset)) = (memory.and_then(Memory::get_label), label_displacement_offset) {
+        let patchable_instruction = PatchableImm32Instruction {
+            instruction_position: asm._instruction_position(),
+            instruction_length: bytes.len() as u8,
+            imm32_offset: offset as u8,
+            kind: PatchKind::Absolute16,
+        };
+        asm._push_patchable_instruction(*label, patchable_instruction);
+    }
+
+    asm._write_bytes_internal(&bytes);
+    Ok(())
+}
+
+/// Translates the instruction to the one that has the same 64-bit encoding,
+/// modulo operand size prefix, as the original instruction in 16-bit code.
+#[allow(clippy::too_many_lines)]
+fn translate_instruction(instruction: &Instruction) -> Result<(Instruction, OperandSize), EmitError> {
+    use OperandSize::{Fixed, Switched};
+
+    let translated = match instruction {
+        Instruction::Ret
+        | Instruction::Cpuid
+        | Instruction::Int_Imm { .. }
+        | Instruction::Lock
+        | Instruction::Rdmsr
+        | Instruction::Wrmsr
+        | Instruction::Cli
+        | Instruction::Sti
+        | Instruction::Hlt
+        | Instruction::Lgdt_Mem { .. }
+        | Instruction::Lidt_Mem { .. }
+        | Instruction::Sgdt_Mem { .. }
+        | Instruction::Sidt_Mem { .. }
+        | Instruction::Ltr_Reg { .. }
+        | Instruction::Ltr_Mem { .. }
+        | Instruction::Invlpg_Mem { .. }
+        | Instruction::Jump_Mem { .. }
+        | Instruction::Call_Mem { .. }
+        | Instruction::Push_Mem { .. }
+        | Instruction::Pop_Mem { .. } => (instruction.clone(), Fixed),
+        Instruction::Mov_CrReg { .. }
+        | Instruction::Mov_RegCr { .. }
+        | Instruction::Mov_DrReg { .. }
+        | Instruction::Mov_RegDr { .. } => {
+            let translated = code_mode::translate_bit32_instruction(instruction)?
+                .expect("Control and debug register moves are translated in 32-bit code.");
+            (translated, Fixed)
+        }
+        Instruction::Mov_RegImm { dst, .. }
+        | Instruction::Mov_RegReg { dst, .. }
+        | Instruction::Mov_RegMem { dst, .. }
+        | Instruction::Cmp_RegImm { dst, .. }
+        | Instruction::Cmp_RegReg { dst, .. }
+        | Instruction::Cmp_RegMem { dst, .. }
+        | Instruction::Add_RegImm { dst, .. }
+        | Instruction::Add_RegReg { dst, .. }
+        | Instruction::Add_RegMem { dst, .. }
+        | Instruction::Sub_RegImm { dst, .. }
+        | Instruction::Sub_RegReg { dst, .. }
+        | Instruction::Sub_RegMem { dst, .. }
+        | Instruction::Xor_RegImm { dst, .. }
+        | Instruction::Xor_RegReg { dst, .. }
+        | Instruction::Xor_RegMem { dst, .. }
+        | Instruction::In_RegImm { dst, .. }
+        | Instruction::In_RegReg { dst, .. }
+        | Instruction::Mov_RegSeg { dst, .. } => (instruction.clone(), Switched(dst.size())),
+        Instruction::Mov_MemReg { src, .. }
+        | Instruction::Cmp_MemReg { src, .. }
+        | Instruction::Add_MemReg { src, .. }
+        | Instruction::Sub_MemReg { src, .. }
+        | Instruction::Xor_MemReg { src, .. }
+        | Instruction::Out_ImmReg { src, .. }
+        | Instruction::Out_RegReg { src, .. } => (instruction.clone(), Switched(src.size())),
+        Instruction::Mov_MemImm { src, .. }
+        | Instruction::Cmp_MemImm { src, .. }
+        | Instruction::Add_MemImm { src, .. }
+        | Instruction::Sub_MemImm { src, .. }
+        | Instruction::Xor_MemImm { src, .. }
+        | Instruction::Push_Imm { src } => (instruction.clone(), Switched(src.real_size())),
+        // The operand size of segment register loads doesn't matter, so the prefix is only dropped.
+        Instruction::Mov_SegReg { .. } => (instruction.clone(), Switched(Size::Bit16)),
+        Instruction::Push_Reg { src } => (
+            Instruction::Push_Reg {
+                src: gpr_to_bit64(*src)?,
+            },
+            Switched(src.size()),
+        ),
+        Instruction::Pop_Reg { src } => (
+            Instruction::Pop_Reg {
+                src: gpr_to_bit64(*src)?,
+            },
+            Switched(src.size()),
+        ),
+        Instruction::Jump_Reg { dst } => (
+            Instruction::Jump_Reg {
+                dst: gpr_to_bit64(*dst)?,
+            },
+            Switched(dst.size()),
+        ),
+        Instruction::Call_Reg { dst } => (
+            Instruction::Call_Reg {
+                dst: gpr_to_bit64(*dst)?,
+            },
+            Switched(dst.size()),
+        ),
+        Instruction::FarJump_Mem { dst, size } => {
+            let translated = Instruction::FarJump_Mem {
+                dst: dst.clone(),
+                size: Size::Bit32,
+            };
+            (translated, Switched(far_operand_size(*size)?))
+        }
+        Instruction::FarCall_Mem { dst, size } => {
+            let translated = Instruction::FarCall_Mem {
+                dst: dst.clone(),
+                size: Size::Bit32,
+            };
+            (translated, Switched(far_operand_size(*size)?))
+        }
+        Instruction::FarRet { size } => (
+            Instruction::FarRet { size: Size::Bit32 },
+            Switched(far_operand_size(*size)?),
+        ),
+        _ => return Err(EmitError::NotSupportedInCodeMode),
+    };
+    Ok(translated)
+}
+
+/// Maps 16-bit and 32-bit registers of instructions that take 64-bit registers in 64-bit code.
+fn gpr_to_bit64(gpr: GPR) -> Result<GPR, EmitError> {
+    match gpr.size() {
+        Size::Bit16 | Size::Bit32 => Ok(gpr.as_bit64()),
+        _ => Err(EmitError::OperandSizeMismatch),
+    }
+}
+
+fn far_operand_size(size: Size) -> Result<Size, EmitError> {
+    match size {
+        Size::Bit16 | Size::Bit32 => Ok(size),
+        _ => Err(EmitError::OperandSizeMismatch),
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(non_camel_case_types, clippy::cast_possible_wrap)]
-use crate::models::{Condition, Label};
+use crate::models::{AlignFill, Alignment, Condition, Label, LoopKind};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -23,6 +23,9 @@ impl FragmentOrderId {
 pub enum RelaxationVariant {
     Short,
     Long,
+
+    /// The long variant of 16-bit code, with rel16 instead of rel32.
+    Long16,
 }
 
 #[derive(Debug)]
@@ -40,6 +43,39 @@ pub enum Fragment {
         condition: Condition,
         label: Label,
     },
+
+    /// Instructions with rel8 form only. The long variants are
+    /// `op +2; jmp +N; jmp label`, where `N` is the length of the last jump.
+    Relaxable_CounterJump {
+        variant: RelaxationVariant,
+        kind: CounterJumpKind,
+        label: Label,
+    },
+
+    /// Padding to the `boundary`, recomputed during relaxation.
+    Align {
+        boundary: Alignment,
+        fill: AlignFill,
+        padding: i32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum CounterJumpKind {
+    Loop(LoopKind),
+    Jrcxz,
+}
+
+impl CounterJumpKind {
+    pub const fn opcode(self) -> u8 {
+        match self {
+            CounterJumpKind::Loop(LoopKind::LoopNotEqual) => 0xE0,
+            CounterJumpKind::Loop(LoopKind::LoopEqual) => 0xE1,
+            CounterJumpKind::Loop(LoopKind::Loop) => 0xE2,
+            CounterJumpKind::Jrcxz => 0xE3,
+        }
+    }
 }
 
 pub mod const_sizes {
@@ -67,6 +103,13 @@ pub mod const_sizes {
             .len() as i32
     };
 
+    pub const LONG16_JUMP: i32 = LONG_JUMP - 2;
+    pub const LONG16_COND_JUMP: i32 = LONG_COND_JUMP - 2;
+
+    pub const SHORT_COUNTER_JUMP: i32 = 2;
+    pub const LONG_COUNTER_JUMP: i32 = SHORT_COUNTER_JUMP + SHORT_JUMP + LONG_JUMP;
+    pub const LONG16_COUNTER_JUMP: i32 = SHORT_COUNTER_JUMP + SHORT_JUMP + LONG16_JUMP;
+
     const _CHECK: () = const {
         // The reason we do this checks is because perhaps in the future
         // we will overwrite the existing buffer with real instructions.
@@ -76,6 +119,7 @@ pub mod const_sizes {
         assert!(LONG_JUMP as usize <= size_of::<super::Fragment>());
         assert!(SHORT_COND_JUMP as usize <= size_of::<super::Fragment>());
         assert!(LONG_COND_JUMP as usize <= size_of::<super::Fragment>());
+        assert!(LONG_COUNTER_JUMP as usize <= size_of::<super::Fragment>());
     };
 }
 
@@ -103,11 +147,50 @@ impl Fragment {
             Fragment::Relaxable_Jump { variant, .. } => match variant {
                 RelaxationVariant::Short => const_sizes::SHORT_JUMP,
                 RelaxationVariant::Long => const_sizes::LONG_JUMP,
+                RelaxationVariant::Long16 => const_sizes::LONG16_JUMP,
             },
             Fragment::Relaxable_CondJump { variant, .. } => match variant {
                 RelaxationVariant::Short => const_sizes::SHORT_COND_JUMP,
                 RelaxationVariant::Long => const_sizes::LONG_COND_JUMP,
+                RelaxationVariant::Long16 => const_sizes::LONG16_COND_JUMP,
             },
+            Fragment::Relaxable_CounterJump { variant, .. } => match variant {
+                RelaxationVariant::Short => const_sizes::SHORT_COUNTER_JUMP,
+                RelaxationVariant::Long => const_sizes::LONG_COUNTER_JUMP,
+                RelaxationVariant::Long16 => const_sizes::LONG16_COUNTER_JUMP,
+            },
+            Fragment::Align { padding, .. } => *padding,
+        }
+    }
+
+    /// Returns the largest length the fragment can have after relaxation.
+    pub fn max_data_length(&self) -> i32 {
+        match self {
+            Fragment::Bytes { data_length, .. } => *data_length,
+            Fragment::Relaxable_Jump { .. } => const_sizes::LONG_JUMP,
+            Fragment::Relaxable_CondJump { .. } => const_sizes::LONG_COND_JUMP,
+            Fragment::Relaxable_CounterJump { .. } => const_sizes::LONG_COUNTER_JUMP,
+            Fragment::Align { boundary, .. } => boundary.value() as i32 - 1,
+        }
+    }
+
+    /// Returns the variant and the target of relaxable fragments.
+    pub fn relaxable_target_mut(&mut self) -> Option<(&mut RelaxationVariant, &Label)> {
+        match self {
+            Fragment::Relaxable_Jump { variant, label }
+            | Fragment::Relaxable_CondJump { variant, label, .. }
+            | Fragment::Relaxable_CounterJump { variant, label, .. } => Some((variant, label)),
+            Fragment::Bytes { .. } | Fragment::Align { .. } => None,
+        }
+    }
+
+    /// Returns the length of the short variant of relaxable fragments.
+    pub fn short_data_length(&self) -> Option<i32> {
+        match self {
+            Fragment::Relaxable_Jump { .. } => Some(const_sizes::SHORT_JUMP),
+            Fragment::Relaxable_CondJump { .. } => Some(const_sizes::SHORT_COND_JUMP),
+            Fragment::Relaxable_CounterJump { .. } => Some(const_sizes::SHORT_COUNTER_JUMP),
+            Fragment::Bytes { .. } | Fragment::Align { .. } => None,
         }
     }
 }
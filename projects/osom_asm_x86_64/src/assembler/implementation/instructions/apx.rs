@@ -0,0 +1,421 @@
+//! Intel APX encodings. The extended `R16` to `R31` registers are encoded
+//! with REX2 prefix, and the new data destination (NDD) forms with the
+//! extended EVEX prefix. All of these require APX to be enabled in the builder.
+#![allow(clippy::cast_sign_loss)]
+
+use crate::assembler::implementation::PatchKind;
+use crate::assembler::implementation::instructions::helpers::{emit_raw_instruction, update_trailing_imm_patch};
+use crate::assembler::implementation::instructions::raw_encoding::{
+    ApxEvex, RawRegister, RmOperand, VexPrefix, encode_apx_evex, encode_rex2, encode_rex2_opcode_register,
+};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{GPR, Immediate32, Immediate64, Label, Memory, Size};
+
+/// The opcodes shared by `mov` and the group-1 ALU instructions.
+#[derive(Debug, Clone, Copy)]
+pub struct AluOpcodes {
+    /// The `rm8, reg8` opcode, the `rm, reg`, `reg8, rm8` and `reg, rm`
+    /// opcodes follow it in this order.
+    base: u8,
+
+    /// The `rm8, imm8` opcode, followed by the `rm, imm` opcode.
+    imm: u8,
+
+    /// The sign extended `rm, imm8` opcode, if there is one.
+    imm8: Option<u8>,
+
+    /// The `ModRM.reg` extension of the immediate forms.
+    extension: u8,
+}
+
+pub const ALU_MOV: AluOpcodes = AluOpcodes {
+    base: 0x88,
+    imm: 0xC6,
+    imm8: None,
+    extension: 0,
+};
+pub const ALU_ADD: AluOpcodes = AluOpcodes {
+    base: 0x00,
+    imm: 0x80,
+    imm8: Some(0x83),
+    extension: 0,
+};
+pub const ALU_SUB: AluOpcodes = AluOpcodes {
+    base: 0x28,
+    imm: 0x80,
+    imm8: Some(0x83),
+    extension: 5,
+};
+pub const ALU_XOR: AluOpcodes = AluOpcodes {
+    base: 0x30,
+    imm: 0x80,
+    imm8: Some(0x83),
+    extension: 6,
+};
+pub const ALU_CMP: AluOpcodes = AluOpcodes {
+    base: 0x38,
+    imm: 0x80,
+    imm8: Some(0x83),
+    extension: 7,
+};
+
+const PREFIX_66: &[u8] = &[0x66];
+
+#[inline]
+fn ensure_apx_enabled(asm: &X86_64Assembler) -> Result<(), EmitError> {
+    if asm.with_apx {
+        Ok(())
+    } else {
+        Err(EmitError::ApxNotEnabled)
+    }
+}
+
+/// The operand size is selected by opcode (8-bit vs the rest),
+/// 0x66 prefix (16-bit) and `W` bit (64-bit).
+#[derive(Debug, Clone, Copy)]
+struct OperandSize {
+    is_8bit: bool,
+    is_16bit: bool,
+    w: bool,
+}
+
+impl OperandSize {
+    const fn new(size: Size) -> Self {
+        Self {
+            is_8bit: matches!(size, Size::Bit8),
+            is_16bit: matches!(size, Size::Bit16),
+            w: matches!(size, Size::Bit64),
+        }
+    }
+
+    const fn prefixes(self) -> &'static [u8] {
+        if self.is_16bit { PREFIX_66 } else { &[] }
+    }
+
+    const fn vex_prefix(self) -> VexPrefix {
+        if self.is_16bit { VexPrefix::P66 } else { VexPrefix::None }
+    }
+
+    /// Returns `opcode` for 8-bit operands and the following one otherwise.
+    const fn opcode(self, opcode: u8) -> u8 {
+        if self.is_8bit { opcode } else { opcode + 1 }
+    }
+}
+
+/// Returns the opcode and the little endian immediate bytes of the
+/// `rm, imm` form. The `size` is the size of the `rm` operand.
+fn encode_alu_immediate(op: AluOpcodes, size: Size, imm: Immediate32) -> Result<(u8, [u8; 4], usize), EmitError> {
+    let imm_size = imm.real_size();
+    if size < imm_size {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+
+    let bytes = imm.value().to_le_bytes();
+    let operand_size = OperandSize::new(size);
+    if operand_size.is_8bit {
+        return Ok((op.imm, bytes, 1));
+    }
+
+    if let Some(imm8) = op.imm8 {
+        if imm_size == Size::Bit8 {
+            return Ok((imm8, bytes, 1));
+        }
+    }
+
+    let length = if operand_size.is_16bit { 2 } else { 4 };
+    Ok((op.imm + 1, bytes, length))
+}
+
+fn check_same_size(first: GPR, second: GPR) -> Result<Size, EmitError> {
+    let size = first.size();
+    if size != second.size() {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+    Ok(size)
+}
+
+pub fn emit_alu_reg_reg_apx(asm: &mut X86_64Assembler, op: AluOpcodes, dst: GPR, src: GPR) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = OperandSize::new(check_same_size(dst, src)?);
+    let rm = RmOperand::Register(RawRegister::from_gpr(dst));
+    let instr = encode_rex2(
+        size.prefixes(),
+        size.w,
+        &[size.opcode(op.base)],
+        RawRegister::from_gpr(src),
+        &rm,
+        &[],
+    )?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_alu_reg_mem_apx(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src: &Memory,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = OperandSize::new(dst.size());
+    let rm = RmOperand::Memory(src);
+    let instr = encode_rex2(
+        size.prefixes(),
+        size.w,
+        &[size.opcode(op.base + 2)],
+        RawRegister::from_gpr(dst),
+        &rm,
+        &[],
+    )?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+pub fn emit_alu_mem_reg_apx(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: &Memory,
+    src: GPR,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = OperandSize::new(src.size());
+    let rm = RmOperand::Memory(dst);
+    let instr = encode_rex2(
+        size.prefixes(),
+        size.w,
+        &[size.opcode(op.base)],
+        RawRegister::from_gpr(src),
+        &rm,
+        &[],
+    )?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+pub fn emit_alu_reg_imm_apx(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src: Immediate32,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = dst.size();
+    let (opcode, imm, imm_length) = encode_alu_immediate(op, size, src)?;
+    let size = OperandSize::new(size);
+    let rm = RmOperand::Register(RawRegister::from_gpr(dst));
+    let instr = encode_rex2(
+        size.prefixes(),
+        size.w,
+        &[opcode],
+        RawRegister::new(op.extension),
+        &rm,
+        &imm[..imm_length],
+    )?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+/// Like the non-APX variant, the operand size is inferred from the immediate.
+pub fn emit_alu_mem_imm_apx(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: &Memory,
+    src: Immediate32,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let imm_size = src.real_size();
+    let size = OperandSize::new(imm_size);
+    let imm = src.value().to_le_bytes();
+    let imm_length = match imm_size {
+        Size::Bit8 => 1,
+        Size::Bit16 => 2,
+        Size::Bit32 | Size::Bit64 => 4,
+    };
+    let rm = RmOperand::Memory(dst);
+    let instr = encode_rex2(
+        size.prefixes(),
+        size.w,
+        &[size.opcode(op.imm)],
+        RawRegister::new(op.extension),
+        &rm,
+        &imm[..imm_length],
+    )?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+pub fn emit_mov_reg_imm_apx(asm: &mut X86_64Assembler, dst: GPR, src: Immediate32) -> Result<(), EmitError> {
+    const MOV_REG8_IMM8: u8 = 0xB0;
+    const MOV_REG_IMM: u8 = 0xB8;
+
+    ensure_apx_enabled(asm)?;
+    let imm = src.value().to_le_bytes();
+    let register = RawRegister::from_gpr(dst);
+    let instr = match dst.size() {
+        Size::Bit8 => encode_rex2_opcode_register(&[], false, MOV_REG8_IMM8, register, &imm[..1])?,
+        Size::Bit16 => encode_rex2_opcode_register(PREFIX_66, false, MOV_REG_IMM, register, &imm[..2])?,
+        Size::Bit32 => encode_rex2_opcode_register(&[], false, MOV_REG_IMM, register, &imm)?,
+        Size::Bit64 => {
+            // Sign extended 32-bit immediate is shorter than `movabs`.
+            let rm = RmOperand::Register(register);
+            encode_rex2(&[], true, &[ALU_MOV.imm + 1], RawRegister::new(0), &rm, &imm)?
+        }
+    };
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_mov_reg_imm64_apx(asm: &mut X86_64Assembler, dst: GPR, src: Immediate64) -> Result<(), EmitError> {
+    const MOVABS: u8 = 0xB8;
+
+    ensure_apx_enabled(asm)?;
+    let imm = src.value().to_le_bytes();
+    let instr = encode_rex2_opcode_register(&[], true, MOVABS, RawRegister::from_gpr(dst), &imm)?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+/// Emits `mov reg, imm` with the full sized immediate set to the address of `label`.
+pub fn emit_mov_reg_label_apx(
+    asm: &mut X86_64Assembler,
+    dst: GPR,
+    label: Label,
+    kind: PatchKind,
+) -> Result<(), EmitError> {
+    const MOV_REG_IMM: u8 = 0xB8;
+
+    ensure_apx_enabled(asm)?;
+    let rex_w = dst.size() == Size::Bit64;
+    let imm = [0; 8];
+    let imm = if rex_w { &imm[..] } else { &imm[..4] };
+    let instr = encode_rex2_opcode_register(&[], rex_w, MOV_REG_IMM, RawRegister::from_gpr(dst), imm)?;
+    update_trailing_imm_patch(asm, label, instr.as_slice(), imm.len(), kind);
+    asm._emit_bytes(instr.as_slice())
+}
+
+/// Emits instruction with 64-bit `rm` operand and opcode extension in `ModRM.reg`,
+/// e.g. `push [mem]` or `jmp reg`. The operand size is implied, so no REX2.W.
+pub fn emit_rm64_apx(asm: &mut X86_64Assembler, opcode: u8, extension: u8, rm: &RmOperand) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let instr = encode_rex2(&[], false, &[opcode], RawRegister::new(extension), rm, &[])?;
+    emit_raw_instruction(asm, rm, &instr)
+}
+
+/// Emits `push reg` or `pop reg` with the register embedded in the opcode.
+pub fn emit_push_pop_reg_apx(asm: &mut X86_64Assembler, opcode: u8, register: GPR) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let instr = encode_rex2_opcode_register(&[], false, opcode, RawRegister::from_gpr(register), &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+fn ndd_evex(size: OperandSize) -> ApxEvex {
+    ApxEvex {
+        prefix: size.vex_prefix(),
+        w: size.w,
+        nd: true,
+    }
+}
+
+pub fn emit_alu_ndd_reg_reg_reg(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src1: GPR,
+    src2: GPR,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = check_same_size(dst, src1)?;
+    if src2.size() != size {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+    let size = OperandSize::new(size);
+    let rm = RmOperand::Register(RawRegister::from_gpr(src1));
+    let instr = encode_apx_evex(
+        ndd_evex(size),
+        size.opcode(op.base),
+        RawRegister::from_gpr(src2),
+        RawRegister::from_gpr(dst),
+        &rm,
+        &[],
+    )?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_alu_ndd_reg_reg_mem(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src1: GPR,
+    src2: &Memory,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = OperandSize::new(check_same_size(dst, src1)?);
+    let rm = RmOperand::Memory(src2);
+    let instr = encode_apx_evex(
+        ndd_evex(size),
+        size.opcode(op.base + 2),
+        RawRegister::from_gpr(src1),
+        RawRegister::from_gpr(dst),
+        &rm,
+        &[],
+    )?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+pub fn emit_alu_ndd_reg_mem_reg(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src1: &Memory,
+    src2: GPR,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = OperandSize::new(check_same_size(dst, src2)?);
+    let rm = RmOperand::Memory(src1);
+    let instr = encode_apx_evex(
+        ndd_evex(size),
+        size.opcode(op.base),
+        RawRegister::from_gpr(src2),
+        RawRegister::from_gpr(dst),
+        &rm,
+        &[],
+    )?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+pub fn emit_alu_ndd_reg_reg_imm(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src1: GPR,
+    src2: Immediate32,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = check_same_size(dst, src1)?;
+    let (opcode, imm, imm_length) = encode_alu_immediate(op, size, src2)?;
+    let rm = RmOperand::Register(RawRegister::from_gpr(src1));
+    let instr = encode_apx_evex(
+        ndd_evex(OperandSize::new(size)),
+        opcode,
+        RawRegister::new(op.extension),
+        RawRegister::from_gpr(dst),
+        &rm,
+        &imm[..imm_length],
+    )?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_alu_ndd_reg_mem_imm(
+    asm: &mut X86_64Assembler,
+    op: AluOpcodes,
+    dst: GPR,
+    src1: &Memory,
+    src2: Immediate32,
+) -> Result<(), EmitError> {
+    ensure_apx_enabled(asm)?;
+    let size = dst.size();
+    let (opcode, imm, imm_length) = encode_alu_immediate(op, size, src2)?;
+    let rm = RmOperand::Memory(src1);
+    let instr = encode_apx_evex(
+        ndd_evex(OperandSize::new(size)),
+        opcode,
+        RawRegister::new(op.extension),
+        RawRegister::from_gpr(dst),
+        &rm,
+        &imm[..imm_length],
+    )?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
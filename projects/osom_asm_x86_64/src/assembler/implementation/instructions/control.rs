@@ -1,30 +1,85 @@
 use osom_encoders_x86_64::encoders as enc;
 use osom_encoders_x86_64::models as enc_models;
 
+use crate::assembler::implementation::instructions::apx;
 use crate::assembler::implementation::instructions::helpers::update_labeled_instruction;
+use crate::assembler::implementation::instructions::raw_encoding::{RawRegister, RmOperand};
+use crate::assembler::implementation::{CodeMode, PatchKind, PatchableImm32Instruction};
 use crate::{
     assembler::{EmitError, X86_64Assembler},
     models::{GPR, Label, Memory},
 };
 
 pub fn emit_jmp_reg(asm: &mut X86_64Assembler, dst: GPR) -> Result<(), EmitError> {
+    if dst.requires_apx() {
+        return apx::emit_rm64_apx(asm, 0xFF, 4, &RmOperand::Register(RawRegister::from_gpr(dst)));
+    }
+
     unsafe { asm._emit_encoded_instruction(enc::jmp::encode_jmp_rm64(dst.as_enc_gpr_or_mem())) }
 }
 
 pub fn emit_jmp_mem(asm: &mut X86_64Assembler, dst: &Memory) -> Result<(), EmitError> {
+    if dst.requires_apx() {
+        return apx::emit_rm64_apx(asm, 0xFF, 4, &RmOperand::Memory(dst));
+    }
+
     unsafe { asm._emit_encoded_instruction(enc::jmp::encode_jmp_rm64(dst.as_enc_gpr_or_mem())) }
 }
 
 pub fn emit_call_label(asm: &mut X86_64Assembler, dst: Label) -> Result<(), EmitError> {
     let instr = enc::call::encode_call_imm32(enc_models::Immediate32::from_i32(0));
-    update_labeled_instruction(asm, dst, &instr);
+    update_labeled_instruction(asm, dst, &instr, PatchKind::Relative);
     asm._emit_encoded_instruction(instr)
 }
 
+/// Emits relative call or jump `instr`, to be patched to reach `address`.
+fn emit_absolute_target(
+    asm: &mut X86_64Assembler,
+    address: u64,
+    instr: &enc_models::EncodedX86_64Instruction,
+) -> Result<(), EmitError> {
+    if asm.mode == CodeMode::Bit32 {
+        if asm.base_address.is_none() {
+            return Err(EmitError::BaseAddressNotSet);
+        }
+        if u32::try_from(address).is_err() {
+            return Err(EmitError::OperandSizeMismatch);
+        }
+    }
+
+    let instr_len = instr.as_slice().len() as u8;
+    let patchable_instruction = PatchableImm32Instruction {
+        instruction_position: asm._instruction_position(),
+        instruction_length: instr_len,
+        imm32_offset: instr_len - 4,
+        kind: PatchKind::Relative,
+    };
+    asm._push_absolute_target_instruction(address, patchable_instruction);
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_call_absolute(asm: &mut X86_64Assembler, address: u64) -> Result<(), EmitError> {
+    let instr = enc::call::encode_call_imm32(enc_models::Immediate32::from_i32(0));
+    emit_absolute_target(asm, address, &instr)
+}
+
+pub fn emit_jmp_absolute(asm: &mut X86_64Assembler, address: u64) -> Result<(), EmitError> {
+    let instr = enc::jmp::encode_jmp_imm32(enc_models::Immediate32::from_i32(0));
+    emit_absolute_target(asm, address, &instr)
+}
+
 pub fn emit_call_reg(asm: &mut X86_64Assembler, dst: GPR) -> Result<(), EmitError> {
+    if dst.requires_apx() {
+        return apx::emit_rm64_apx(asm, 0xFF, 2, &RmOperand::Register(RawRegister::from_gpr(dst)));
+    }
+
     unsafe { asm._emit_encoded_instruction(enc::call::encode_call_rm64(dst.as_enc_gpr_or_mem())) }
 }
 
 pub fn emit_call_mem(asm: &mut X86_64Assembler, dst: &Memory) -> Result<(), EmitError> {
+    if dst.requires_apx() {
+        return apx::emit_rm64_apx(asm, 0xFF, 2, &RmOperand::Memory(dst));
+    }
+
     unsafe { asm._emit_encoded_instruction(enc::call::encode_call_rm64(dst.as_enc_gpr_or_mem())) }
 }
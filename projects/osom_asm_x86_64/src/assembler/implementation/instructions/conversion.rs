@@ -0,0 +1,307 @@
+use crate::assembler::implementation::instructions::helpers::emit_raw_instruction;
+use crate::assembler::implementation::instructions::raw_encoding::{
+    OpcodeMap, RawRegister, RmOperand, Vex, VexPrefix, encode_legacy, encode_vex,
+};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{FloatType, GPR, Memory, RoundingMode, Size, XMM, YMM};
+
+const PREFIX_66: &[u8] = &[0x66];
+const PREFIX_F3: &[u8] = &[0xF3];
+const PREFIX_F2: &[u8] = &[0xF2];
+
+/// Returns REX.W for an integer operand of given size. Only
+/// 32-bit and 64-bit integers can be moved to/from XMM registers.
+fn rex_w_for(size: Size) -> Result<bool, EmitError> {
+    match size {
+        Size::Bit32 => Ok(false),
+        Size::Bit64 => Ok(true),
+        _ => Err(EmitError::OperandSizeMismatch),
+    }
+}
+
+/// Returns the mandatory prefix that selects between `ss` and `sd` forms.
+fn scalar_prefix(ty: FloatType) -> Result<&'static [u8], EmitError> {
+    match ty {
+        FloatType::ScalarSingle => Ok(PREFIX_F3),
+        FloatType::ScalarDouble => Ok(PREFIX_F2),
+        FloatType::PackedSingle | FloatType::PackedDouble => Err(EmitError::OperandSizeMismatch),
+    }
+}
+
+pub fn emit_movd_xmm_reg(asm: &mut X86_64Assembler, dst: XMM, src: GPR) -> Result<(), EmitError> {
+    const OPCODE: &[u8] = &[0x0F, 0x6E];
+    let rex_w = rex_w_for(src.size())?;
+    let src = RmOperand::Register(RawRegister::from_gpr(src));
+    let instr = encode_legacy(PREFIX_66, rex_w, OPCODE, RawRegister::from_xmm(dst), &src, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_movd_reg_xmm(asm: &mut X86_64Assembler, dst: GPR, src: XMM) -> Result<(), EmitError> {
+    const OPCODE: &[u8] = &[0x0F, 0x7E];
+    let rex_w = rex_w_for(dst.size())?;
+    let dst = RmOperand::Register(RawRegister::from_gpr(dst));
+    let instr = encode_legacy(PREFIX_66, rex_w, OPCODE, RawRegister::from_xmm(src), &dst, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+const CVTSI2S_OPCODE: &[u8] = &[0x0F, 0x2A];
+
+pub fn emit_cvt_int_to_float_xmm_reg(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    dst: XMM,
+    src: GPR,
+) -> Result<(), EmitError> {
+    let prefix = scalar_prefix(ty)?;
+    let rex_w = rex_w_for(src.size())?;
+    let src = RmOperand::Register(RawRegister::from_gpr(src));
+    let instr = encode_legacy(prefix, rex_w, CVTSI2S_OPCODE, RawRegister::from_xmm(dst), &src, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_cvt_int_to_float_xmm_mem(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    dst: XMM,
+    src: &Memory,
+    size: Size,
+) -> Result<(), EmitError> {
+    let prefix = scalar_prefix(ty)?;
+    let rex_w = rex_w_for(size)?;
+    let src = RmOperand::Memory(src);
+    let instr = encode_legacy(prefix, rex_w, CVTSI2S_OPCODE, RawRegister::from_xmm(dst), &src, &[])?;
+    emit_raw_instruction(asm, &src, &instr)
+}
+
+const fn cvt_float_to_int_opcode(truncate: bool) -> &'static [u8] {
+    if truncate { &[0x0F, 0x2C] } else { &[0x0F, 0x2D] }
+}
+
+pub fn emit_cvt_float_to_int_reg_xmm(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    truncate: bool,
+    dst: GPR,
+    src: XMM,
+) -> Result<(), EmitError> {
+    let prefix = scalar_prefix(ty)?;
+    let rex_w = rex_w_for(dst.size())?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    let opcode = cvt_float_to_int_opcode(truncate);
+    let instr = encode_legacy(prefix, rex_w, opcode, RawRegister::from_gpr(dst), &src, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_cvt_float_to_int_reg_mem(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    truncate: bool,
+    dst: GPR,
+    src: &Memory,
+) -> Result<(), EmitError> {
+    let prefix = scalar_prefix(ty)?;
+    let rex_w = rex_w_for(dst.size())?;
+    let src = RmOperand::Memory(src);
+    let opcode = cvt_float_to_int_opcode(truncate);
+    let instr = encode_legacy(prefix, rex_w, opcode, RawRegister::from_gpr(dst), &src, &[])?;
+    emit_raw_instruction(asm, &src, &instr)
+}
+
+/// Emits `cvtss2sd` or `cvtsd2ss`, depending on the `src` type.
+fn emit_cvt_float_to_float(
+    asm: &mut X86_64Assembler,
+    src_ty: FloatType,
+    dst: XMM,
+    src: &RmOperand,
+) -> Result<(), EmitError> {
+    const OPCODE: &[u8] = &[0x0F, 0x5A];
+    let prefix = scalar_prefix(src_ty)?;
+    let instr = encode_legacy(prefix, false, OPCODE, RawRegister::from_xmm(dst), src, &[])?;
+    emit_raw_instruction(asm, src, &instr)
+}
+
+pub fn emit_cvtss2sd_xmm_xmm(asm: &mut X86_64Assembler, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_cvt_float_to_float(asm, FloatType::ScalarSingle, dst, &src)
+}
+
+pub fn emit_cvtss2sd_xmm_mem(asm: &mut X86_64Assembler, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    emit_cvt_float_to_float(asm, FloatType::ScalarSingle, dst, &RmOperand::Memory(src))
+}
+
+pub fn emit_cvtsd2ss_xmm_xmm(asm: &mut X86_64Assembler, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_cvt_float_to_float(asm, FloatType::ScalarDouble, dst, &src)
+}
+
+pub fn emit_cvtsd2ss_xmm_mem(asm: &mut X86_64Assembler, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    emit_cvt_float_to_float(asm, FloatType::ScalarDouble, dst, &RmOperand::Memory(src))
+}
+
+fn emit_round(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    mode: RoundingMode,
+    dst: XMM,
+    src: &RmOperand,
+) -> Result<(), EmitError> {
+    let opcode: &[u8] = match ty {
+        FloatType::PackedSingle => &[0x0F, 0x3A, 0x08],
+        FloatType::PackedDouble => &[0x0F, 0x3A, 0x09],
+        FloatType::ScalarSingle => &[0x0F, 0x3A, 0x0A],
+        FloatType::ScalarDouble => &[0x0F, 0x3A, 0x0B],
+    };
+    let instr = encode_legacy(
+        PREFIX_66,
+        false,
+        opcode,
+        RawRegister::from_xmm(dst),
+        src,
+        &[mode.as_u8()],
+    )?;
+    emit_raw_instruction(asm, src, &instr)
+}
+
+pub fn emit_round_xmm_xmm(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    mode: RoundingMode,
+    dst: XMM,
+    src: XMM,
+) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_round(asm, ty, mode, dst, &src)
+}
+
+pub fn emit_round_xmm_mem(
+    asm: &mut X86_64Assembler,
+    ty: FloatType,
+    mode: RoundingMode,
+    dst: XMM,
+    src: &Memory,
+) -> Result<(), EmitError> {
+    emit_round(asm, ty, mode, dst, &RmOperand::Memory(src))
+}
+
+/// F16C instructions don't use `VEX.vvvv`, both are `VEX.66.W0`.
+fn emit_f16c(
+    asm: &mut X86_64Assembler,
+    map: OpcodeMap,
+    opcode: u8,
+    is_256: bool,
+    reg: RawRegister,
+    rm: &RmOperand,
+    immediate: &[u8],
+) -> Result<(), EmitError> {
+    let vex = Vex {
+        prefix: VexPrefix::P66,
+        map,
+        w: false,
+        l: is_256,
+    };
+    let instr = encode_vex(vex, opcode, reg, 0, rm, immediate)?;
+    emit_raw_instruction(asm, rm, &instr)
+}
+
+const VCVTPH2PS_OPCODE: u8 = 0x13;
+const VCVTPS2PH_OPCODE: u8 = 0x1D;
+
+pub fn emit_vcvtph2ps_xmm_xmm(asm: &mut X86_64Assembler, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    let dst = RawRegister::from_xmm(dst);
+    emit_f16c(asm, OpcodeMap::Map0F38, VCVTPH2PS_OPCODE, false, dst, &src, &[])
+}
+
+pub fn emit_vcvtph2ps_xmm_mem(asm: &mut X86_64Assembler, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    let src = RmOperand::Memory(src);
+    let dst = RawRegister::from_xmm(dst);
+    emit_f16c(asm, OpcodeMap::Map0F38, VCVTPH2PS_OPCODE, false, dst, &src, &[])
+}
+
+pub fn emit_vcvtph2ps_ymm_xmm(asm: &mut X86_64Assembler, dst: YMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    let dst = RawRegister::from_ymm(dst);
+    emit_f16c(asm, OpcodeMap::Map0F38, VCVTPH2PS_OPCODE, true, dst, &src, &[])
+}
+
+pub fn emit_vcvtph2ps_ymm_mem(asm: &mut X86_64Assembler, dst: YMM, src: &Memory) -> Result<(), EmitError> {
+    let src = RmOperand::Memory(src);
+    let dst = RawRegister::from_ymm(dst);
+    emit_f16c(asm, OpcodeMap::Map0F38, VCVTPH2PS_OPCODE, true, dst, &src, &[])
+}
+
+pub fn emit_vcvtps2ph_xmm_xmm(
+    asm: &mut X86_64Assembler,
+    mode: RoundingMode,
+    dst: XMM,
+    src: XMM,
+) -> Result<(), EmitError> {
+    let dst = RmOperand::Register(RawRegister::from_xmm(dst));
+    let src = RawRegister::from_xmm(src);
+    emit_f16c(
+        asm,
+        OpcodeMap::Map0F3A,
+        VCVTPS2PH_OPCODE,
+        false,
+        src,
+        &dst,
+        &[mode.as_u8()],
+    )
+}
+
+pub fn emit_vcvtps2ph_mem_xmm(
+    asm: &mut X86_64Assembler,
+    mode: RoundingMode,
+    dst: &Memory,
+    src: XMM,
+) -> Result<(), EmitError> {
+    let dst = RmOperand::Memory(dst);
+    let src = RawRegister::from_xmm(src);
+    emit_f16c(
+        asm,
+        OpcodeMap::Map0F3A,
+        VCVTPS2PH_OPCODE,
+        false,
+        src,
+        &dst,
+        &[mode.as_u8()],
+    )
+}
+
+pub fn emit_vcvtps2ph_xmm_ymm(
+    asm: &mut X86_64Assembler,
+    mode: RoundingMode,
+    dst: XMM,
+    src: YMM,
+) -> Result<(), EmitError> {
+    let dst = RmOperand::Register(RawRegister::from_xmm(dst));
+    let src = RawRegister::from_ymm(src);
+    emit_f16c(
+        asm,
+        OpcodeMap::Map0F3A,
+        VCVTPS2PH_OPCODE,
+        true,
+        src,
+        &dst,
+        &[mode.as_u8()],
+    )
+}
+
+pub fn emit_vcvtps2ph_mem_ymm(
+    asm: &mut X86_64Assembler,
+    mode: RoundingMode,
+    dst: &Memory,
+    src: YMM,
+) -> Result<(), EmitError> {
+    let dst = RmOperand::Memory(dst);
+    let src = RawRegister::from_ymm(src);
+    emit_f16c(
+        asm,
+        OpcodeMap::Map0F3A,
+        VCVTPS2PH_OPCODE,
+        true,
+        src,
+        &dst,
+        &[mode.as_u8()],
+    )
+}
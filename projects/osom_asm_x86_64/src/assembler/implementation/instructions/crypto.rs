@@ -0,0 +1,181 @@
+use crate::assembler::implementation::instructions::helpers::{
+    emit_raw_instruction, immediate_u8, update_patchable_info_raw,
+};
+use crate::assembler::implementation::instructions::raw_encoding::{RawRegister, RmOperand, encode_legacy};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{AesKind, GPR, Immediate32, Memory, ShaKind, Size, XMM};
+
+const PREFIX_66: &[u8] = &[0x66];
+const PREFIX_F2: &[u8] = &[0xF2];
+const PREFIX_66_F2: &[u8] = &[0x66, 0xF2];
+
+/// Emits SSE-like instruction with `xmm` destination and `xmm/m128` source.
+fn emit_xmm_rm(
+    asm: &mut X86_64Assembler,
+    prefixes: &[u8],
+    opcode: &[u8],
+    dst: XMM,
+    src: &RmOperand,
+    immediate: &[u8],
+) -> Result<(), EmitError> {
+    let instr = encode_legacy(prefixes, false, opcode, RawRegister::from_xmm(dst), src, immediate)?;
+    emit_raw_instruction(asm, src, &instr)
+}
+
+const fn aes_opcode(kind: AesKind) -> &'static [u8] {
+    match kind {
+        AesKind::Encrypt => &[0x0F, 0x38, 0xDC],
+        AesKind::EncryptLast => &[0x0F, 0x38, 0xDD],
+        AesKind::Decrypt => &[0x0F, 0x38, 0xDE],
+        AesKind::DecryptLast => &[0x0F, 0x38, 0xDF],
+    }
+}
+
+const AESIMC_OPCODE: &[u8] = &[0x0F, 0x38, 0xDB];
+const AESKEYGENASSIST_OPCODE: &[u8] = &[0x0F, 0x3A, 0xDF];
+const PCLMULQDQ_OPCODE: &[u8] = &[0x0F, 0x3A, 0x44];
+const SHA1RNDS4_OPCODE: &[u8] = &[0x0F, 0x3A, 0xCC];
+
+const fn sha_opcode(kind: ShaKind) -> &'static [u8] {
+    match kind {
+        ShaKind::Sha1NextE => &[0x0F, 0x38, 0xC8],
+        ShaKind::Sha1Msg1 => &[0x0F, 0x38, 0xC9],
+        ShaKind::Sha1Msg2 => &[0x0F, 0x38, 0xCA],
+        ShaKind::Sha256Rnds2 => &[0x0F, 0x38, 0xCB],
+        ShaKind::Sha256Msg1 => &[0x0F, 0x38, 0xCC],
+        ShaKind::Sha256Msg2 => &[0x0F, 0x38, 0xCD],
+    }
+}
+
+pub fn emit_aes_xmm_xmm(asm: &mut X86_64Assembler, kind: AesKind, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, PREFIX_66, aes_opcode(kind), dst, &src, &[])
+}
+
+pub fn emit_aes_xmm_mem(asm: &mut X86_64Assembler, kind: AesKind, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    emit_xmm_rm(asm, PREFIX_66, aes_opcode(kind), dst, &RmOperand::Memory(src), &[])
+}
+
+pub fn emit_aesimc_xmm_xmm(asm: &mut X86_64Assembler, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, PREFIX_66, AESIMC_OPCODE, dst, &src, &[])
+}
+
+pub fn emit_aesimc_xmm_mem(asm: &mut X86_64Assembler, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    emit_xmm_rm(asm, PREFIX_66, AESIMC_OPCODE, dst, &RmOperand::Memory(src), &[])
+}
+
+pub fn emit_aeskeygenassist_xmm_xmm_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, PREFIX_66, AESKEYGENASSIST_OPCODE, dst, &src, &[imm])
+}
+
+pub fn emit_aeskeygenassist_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    emit_xmm_rm(
+        asm,
+        PREFIX_66,
+        AESKEYGENASSIST_OPCODE,
+        dst,
+        &RmOperand::Memory(src),
+        &[imm],
+    )
+}
+
+pub fn emit_pclmulqdq_xmm_xmm_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, PREFIX_66, PCLMULQDQ_OPCODE, dst, &src, &[imm])
+}
+
+pub fn emit_pclmulqdq_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    emit_xmm_rm(asm, PREFIX_66, PCLMULQDQ_OPCODE, dst, &RmOperand::Memory(src), &[imm])
+}
+
+pub fn emit_sha_xmm_xmm(asm: &mut X86_64Assembler, kind: ShaKind, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, &[], sha_opcode(kind), dst, &src, &[])
+}
+
+pub fn emit_sha_xmm_mem(asm: &mut X86_64Assembler, kind: ShaKind, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    emit_xmm_rm(asm, &[], sha_opcode(kind), dst, &RmOperand::Memory(src), &[])
+}
+
+pub fn emit_sha1rnds4_xmm_xmm_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, 3)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, &[], SHA1RNDS4_OPCODE, dst, &src, &[imm])
+}
+
+pub fn emit_sha1rnds4_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, 3)?;
+    emit_xmm_rm(asm, &[], SHA1RNDS4_OPCODE, dst, &RmOperand::Memory(src), &[imm])
+}
+
+/// Validates `crc32` operand sizes and returns prefixes, REX.W and opcode.
+fn crc32_encoding(dst: GPR, src_size: Size) -> Result<(&'static [u8], bool, &'static [u8]), EmitError> {
+    const OPCODE_8: &[u8] = &[0x0F, 0x38, 0xF0];
+    const OPCODE: &[u8] = &[0x0F, 0x38, 0xF1];
+
+    match (dst.size(), src_size) {
+        (Size::Bit32, Size::Bit8) => Ok((PREFIX_F2, false, OPCODE_8)),
+        (Size::Bit32, Size::Bit16) => Ok((PREFIX_66_F2, false, OPCODE)),
+        (Size::Bit32, Size::Bit32) => Ok((PREFIX_F2, false, OPCODE)),
+        (Size::Bit64, Size::Bit8) => Ok((PREFIX_F2, true, OPCODE_8)),
+        (Size::Bit64, Size::Bit64) => Ok((PREFIX_F2, true, OPCODE)),
+        _ => Err(EmitError::OperandSizeMismatch),
+    }
+}
+
+pub fn emit_crc32_reg_reg(asm: &mut X86_64Assembler, dst: GPR, src: GPR) -> Result<(), EmitError> {
+    let (prefixes, rex_w, opcode) = crc32_encoding(dst, src.size())?;
+    let src = RmOperand::Register(RawRegister::from_gpr(src));
+    let instr = encode_legacy(prefixes, rex_w, opcode, RawRegister::from_gpr(dst), &src, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_crc32_reg_mem(asm: &mut X86_64Assembler, dst: GPR, src: &Memory, size: Size) -> Result<(), EmitError> {
+    let (prefixes, rex_w, opcode) = crc32_encoding(dst, size)?;
+    let instr = encode_legacy(
+        prefixes,
+        rex_w,
+        opcode,
+        RawRegister::from_gpr(dst),
+        &RmOperand::Memory(src),
+        &[],
+    )?;
+    update_patchable_info_raw(asm, src, &instr);
+    asm._emit_bytes(instr.as_slice())
+}
@@ -0,0 +1,135 @@
+use crate::assembler::implementation::instructions::helpers::update_patchable_info_raw;
+use crate::assembler::implementation::instructions::raw_encoding::{
+    OpcodeMap, RawInstruction, RawRegister, RmOperand, Vex, VexPrefix, encode_vex,
+};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{FloatType, FmaKind, FmaOrder, Memory, XMM, YMM};
+
+/// All FMA3 instructions live in `VEX.66.0F38` map. The opcode
+/// is composed from the order base, kind offset and scalar bit.
+fn encode_fma(
+    kind: FmaKind,
+    order: FmaOrder,
+    ty: FloatType,
+    is_256: bool,
+    dst: RawRegister,
+    src1: RawRegister,
+    src2: &RmOperand,
+) -> Result<RawInstruction, EmitError> {
+    let order_base: u8 = match order {
+        FmaOrder::Order132 => 0x98,
+        FmaOrder::Order213 => 0xA8,
+        FmaOrder::Order231 => 0xB8,
+    };
+    let kind_offset: u8 = match kind {
+        FmaKind::MulAdd => 0,
+        FmaKind::MulSub => 2,
+        FmaKind::NegMulAdd => 4,
+        FmaKind::NegMulSub => 6,
+    };
+    let opcode = order_base + kind_offset + u8::from(ty.is_scalar());
+
+    let vex = Vex {
+        prefix: VexPrefix::P66,
+        map: OpcodeMap::Map0F38,
+        w: ty.is_double(),
+        l: is_256,
+    };
+    encode_vex(vex, opcode, dst, src1.index(), src2, &[])
+}
+
+pub fn emit_fma_xmm_xmm_xmm(
+    asm: &mut X86_64Assembler,
+    kind: FmaKind,
+    order: FmaOrder,
+    ty: FloatType,
+    dst: XMM,
+    src1: XMM,
+    src2: XMM,
+) -> Result<(), EmitError> {
+    let src2 = RmOperand::Register(RawRegister::from_xmm(src2));
+    let instr = encode_fma(
+        kind,
+        order,
+        ty,
+        false,
+        RawRegister::from_xmm(dst),
+        RawRegister::from_xmm(src1),
+        &src2,
+    )?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_fma_xmm_xmm_mem(
+    asm: &mut X86_64Assembler,
+    kind: FmaKind,
+    order: FmaOrder,
+    ty: FloatType,
+    dst: XMM,
+    src1: XMM,
+    src2: &Memory,
+) -> Result<(), EmitError> {
+    let instr = encode_fma(
+        kind,
+        order,
+        ty,
+        false,
+        RawRegister::from_xmm(dst),
+        RawRegister::from_xmm(src1),
+        &RmOperand::Memory(src2),
+    )?;
+    update_patchable_info_raw(asm, src2, &instr);
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_fma_ymm_ymm_ymm(
+    asm: &mut X86_64Assembler,
+    kind: FmaKind,
+    order: FmaOrder,
+    ty: FloatType,
+    dst: YMM,
+    src1: YMM,
+    src2: YMM,
+) -> Result<(), EmitError> {
+    if ty.is_scalar() {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+
+    let src2 = RmOperand::Register(RawRegister::from_ymm(src2));
+    let instr = encode_fma(
+        kind,
+        order,
+        ty,
+        true,
+        RawRegister::from_ymm(dst),
+        RawRegister::from_ymm(src1),
+        &src2,
+    )?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_fma_ymm_ymm_mem(
+    asm: &mut X86_64Assembler,
+    kind: FmaKind,
+    order: FmaOrder,
+    ty: FloatType,
+    dst: YMM,
+    src1: YMM,
+    src2: &Memory,
+) -> Result<(), EmitError> {
+    if ty.is_scalar() {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+
+    let instr = encode_fma(
+        kind,
+        order,
+        ty,
+        true,
+        RawRegister::from_ymm(dst),
+        RawRegister::from_ymm(src1),
+        &RmOperand::Memory(src2),
+    )?;
+    update_patchable_info_raw(asm, src2, &instr);
+    asm._emit_bytes(instr.as_slice())
+}
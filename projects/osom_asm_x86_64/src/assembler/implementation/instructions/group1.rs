@@ -2,3 +2,7 @@ super::macros::generate_group1_fn!(cmp);
 super::macros::generate_group1_fn!(xor);
 super::macros::generate_group1_fn!(add);
 super::macros::generate_group1_fn!(sub);
+
+super::macros::generate_fn_emit_ndd!(xor);
+super::macros::generate_fn_emit_ndd!(add);
+super::macros::generate_fn_emit_ndd!(sub);
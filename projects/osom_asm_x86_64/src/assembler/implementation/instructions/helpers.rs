@@ -1,28 +1,50 @@
 use osom_encoders_x86_64::models as enc_models;
 
-use crate::assembler::X86_64Assembler;
-use crate::assembler::implementation::PatchableImm32Instruction;
+use crate::assembler::implementation::instructions::raw_encoding::{RawInstruction, RmOperand};
+use crate::assembler::implementation::{PatchKind, PatchableImm32Instruction};
+use crate::assembler::{EmitError, X86_64Assembler};
 use crate::models::{Immediate32, Label, Memory, Size};
 
 pub fn update_labeled_instruction(
     asm: &mut X86_64Assembler,
     label: Label,
     instr: &enc_models::EncodedX86_64Instruction,
+    kind: PatchKind,
 ) {
-    let position = asm._current_position();
+    let position = asm._instruction_position();
     let instr_len = instr.as_slice().len() as u8;
     debug_assert!(instr_len >= 4, "Instruction length is too short");
     let patchable_instruction = PatchableImm32Instruction {
         instruction_position: position,
         instruction_length: instr_len,
         imm32_offset: instr_len - 4,
+        kind,
+    };
+    asm._push_patchable_instruction(label, patchable_instruction);
+}
+
+/// Registers patch of the immediate of `imm_length` bytes, that ends the instruction.
+pub fn update_trailing_imm_patch(
+    asm: &mut X86_64Assembler,
+    label: Label,
+    instr: &[u8],
+    imm_length: usize,
+    kind: PatchKind,
+) {
+    debug_assert!(instr.len() >= imm_length, "Instruction length is too short");
+    let patchable_instruction = PatchableImm32Instruction {
+        instruction_position: asm._instruction_position(),
+        instruction_length: instr.len() as u8,
+        imm32_offset: (instr.len() - imm_length) as u8,
+        kind,
     };
     asm._push_patchable_instruction(label, patchable_instruction);
 }
 
 pub fn update_patchable_info(asm: &mut X86_64Assembler, src: &Memory, instr: &enc_models::EncodedX86_64Instruction) {
     if let Some(label) = src.get_label() {
-        update_labeled_instruction(asm, *label, instr);
+        let kind = asm._memory_patch_kind();
+        update_labeled_instruction(asm, *label, instr, kind);
     }
 }
 
@@ -33,7 +55,7 @@ pub fn update_patchable_info_with_imm(
     imm: Immediate32,
 ) {
     if let Some(label) = src.get_label() {
-        let position = asm._current_position();
+        let position = asm._instruction_position();
         let instr_len = instr.as_slice().len() as u8;
         let offset = match imm.real_size() {
             Size::Bit8 => 1,
@@ -48,7 +70,47 @@ pub fn update_patchable_info_with_imm(
             instruction_position: position,
             instruction_length: instr_len,
             imm32_offset: instr_len - final_offset,
+            kind: asm._memory_patch_kind(),
+        };
+        asm._push_patchable_instruction(*label, patchable_instruction);
+    }
+}
+
+pub fn update_patchable_info_raw(asm: &mut X86_64Assembler, src: &Memory, instr: &RawInstruction) {
+    if let Some(label) = src.get_label() {
+        let position = asm._instruction_position();
+        let imm32_offset = instr
+            .displacement_offset()
+            .expect("Label memory operand has to produce RIP-relative displacement.");
+        let patchable_instruction = PatchableImm32Instruction {
+            instruction_position: position,
+            instruction_length: instr.as_slice().len() as u8,
+            imm32_offset,
+            kind: asm._memory_patch_kind(),
         };
         asm._push_patchable_instruction(*label, patchable_instruction);
     }
 }
+
+/// Emits the raw instruction, registering label patch if `rm` is a labeled memory.
+pub fn emit_raw_instruction(
+    asm: &mut X86_64Assembler,
+    rm: &RmOperand,
+    instr: &RawInstruction,
+) -> Result<(), EmitError> {
+    if let RmOperand::Memory(memory) = rm {
+        update_patchable_info_raw(asm, memory, instr);
+    }
+    asm._emit_bytes(instr.as_slice())
+}
+
+/// Converts `imm` to an 8-bit immediate, checking that it is in `0..=max` range.
+pub fn immediate_u8(imm: Immediate32, max: u8) -> Result<u8, EmitError> {
+    let value = imm.value();
+    if !(0..=i32::from(max)).contains(&value) {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    Ok(value as u8)
+}
@@ -0,0 +1,90 @@
+use crate::assembler::implementation::instructions::helpers::emit_raw_instruction;
+use crate::assembler::implementation::instructions::raw_encoding::{RawRegister, RmOperand, encode_legacy};
+use crate::assembler::implementation::{CodeMode, PatchKind, PatchableImm32Instruction};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{GPR, Immediate32, Label, Memory, Scale, Size};
+
+const LEA_OPCODE: &[u8] = &[0x8D];
+const MOVSXD_OPCODE: &[u8] = &[0x63];
+const ADD_OPCODE: &[u8] = &[0x01];
+const JMP_OPCODE: &[u8] = &[0xFF];
+const JMP_EXTENSION: u8 = 4;
+
+/// Emits a placeholder that is patched with the `kind` value of `label`.
+fn emit_label_data(asm: &mut X86_64Assembler, label: Label, kind: PatchKind) -> Result<(), EmitError> {
+    let placeholder = [0; 8];
+    let placeholder = match kind {
+        PatchKind::Absolute64 => &placeholder[..],
+        _ => &placeholder[..4],
+    };
+    let patchable_instruction = PatchableImm32Instruction {
+        instruction_position: asm._instruction_position(),
+        instruction_length: placeholder.len() as u8,
+        imm32_offset: 0,
+        kind,
+    };
+    asm._push_patchable_instruction(label, patchable_instruction);
+    asm._emit_raw_bytes(placeholder)
+}
+
+pub fn emit_label_difference32(asm: &mut X86_64Assembler, target: Label, base: Label) -> Result<(), EmitError> {
+    emit_label_data(asm, target, PatchKind::Difference(base))
+}
+
+pub fn emit_label_address32(asm: &mut X86_64Assembler, label: Label) -> Result<(), EmitError> {
+    emit_label_data(asm, label, PatchKind::Absolute)
+}
+
+pub fn emit_label_address64(asm: &mut X86_64Assembler, label: Label) -> Result<(), EmitError> {
+    emit_label_data(asm, label, PatchKind::Absolute64)
+}
+
+pub fn emit_jump_table_dispatch(
+    asm: &mut X86_64Assembler,
+    table: Label,
+    index: GPR,
+    base: GPR,
+) -> Result<(), EmitError> {
+    if asm.mode != CodeMode::Bit64 {
+        return Err(EmitError::NotSupportedInCodeMode);
+    }
+    if index.size() != Size::Bit64 || base.size() != Size::Bit64 {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+    if index == base {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    let raw_index = RawRegister::from_gpr(index);
+    let raw_base = RawRegister::from_gpr(base);
+
+    let table_memory = Memory::label(table);
+    let table_operand = RmOperand::Memory(&table_memory);
+    let lea = encode_legacy(&[], true, LEA_OPCODE, raw_base, &table_operand, &[])?;
+    emit_raw_instruction(asm, &table_operand, &lea)?;
+
+    let entry_memory = Memory::based_scaled(base, index, Scale::Scale4, Immediate32::ZERO)
+        .map_err(|_| EmitError::IncompatibleOperands)?;
+    let movsxd = encode_legacy(
+        &[],
+        true,
+        MOVSXD_OPCODE,
+        raw_index,
+        &RmOperand::Memory(&entry_memory),
+        &[],
+    )?;
+    asm._emit_bytes(movsxd.as_slice())?;
+
+    let add = encode_legacy(&[], true, ADD_OPCODE, raw_index, &RmOperand::Register(raw_base), &[])?;
+    asm._emit_bytes(add.as_slice())?;
+
+    let jmp = encode_legacy(
+        &[],
+        false,
+        JMP_OPCODE,
+        RawRegister::new(JMP_EXTENSION),
+        &RmOperand::Register(raw_base),
+        &[],
+    )?;
+    asm._emit_bytes(jmp.as_slice())
+}
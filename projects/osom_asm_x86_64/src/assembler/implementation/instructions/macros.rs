@@ -5,6 +5,11 @@ macro_rules! generate_fn_emit_reg_imm {
                 use osom_encoders_x86_64::encoders as enc;
                 use osom_encoders_x86_64::models as enc_models;
                 use crate::models::{GPR, Size};
+                use crate::assembler::implementation::instructions::apx;
+
+                if dst.requires_apx() {
+                    return apx::emit_alu_reg_imm_apx(asm, apx::[<ALU_ $name:upper>], dst, src);
+                }
 
                 unsafe {
                     let dst_mem = dst.as_enc_gpr_or_mem();
@@ -92,6 +97,11 @@ macro_rules! generate_fn_emit_reg_reg {
             pub fn [<emit_ $name _reg_reg>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src: crate::models::GPR) -> Result<(), crate::assembler::EmitError> {
                 use osom_encoders_x86_64::encoders as enc;
                 use crate::models::Size;
+                use crate::assembler::implementation::instructions::apx;
+
+                if dst.requires_apx() || src.requires_apx() {
+                    return apx::emit_alu_reg_reg_apx(asm, apx::[<ALU_ $name:upper>], dst, src);
+                }
 
                 unsafe {
                     let size = dst.size();
@@ -130,7 +140,11 @@ macro_rules! generate_fn_emit_reg_mem {
             pub fn [<emit_ $name _reg_mem>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src: &crate::models::Memory) -> Result<(), crate::assembler::EmitError> {
                 use osom_encoders_x86_64::encoders as enc;
                 use crate::models::Size;
-                use crate::assembler::implementation::instructions::helpers;
+                use crate::assembler::implementation::instructions::{apx, helpers};
+
+                if dst.requires_apx() || src.requires_apx() {
+                    return apx::emit_alu_reg_mem_apx(asm, apx::[<ALU_ $name:upper>], dst, src);
+                }
 
                 unsafe {
                     let mem = src.as_enc_gpr_or_mem();
@@ -161,7 +175,11 @@ macro_rules! generate_fn_emit_mem_reg {
             pub fn [<emit_ $name _mem_reg>](asm: &mut crate::assembler::X86_64Assembler, dst: &crate::models::Memory, src: crate::models::GPR) -> Result<(), crate::assembler::EmitError> {
                 use osom_encoders_x86_64::encoders as enc;
                 use crate::models::Size;
-                use crate::assembler::implementation::instructions::helpers;
+                use crate::assembler::implementation::instructions::{apx, helpers};
+
+                if dst.requires_apx() || src.requires_apx() {
+                    return apx::emit_alu_mem_reg_apx(asm, apx::[<ALU_ $name:upper>], dst, src);
+                }
 
                 unsafe {
                     let mem = dst.as_enc_gpr_or_mem();
@@ -193,7 +211,11 @@ macro_rules! generate_fn_emit_mem_imm {
                 use osom_encoders_x86_64::encoders as enc;
                 use osom_encoders_x86_64::models as enc_models;
                 use crate::models::Size;
-                use crate::assembler::implementation::instructions::helpers;
+                use crate::assembler::implementation::instructions::{apx, helpers};
+
+                if dst.requires_apx() {
+                    return apx::emit_alu_mem_imm_apx(asm, apx::[<ALU_ $name:upper>], dst, src);
+                }
 
                 unsafe {
                     let mem = dst.as_enc_gpr_or_mem();
@@ -239,3 +261,36 @@ macro_rules! generate_group1_fn {
 }
 
 pub(crate) use generate_group1_fn;
+
+macro_rules! generate_fn_emit_ndd {
+    ($name:ident) => {
+        paste::paste! {
+            pub fn [<emit_ $name _reg_reg_reg>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src1: crate::models::GPR, src2: crate::models::GPR) -> Result<(), crate::assembler::EmitError> {
+                use crate::assembler::implementation::instructions::apx;
+                apx::emit_alu_ndd_reg_reg_reg(asm, apx::[<ALU_ $name:upper>], dst, src1, src2)
+            }
+
+            pub fn [<emit_ $name _reg_reg_mem>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src1: crate::models::GPR, src2: &crate::models::Memory) -> Result<(), crate::assembler::EmitError> {
+                use crate::assembler::implementation::instructions::apx;
+                apx::emit_alu_ndd_reg_reg_mem(asm, apx::[<ALU_ $name:upper>], dst, src1, src2)
+            }
+
+            pub fn [<emit_ $name _reg_mem_reg>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src1: &crate::models::Memory, src2: crate::models::GPR) -> Result<(), crate::assembler::EmitError> {
+                use crate::assembler::implementation::instructions::apx;
+                apx::emit_alu_ndd_reg_mem_reg(asm, apx::[<ALU_ $name:upper>], dst, src1, src2)
+            }
+
+            pub fn [<emit_ $name _reg_reg_imm>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src1: crate::models::GPR, src2: crate::models::Immediate32) -> Result<(), crate::assembler::EmitError> {
+                use crate::assembler::implementation::instructions::apx;
+                apx::emit_alu_ndd_reg_reg_imm(asm, apx::[<ALU_ $name:upper>], dst, src1, src2)
+            }
+
+            pub fn [<emit_ $name _reg_mem_imm>](asm: &mut crate::assembler::X86_64Assembler, dst: crate::models::GPR, src1: &crate::models::Memory, src2: crate::models::Immediate32) -> Result<(), crate::assembler::EmitError> {
+                use crate::assembler::implementation::instructions::apx;
+                apx::emit_alu_ndd_reg_mem_imm(asm, apx::[<ALU_ $name:upper>], dst, src1, src2)
+            }
+        }
+    };
+}
+
+pub(crate) use generate_fn_emit_ndd;
@@ -1,5 +1,6 @@
 mod helpers;
 mod macros;
+mod raw_encoding;
 
 mod mov;
 pub use mov::*;
@@ -15,3 +16,29 @@ pub use control::*;
 
 mod push_pop;
 pub use push_pop::*;
+
+mod fma;
+pub use fma::*;
+
+mod crypto;
+pub use crypto::*;
+
+mod conversion;
+pub use conversion::*;
+
+mod sse4;
+pub use sse4::*;
+
+mod x87;
+pub use x87::*;
+
+mod system;
+pub use system::*;
+
+mod tsx;
+pub use tsx::*;
+
+mod jump_table;
+pub use jump_table::*;
+
+mod apx;
@@ -1,8 +1,11 @@
 use osom_encoders_x86_64::encoders as enc;
 use osom_encoders_x86_64::models as enc_models;
 
+use crate::assembler::implementation::PatchKind;
+use crate::assembler::implementation::instructions::apx;
+use crate::assembler::implementation::instructions::helpers::update_trailing_imm_patch;
 use crate::assembler::{EmitError, X86_64Assembler};
-use crate::models::{GPR, Immediate32, Immediate64, Size};
+use crate::models::{GPR, Immediate32, Immediate64, Label, Size};
 
 pub fn emit_mov_reg_imm64(asm: &mut X86_64Assembler, dst: GPR, src: Immediate64) -> Result<(), EmitError> {
     unsafe {
@@ -11,6 +14,9 @@ pub fn emit_mov_reg_imm64(asm: &mut X86_64Assembler, dst: GPR, src: Immediate64)
             if dst.size() != Size::Bit64 {
                 return Err(EmitError::OperandSizeMismatch);
             }
+            if dst.requires_apx() {
+                return apx::emit_mov_reg_imm64_apx(asm, dst, src);
+            }
             let imm64 = enc_models::Immediate64::from_i64(src_value);
             asm._emit_encoded_instruction(enc::mov::encode_mov_reg64_imm64(dst.as_enc_gpr(), imm64))?;
             return Ok(());
@@ -21,6 +27,28 @@ pub fn emit_mov_reg_imm64(asm: &mut X86_64Assembler, dst: GPR, src: Immediate64)
     }
 }
 
+pub fn emit_mov_reg_label(asm: &mut X86_64Assembler, dst: GPR, label: Label) -> Result<(), EmitError> {
+    let (kind, imm_length) = match dst.size() {
+        Size::Bit64 => (PatchKind::Absolute64, 8),
+        Size::Bit32 => (PatchKind::Absolute, 4),
+        _ => return Err(EmitError::OperandSizeMismatch),
+    };
+
+    if dst.requires_apx() {
+        return apx::emit_mov_reg_label_apx(asm, dst, label, kind);
+    }
+
+    let instr = unsafe {
+        if imm_length == 8 {
+            enc::mov::encode_mov_reg64_imm64(dst.as_enc_gpr(), enc_models::Immediate64::from_i64(0))
+        } else {
+            enc::mov::encode_mov_reg32_imm32(dst.as_enc_gpr(), enc_models::Immediate32::from_i32(0))
+        }
+    };
+    update_trailing_imm_patch(asm, label, instr.as_slice(), imm_length, kind);
+    asm._emit_encoded_instruction(instr)
+}
+
 pub fn emit_mov_reg_imm(asm: &mut X86_64Assembler, dst: GPR, src: Immediate32) -> Result<(), EmitError> {
     unsafe {
         let dst_size = dst.size();
@@ -29,6 +57,10 @@ pub fn emit_mov_reg_imm(asm: &mut X86_64Assembler, dst: GPR, src: Immediate32) -
             return Err(EmitError::OperandSizeMismatch);
         }
 
+        if dst.requires_apx() {
+            return apx::emit_mov_reg_imm_apx(asm, dst, src);
+        }
+
         let src_value = src.value();
         match dst_size {
             Size::Bit8 => {
@@ -1,12 +1,17 @@
 use osom_encoders_x86_64::encoders as enc;
 use osom_encoders_x86_64::models as enc_models;
 
+use crate::assembler::implementation::instructions::apx;
 use crate::assembler::implementation::instructions::helpers::update_patchable_info;
+use crate::assembler::implementation::instructions::raw_encoding::RmOperand;
 use crate::{
     assembler::{EmitError, X86_64Assembler},
     models::{GPR, Immediate32, Memory, Size},
 };
 
+const PUSH_REG: u8 = 0x50;
+const POP_REG: u8 = 0x58;
+
 pub fn emit_push_imm(asm: &mut X86_64Assembler, src: Immediate32) -> Result<(), EmitError> {
     let instruction = match src.real_size() {
         Size::Bit32 => {
@@ -32,10 +37,18 @@ pub fn emit_push_reg(asm: &mut X86_64Assembler, src: GPR) -> Result<(), EmitErro
         return Err(EmitError::OperandSizeMismatch);
     }
 
+    if src.requires_apx() {
+        return apx::emit_push_pop_reg_apx(asm, PUSH_REG, src);
+    }
+
     unsafe { asm._emit_encoded_instruction(enc::push::encode_push_reg64(src.as_enc_gpr())) }
 }
 
 pub fn emit_push_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    if src.requires_apx() {
+        return apx::emit_rm64_apx(asm, 0xFF, 6, &RmOperand::Memory(src));
+    }
+
     let instruction = unsafe { enc::push::encode_push_rm64(src.as_enc_gpr_or_mem()) };
     update_patchable_info(asm, src, &instruction);
     asm._emit_encoded_instruction(instruction)
@@ -46,10 +59,18 @@ pub fn emit_pop_reg(asm: &mut X86_64Assembler, src: GPR) -> Result<(), EmitError
         return Err(EmitError::OperandSizeMismatch);
     }
 
+    if src.requires_apx() {
+        return apx::emit_push_pop_reg_apx(asm, POP_REG, src);
+    }
+
     unsafe { asm._emit_encoded_instruction(enc::pop::encode_pop_reg64(src.as_enc_gpr())) }
 }
 
 pub fn emit_pop_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    if src.requires_apx() {
+        return apx::emit_rm64_apx(asm, 0x8F, 0, &RmOperand::Memory(src));
+    }
+
     let instruction = unsafe { enc::pop::encode_pop_rm64(src.as_enc_gpr_or_mem()) };
     update_patchable_info(asm, src, &instruction);
     asm._emit_encoded_instruction(instruction)
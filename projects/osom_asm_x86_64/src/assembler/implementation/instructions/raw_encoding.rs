@@ -0,0 +1,487 @@
+//! Encoders for instructions that `osom_encoders_x86_64` doesn't support.
+//! These produce the same kind of output: legacy/REX or VEX prefixes
+//! followed by opcode, `ModRM`, optional `SIB`, displacement and immediate.
+#![allow(clippy::cast_sign_loss)]
+
+use crate::assembler::EmitError;
+use crate::models::{GPR, GPRKind, Immediate32, Memory, MemoryImpl, Size, XMM, YMM};
+
+const MAX_INSTRUCTION_LENGTH: usize = 15;
+
+/// The binary encoded instruction. Unlike the encoder's instruction
+/// it also remembers where the RIP-relative displacement is, so that
+/// label references can be patched later.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct RawInstruction {
+    buffer: [u8; MAX_INSTRUCTION_LENGTH],
+    length: u8,
+    displacement_offset: Option<u8>,
+}
+
+impl RawInstruction {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; MAX_INSTRUCTION_LENGTH],
+            length: 0,
+            displacement_offset: None,
+        }
+    }
+
+    #[inline(always)]
+    pub fn push(&mut self, byte: u8) {
+        debug_assert!(
+            (self.length as usize) < MAX_INSTRUCTION_LENGTH,
+            "Instruction is longer than {MAX_INSTRUCTION_LENGTH} bytes."
+        );
+        self.buffer[self.length as usize] = byte;
+        self.length += 1;
+    }
+
+    #[inline(always)]
+    pub fn push_slice(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.push(*byte);
+        }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer[..self.length as usize]
+    }
+
+    /// The offset of the RIP-relative 32-bit displacement, if there is any.
+    #[inline(always)]
+    #[must_use]
+    pub const fn displacement_offset(&self) -> Option<u8> {
+        self.displacement_offset
+    }
+
+    fn push_modrm(&mut self, reg: u8, rm: &RmOperand) {
+        #[inline(always)]
+        const fn modrm(mod_field: u8, reg_field: u8, rm_field: u8) -> u8 {
+            (mod_field << 6) | ((reg_field & 0b111) << 3) | (rm_field & 0b111)
+        }
+
+        #[inline(always)]
+        const fn sib(scale: u8, index: u8, base: u8) -> u8 {
+            (scale << 6) | ((index & 0b111) << 3) | (base & 0b111)
+        }
+
+        let memory = match rm {
+            RmOperand::Register(register) => {
+                self.push(modrm(0b11, reg, register.index));
+                return;
+            }
+            RmOperand::Memory(memory) => memory,
+        };
+
+        match memory.as_impl() {
+            MemoryImpl::Based { base, offset } => {
+                let base = base.index();
+                let displacement = Displacement::new(*offset, base & 0b111 == 0b101);
+                if base & 0b111 == 0b100 {
+                    // RSP and R12 can only be encoded through SIB.
+                    self.push(modrm(displacement.mod_field(), reg, 0b100));
+                    self.push(sib(0b00, 0b100, 0b100));
+                } else {
+                    self.push(modrm(displacement.mod_field(), reg, base));
+                }
+                self.push_displacement(displacement);
+            }
+            MemoryImpl::Scaled { index, scale, offset } => {
+                // Index without base is only encodable with mandatory disp32.
+                self.push(modrm(0b00, reg, 0b100));
+                self.push(sib(scale.sib_bits(), index.index(), 0b101));
+                self.push_slice(&offset.value().to_le_bytes());
+            }
+            MemoryImpl::BasedScaled {
+                base,
+                index,
+                scale,
+                offset,
+            } => {
+                let base = base.index();
+                let displacement = Displacement::new(*offset, base & 0b111 == 0b101);
+                self.push(modrm(displacement.mod_field(), reg, 0b100));
+                self.push(sib(scale.sib_bits(), index.index(), base));
+                self.push_displacement(displacement);
+            }
+            MemoryImpl::Label { .. } => {
+                self.push(modrm(0b00, reg, 0b101));
+                self.displacement_offset = Some(self.length);
+                self.push_slice(&[0; 4]);
+            }
+        }
+    }
+
+    fn push_displacement(&mut self, displacement: Displacement) {
+        match displacement {
+            Displacement::None => {}
+            Displacement::Bit8(value) => self.push(value as u8),
+            Displacement::Bit32(value) => self.push_slice(&value.to_le_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Displacement {
+    None,
+    Bit8(i8),
+    Bit32(i32),
+}
+
+impl Displacement {
+    /// `force` is set for RBP/R13 bases, which can't be encoded without
+    /// displacement, since that encoding means RIP-relative addressing.
+    fn new(offset: Immediate32, force: bool) -> Self {
+        let value = offset.value();
+        if value == 0 && !force {
+            return Self::None;
+        }
+
+        if offset.real_size() == Size::Bit8 {
+            Self::Bit8(value as i8)
+        } else {
+            Self::Bit32(value)
+        }
+    }
+
+    const fn mod_field(self) -> u8 {
+        match self {
+            Self::None => 0b00,
+            Self::Bit8(_) => 0b01,
+            Self::Bit32(_) => 0b10,
+        }
+    }
+}
+
+/// A register as seen by the raw encoders.
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct RawRegister {
+    index: u8,
+    requires_rex: bool,
+    forbids_rex: bool,
+}
+
+impl RawRegister {
+    #[inline(always)]
+    pub const fn new(index: u8) -> Self {
+        Self {
+            index,
+            requires_rex: false,
+            forbids_rex: false,
+        }
+    }
+
+    /// SPL, BPL, SIL and DIL can only be encoded with REX prefix,
+    /// while AH, CH, DH and BH can only be encoded without it.
+    #[inline]
+    pub fn from_gpr(gpr: GPR) -> Self {
+        let index = gpr.index();
+        let kind = gpr.kind();
+        Self {
+            index,
+            requires_rex: kind == GPRKind::Bit8 && (4..=7).contains(&index),
+            forbids_rex: kind == GPRKind::Bit8High,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn from_xmm(xmm: XMM) -> Self {
+        Self::new(xmm.index())
+    }
+
+    #[inline(always)]
+    pub const fn from_ymm(ymm: YMM) -> Self {
+        Self::new(ymm.index())
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self.index
+    }
+}
+
+/// The operand encoded in the `rm` field of `ModRM` byte.
+#[derive(Debug, Clone, Copy)]
+pub enum RmOperand<'a> {
+    Register(RawRegister),
+    Memory(&'a Memory),
+}
+
+impl RmOperand<'_> {
+    /// Returns the `X` and `B` extension bits, in this order.
+    fn extension_bits(&self) -> (u8, u8) {
+        match self {
+            RmOperand::Register(register) => (0, (register.index >> 3) & 1),
+            RmOperand::Memory(memory) => match memory.as_impl() {
+                MemoryImpl::Based { base, .. } => (0, (base.index() >> 3) & 1),
+                MemoryImpl::Scaled { index, .. } => ((index.index() >> 3) & 1, 0),
+                MemoryImpl::BasedScaled { base, index, .. } => ((index.index() >> 3) & 1, (base.index() >> 3) & 1),
+                MemoryImpl::Label { .. } => (0, 0),
+            },
+        }
+    }
+
+    /// Returns the APX `X4` and `B4` extension bits, in this order.
+    fn apx_extension_bits(&self) -> (u8, u8) {
+        match self {
+            RmOperand::Register(register) => (0, (register.index >> 4) & 1),
+            RmOperand::Memory(memory) => match memory.as_impl() {
+                MemoryImpl::Based { base, .. } => (0, (base.index() >> 4) & 1),
+                MemoryImpl::Scaled { index, .. } => ((index.index() >> 4) & 1, 0),
+                MemoryImpl::BasedScaled { base, index, .. } => ((index.index() >> 4) & 1, (base.index() >> 4) & 1),
+                MemoryImpl::Label { .. } => (0, 0),
+            },
+        }
+    }
+
+    fn forbids_rex(&self) -> bool {
+        match self {
+            RmOperand::Register(register) => register.forbids_rex,
+            RmOperand::Memory(_) => false,
+        }
+    }
+}
+
+/// Checks that neither `reg` nor `rm` is an APX extended register,
+/// these can't be encoded with REX or VEX prefixes.
+fn check_no_apx_registers(reg: RawRegister, rm: &RmOperand) -> Result<(), EmitError> {
+    let (x4, b4) = rm.apx_extension_bits();
+    if (reg.index >> 4) | x4 | b4 != 0 {
+        return Err(EmitError::IncompatibleOperands);
+    }
+    Ok(())
+}
+
+/// Encodes an instruction with legacy prefixes and optional REX.
+///
+/// `prefixes` are emitted as they are, before REX. The `opcode` contains
+/// escape bytes, e.g. `[0x0F, 0x38, 0xF0]`. `reg` is either a register or
+/// an opcode extension (`/digit`).
+pub fn encode_legacy(
+    prefixes: &[u8],
+    rex_w: bool,
+    opcode: &[u8],
+    reg: RawRegister,
+    rm: &RmOperand,
+    immediate: &[u8],
+) -> Result<RawInstruction, EmitError> {
+    check_no_apx_registers(reg, rm)?;
+    let (x, b) = rm.extension_bits();
+    let r = (reg.index >> 3) & 1;
+    let rm_register = match rm {
+        RmOperand::Register(register) => Some(*register),
+        RmOperand::Memory(_) => None,
+    };
+
+    let requires_rex =
+        rex_w || (r | x | b) != 0 || reg.requires_rex || rm_register.is_some_and(|register| register.requires_rex);
+    let forbids_rex = reg.forbids_rex || rm_register.is_some_and(|register| register.forbids_rex);
+    if requires_rex && forbids_rex {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    let mut instruction = RawInstruction::new();
+    instruction.push_slice(prefixes);
+    if requires_rex {
+        instruction.push(0x40 | (u8::from(rex_w) << 3) | (r << 2) | (x << 1) | b);
+    }
+    instruction.push_slice(opcode);
+    instruction.push_modrm(reg.index, rm);
+    instruction.push_slice(immediate);
+    Ok(instruction)
+}
+
+/// The implied prefix encoded in `pp` field of VEX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(dead_code)]
+pub enum VexPrefix {
+    None = 0b00,
+    P66 = 0b01,
+    PF3 = 0b10,
+    PF2 = 0b11,
+}
+
+/// The implied escape bytes encoded in `mmmmm` field of VEX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(dead_code)]
+pub enum OpcodeMap {
+    Map0F = 0b00001,
+    Map0F38 = 0b00010,
+    Map0F3A = 0b00011,
+}
+
+/// The static part of VEX prefix, i.e. everything except registers.
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct Vex {
+    pub prefix: VexPrefix,
+    pub map: OpcodeMap,
+    pub w: bool,
+    pub l: bool,
+}
+
+/// Encodes a VEX instruction. The `vvvv` is the additional (typically
+/// the first source) register, it should be `0` when unused.
+pub fn encode_vex(
+    vex: Vex,
+    opcode: u8,
+    reg: RawRegister,
+    vvvv: u8,
+    rm: &RmOperand,
+    immediate: &[u8],
+) -> Result<RawInstruction, EmitError> {
+    check_no_apx_registers(reg, rm)?;
+    let (x, b) = rm.extension_bits();
+    let r = (reg.index >> 3) & 1;
+    let vvvv = !vvvv & 0b1111;
+    let l = u8::from(vex.l);
+    let pp = vex.prefix as u8;
+
+    let mut instruction = RawInstruction::new();
+    if x == 0 && b == 0 && !vex.w && vex.map == OpcodeMap::Map0F {
+        // The shorter, two bytes form.
+        instruction.push(0xC5);
+        instruction.push(((r ^ 1) << 7) | (vvvv << 3) | (l << 2) | pp);
+    } else {
+        instruction.push(0xC4);
+        instruction.push(((r ^ 1) << 7) | ((x ^ 1) << 6) | ((b ^ 1) << 5) | vex.map as u8);
+        instruction.push((u8::from(vex.w) << 7) | (vvvv << 3) | (l << 2) | pp);
+    }
+    instruction.push(opcode);
+    instruction.push_modrm(reg.index, rm);
+    instruction.push_slice(immediate);
+    Ok(instruction)
+}
+
+/// Builds the second byte of REX2 prefix. The `reg` and `base` are full
+/// 5-bit register indexes, `index` is the SIB index (or `0` if unused).
+const fn rex2_payload(m0: bool, w: bool, reg: u8, index: u8, base: u8) -> u8 {
+    ((m0 as u8) << 7)
+        | (((reg >> 4) & 1) << 6)
+        | (((index >> 4) & 1) << 5)
+        | (((base >> 4) & 1) << 4)
+        | ((w as u8) << 3)
+        | (((reg >> 3) & 1) << 2)
+        | (((index >> 3) & 1) << 1)
+        | ((base >> 3) & 1)
+}
+
+/// Encodes an instruction with legacy prefixes and APX REX2 prefix.
+///
+/// The `opcode` is either a single byte from the legacy map 0, or
+/// `0x0F` escape followed by a single byte from map 1. REX2 can't
+/// encode the three byte maps.
+pub fn encode_rex2(
+    prefixes: &[u8],
+    rex_w: bool,
+    opcode: &[u8],
+    reg: RawRegister,
+    rm: &RmOperand,
+    immediate: &[u8],
+) -> Result<RawInstruction, EmitError> {
+    let (m0, opcode) = match opcode {
+        [0x0F, opcode] => (true, *opcode),
+        [opcode] => (false, *opcode),
+        _ => return Err(EmitError::IncompatibleOperands),
+    };
+    if reg.forbids_rex || rm.forbids_rex() {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    let (x, b) = rm.extension_bits();
+    let (x4, b4) = rm.apx_extension_bits();
+    let mut instruction = RawInstruction::new();
+    instruction.push_slice(prefixes);
+    instruction.push(REX2);
+    instruction.push(rex2_payload(
+        m0,
+        rex_w,
+        reg.index,
+        (x4 << 4) | (x << 3),
+        (b4 << 4) | (b << 3),
+    ));
+    instruction.push(opcode);
+    instruction.push_modrm(reg.index, rm);
+    instruction.push_slice(immediate);
+    Ok(instruction)
+}
+
+/// Encodes a legacy map 0 instruction with the register embedded in the
+/// low 3 bits of `opcode`, e.g. `push r` or `mov r, imm`, with REX2 prefix.
+pub fn encode_rex2_opcode_register(
+    prefixes: &[u8],
+    rex_w: bool,
+    opcode: u8,
+    register: RawRegister,
+    immediate: &[u8],
+) -> Result<RawInstruction, EmitError> {
+    if register.forbids_rex {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    let mut instruction = RawInstruction::new();
+    instruction.push_slice(prefixes);
+    instruction.push(REX2);
+    instruction.push(rex2_payload(false, rex_w, 0, 0, register.index));
+    instruction.push(opcode | (register.index & 0b111));
+    instruction.push_slice(immediate);
+    Ok(instruction)
+}
+
+const REX2: u8 = 0xD5;
+const EVEX: u8 = 0x62;
+
+/// The APX extended EVEX map of promoted legacy instructions.
+const EVEX_MAP4: u8 = 0b100;
+
+/// The static part of APX EVEX prefix of promoted legacy instructions.
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct ApxEvex {
+    pub prefix: VexPrefix,
+    pub w: bool,
+
+    /// The new data destination flag. When set, the `vvvv` register
+    /// is the destination of the instruction.
+    pub nd: bool,
+}
+
+/// Encodes a promoted legacy instruction with APX EVEX prefix.
+/// The `opcode` is a byte from the map 4, which mirrors legacy map 0.
+pub fn encode_apx_evex(
+    evex: ApxEvex,
+    opcode: u8,
+    reg: RawRegister,
+    vvvv: RawRegister,
+    rm: &RmOperand,
+    immediate: &[u8],
+) -> Result<RawInstruction, EmitError> {
+    if reg.forbids_rex || vvvv.forbids_rex || rm.forbids_rex() {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    let (x3, b3) = rm.extension_bits();
+    let (x4, b4) = rm.apx_extension_bits();
+    let r3 = (reg.index >> 3) & 1;
+    let r4 = (reg.index >> 4) & 1;
+    let v = !vvvv.index & 0b1_1111;
+
+    let mut instruction = RawInstruction::new();
+    instruction.push(EVEX);
+    instruction.push(((r3 ^ 1) << 7) | ((x3 ^ 1) << 6) | ((b3 ^ 1) << 5) | ((r4 ^ 1) << 4) | (b4 << 3) | EVEX_MAP4);
+    instruction.push((u8::from(evex.w) << 7) | ((v & 0b1111) << 3) | ((x4 ^ 1) << 2) | evex.prefix as u8);
+    instruction.push((u8::from(evex.nd) << 4) | ((v >> 4) << 3));
+    instruction.push(opcode);
+    instruction.push_modrm(reg.index, rm);
+    instruction.push_slice(immediate);
+    Ok(instruction)
+}
@@ -0,0 +1,293 @@
+use crate::assembler::implementation::instructions::helpers::{emit_raw_instruction, immediate_u8};
+use crate::assembler::implementation::instructions::raw_encoding::{RawRegister, RmOperand, encode_legacy};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{GPR, Immediate32, Memory, PackedMinMaxKind, Size, XMM};
+
+const PREFIX_66: &[u8] = &[0x66];
+
+/// Emits `66`-prefixed instruction with `xmm` in `ModRM.reg`.
+fn emit_xmm_rm(
+    asm: &mut X86_64Assembler,
+    rex_w: bool,
+    opcode: &[u8],
+    reg: XMM,
+    rm: &RmOperand,
+    immediate: &[u8],
+) -> Result<(), EmitError> {
+    let instr = encode_legacy(PREFIX_66, rex_w, opcode, RawRegister::from_xmm(reg), rm, immediate)?;
+    emit_raw_instruction(asm, rm, &instr)
+}
+
+const PCMPISTRI_OPCODE: &[u8] = &[0x0F, 0x3A, 0x63];
+const PCMPESTRI_OPCODE: &[u8] = &[0x0F, 0x3A, 0x61];
+const PTEST_OPCODE: &[u8] = &[0x0F, 0x38, 0x17];
+const PBLENDVB_OPCODE: &[u8] = &[0x0F, 0x38, 0x10];
+const BLENDPS_OPCODE: &[u8] = &[0x0F, 0x3A, 0x0C];
+
+pub fn emit_pcmpistri_xmm_xmm_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, PCMPISTRI_OPCODE, dst, &src, &[imm])
+}
+
+pub fn emit_pcmpistri_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    emit_xmm_rm(asm, false, PCMPISTRI_OPCODE, dst, &RmOperand::Memory(src), &[imm])
+}
+
+pub fn emit_pcmpestri_xmm_xmm_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, PCMPESTRI_OPCODE, dst, &src, &[imm])
+}
+
+pub fn emit_pcmpestri_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, u8::MAX)?;
+    emit_xmm_rm(asm, false, PCMPESTRI_OPCODE, dst, &RmOperand::Memory(src), &[imm])
+}
+
+pub fn emit_ptest_xmm_xmm(asm: &mut X86_64Assembler, dst: XMM, src: XMM) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, PTEST_OPCODE, dst, &src, &[])
+}
+
+pub fn emit_ptest_xmm_mem(asm: &mut X86_64Assembler, dst: XMM, src: &Memory) -> Result<(), EmitError> {
+    emit_xmm_rm(asm, false, PTEST_OPCODE, dst, &RmOperand::Memory(src), &[])
+}
+
+pub fn emit_pblendvb_xmm_xmm(asm: &mut X86_64Assembler, dst: XMM, src: XMM, mask: XMM) -> Result<(), EmitError> {
+    if mask != XMM::XMM0 {
+        return Err(EmitError::InvalidImplicitOperand);
+    }
+
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, PBLENDVB_OPCODE, dst, &src, &[])
+}
+
+pub fn emit_pblendvb_xmm_mem(asm: &mut X86_64Assembler, dst: XMM, src: &Memory, mask: XMM) -> Result<(), EmitError> {
+    if mask != XMM::XMM0 {
+        return Err(EmitError::InvalidImplicitOperand);
+    }
+
+    emit_xmm_rm(asm, false, PBLENDVB_OPCODE, dst, &RmOperand::Memory(src), &[])
+}
+
+pub fn emit_blendps_xmm_xmm_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, 0b1111)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, BLENDPS_OPCODE, dst, &src, &[imm])
+}
+
+pub fn emit_blendps_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, 0b1111)?;
+    emit_xmm_rm(asm, false, BLENDPS_OPCODE, dst, &RmOperand::Memory(src), &[imm])
+}
+
+const fn packed_min_max_opcode(kind: PackedMinMaxKind) -> &'static [u8] {
+    match kind {
+        PackedMinMaxKind::MinSignedByte => &[0x0F, 0x38, 0x38],
+        PackedMinMaxKind::MinSignedDword => &[0x0F, 0x38, 0x39],
+        PackedMinMaxKind::MinUnsignedWord => &[0x0F, 0x38, 0x3A],
+        PackedMinMaxKind::MinUnsignedDword => &[0x0F, 0x38, 0x3B],
+        PackedMinMaxKind::MaxSignedByte => &[0x0F, 0x38, 0x3C],
+        PackedMinMaxKind::MaxSignedDword => &[0x0F, 0x38, 0x3D],
+        PackedMinMaxKind::MaxUnsignedWord => &[0x0F, 0x38, 0x3E],
+        PackedMinMaxKind::MaxUnsignedDword => &[0x0F, 0x38, 0x3F],
+    }
+}
+
+pub fn emit_packed_min_max_xmm_xmm(
+    asm: &mut X86_64Assembler,
+    kind: PackedMinMaxKind,
+    dst: XMM,
+    src: XMM,
+) -> Result<(), EmitError> {
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, packed_min_max_opcode(kind), dst, &src, &[])
+}
+
+pub fn emit_packed_min_max_xmm_mem(
+    asm: &mut X86_64Assembler,
+    kind: PackedMinMaxKind,
+    dst: XMM,
+    src: &Memory,
+) -> Result<(), EmitError> {
+    emit_xmm_rm(
+        asm,
+        false,
+        packed_min_max_opcode(kind),
+        dst,
+        &RmOperand::Memory(src),
+        &[],
+    )
+}
+
+/// Returns the highest lane index of 128-bit register with given lane size.
+fn max_lane_index(size: Size) -> u8 {
+    match size {
+        Size::Bit8 => 15,
+        Size::Bit16 => 7,
+        Size::Bit32 => 3,
+        Size::Bit64 => 1,
+    }
+}
+
+/// Lanes narrower than 64 bits are transferred through 32-bit registers.
+fn check_lane_gpr(size: Size, gpr: GPR) -> Result<(), EmitError> {
+    let expected = if size == Size::Bit64 { Size::Bit64 } else { Size::Bit32 };
+    if gpr.size() != expected {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+    Ok(())
+}
+
+const fn pinsr_opcode(size: Size) -> &'static [u8] {
+    match size {
+        Size::Bit8 => &[0x0F, 0x3A, 0x20],
+        Size::Bit16 => &[0x0F, 0xC4],
+        Size::Bit32 | Size::Bit64 => &[0x0F, 0x3A, 0x22],
+    }
+}
+
+pub fn emit_pinsr_xmm_reg_imm(
+    asm: &mut X86_64Assembler,
+    size: Size,
+    dst: XMM,
+    src: GPR,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    check_lane_gpr(size, src)?;
+    let imm = immediate_u8(imm, max_lane_index(size))?;
+    let src = RmOperand::Register(RawRegister::from_gpr(src));
+    emit_xmm_rm(asm, size == Size::Bit64, pinsr_opcode(size), dst, &src, &[imm])
+}
+
+pub fn emit_pinsr_xmm_mem_imm(
+    asm: &mut X86_64Assembler,
+    size: Size,
+    dst: XMM,
+    src: &Memory,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, max_lane_index(size))?;
+    let src = RmOperand::Memory(src);
+    emit_xmm_rm(asm, size == Size::Bit64, pinsr_opcode(size), dst, &src, &[imm])
+}
+
+const fn pextr_opcode(size: Size) -> &'static [u8] {
+    match size {
+        Size::Bit8 => &[0x0F, 0x3A, 0x14],
+        Size::Bit16 => &[0x0F, 0x3A, 0x15],
+        Size::Bit32 | Size::Bit64 => &[0x0F, 0x3A, 0x16],
+    }
+}
+
+pub fn emit_pextr_reg_xmm_imm(
+    asm: &mut X86_64Assembler,
+    size: Size,
+    dst: GPR,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    const PEXTRW_REG_OPCODE: &[u8] = &[0x0F, 0xC5];
+
+    check_lane_gpr(size, dst)?;
+    let imm = immediate_u8(imm, max_lane_index(size))?;
+    if size == Size::Bit16 {
+        // The SSE2 form is shorter and, unlike the rest, has GPR in ModRM.reg.
+        let src = RmOperand::Register(RawRegister::from_xmm(src));
+        let instr = encode_legacy(
+            PREFIX_66,
+            false,
+            PEXTRW_REG_OPCODE,
+            RawRegister::from_gpr(dst),
+            &src,
+            &[imm],
+        )?;
+        return asm._emit_bytes(instr.as_slice());
+    }
+
+    let dst = RmOperand::Register(RawRegister::from_gpr(dst));
+    emit_xmm_rm(asm, size == Size::Bit64, pextr_opcode(size), src, &dst, &[imm])
+}
+
+pub fn emit_pextr_mem_xmm_imm(
+    asm: &mut X86_64Assembler,
+    size: Size,
+    dst: &Memory,
+    src: XMM,
+    imm: Immediate32,
+) -> Result<(), EmitError> {
+    let imm = immediate_u8(imm, max_lane_index(size))?;
+    let dst = RmOperand::Memory(dst);
+    emit_xmm_rm(asm, size == Size::Bit64, pextr_opcode(size), src, &dst, &[imm])
+}
+
+fn pmovx_opcode(signed: bool, from: Size, to: Size) -> Result<[u8; 3], EmitError> {
+    let offset = match (from, to) {
+        (Size::Bit8, Size::Bit16) => 0,
+        (Size::Bit8, Size::Bit32) => 1,
+        (Size::Bit8, Size::Bit64) => 2,
+        (Size::Bit16, Size::Bit32) => 3,
+        (Size::Bit16, Size::Bit64) => 4,
+        (Size::Bit32, Size::Bit64) => 5,
+        _ => return Err(EmitError::OperandSizeMismatch),
+    };
+    let base = if signed { 0x20 } else { 0x30 };
+    Ok([0x0F, 0x38, base + offset])
+}
+
+pub fn emit_pmovx_xmm_xmm(
+    asm: &mut X86_64Assembler,
+    signed: bool,
+    from: Size,
+    to: Size,
+    dst: XMM,
+    src: XMM,
+) -> Result<(), EmitError> {
+    let opcode = pmovx_opcode(signed, from, to)?;
+    let src = RmOperand::Register(RawRegister::from_xmm(src));
+    emit_xmm_rm(asm, false, &opcode, dst, &src, &[])
+}
+
+pub fn emit_pmovx_xmm_mem(
+    asm: &mut X86_64Assembler,
+    signed: bool,
+    from: Size,
+    to: Size,
+    dst: XMM,
+    src: &Memory,
+) -> Result<(), EmitError> {
+    let opcode = pmovx_opcode(signed, from, to)?;
+    emit_xmm_rm(asm, false, &opcode, dst, &RmOperand::Memory(src), &[])
+}
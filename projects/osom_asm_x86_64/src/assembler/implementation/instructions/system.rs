@@ -0,0 +1,191 @@
+use crate::assembler::implementation::instructions::helpers::{emit_raw_instruction, immediate_u8};
+use crate::assembler::implementation::instructions::raw_encoding::{RawRegister, RmOperand, encode_legacy};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{CR, DR, GPR, Immediate32, Memory, Segment, Size};
+
+const PREFIX_66: &[u8] = &[0x66];
+const REX_W: u8 = 0x48;
+
+/// Emits instruction with memory operand, where `ModRM.reg` is an opcode extension.
+fn emit_system_mem(
+    asm: &mut X86_64Assembler,
+    rex_w: bool,
+    opcode: &[u8],
+    extension: u8,
+    mem: &Memory,
+) -> Result<(), EmitError> {
+    let rm = RmOperand::Memory(mem);
+    let instr = encode_legacy(&[], rex_w, opcode, RawRegister::new(extension), &rm, &[])?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+/// Emits move between a system register (encoded in `ModRM.reg`) and a 64-bit GPR.
+/// The operand size is fixed to 64 bits, so REX.W is not needed.
+fn emit_mov_system(asm: &mut X86_64Assembler, opcode: &[u8], system: u8, gpr: GPR) -> Result<(), EmitError> {
+    if gpr.size() != Size::Bit64 {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+
+    let rm = RmOperand::Register(RawRegister::from_gpr(gpr));
+    let instr = encode_legacy(&[], false, opcode, RawRegister::new(system), &rm, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_mov_cr_reg(asm: &mut X86_64Assembler, dst: CR, src: GPR) -> Result<(), EmitError> {
+    emit_mov_system(asm, &[0x0F, 0x22], dst.index(), src)
+}
+
+pub fn emit_mov_reg_cr(asm: &mut X86_64Assembler, dst: GPR, src: CR) -> Result<(), EmitError> {
+    emit_mov_system(asm, &[0x0F, 0x20], src.index(), dst)
+}
+
+pub fn emit_mov_dr_reg(asm: &mut X86_64Assembler, dst: DR, src: GPR) -> Result<(), EmitError> {
+    emit_mov_system(asm, &[0x0F, 0x23], dst.index(), src)
+}
+
+pub fn emit_mov_reg_dr(asm: &mut X86_64Assembler, dst: GPR, src: DR) -> Result<(), EmitError> {
+    emit_mov_system(asm, &[0x0F, 0x21], src.index(), dst)
+}
+
+fn emit_mov_segment(asm: &mut X86_64Assembler, opcode: u8, segment: Segment, gpr: GPR) -> Result<(), EmitError> {
+    let (prefixes, rex_w): (&[u8], bool) = match gpr.size() {
+        Size::Bit16 => (PREFIX_66, false),
+        Size::Bit32 => (&[], false),
+        Size::Bit64 => (&[], true),
+        Size::Bit8 => return Err(EmitError::OperandSizeMismatch),
+    };
+    let rm = RmOperand::Register(RawRegister::from_gpr(gpr));
+    let instr = encode_legacy(prefixes, rex_w, &[opcode], RawRegister::new(segment.index()), &rm, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_mov_seg_reg(asm: &mut X86_64Assembler, dst: Segment, src: GPR) -> Result<(), EmitError> {
+    if dst == Segment::CS {
+        return Err(EmitError::IncompatibleOperands);
+    }
+
+    emit_mov_segment(asm, 0x8E, dst, src)
+}
+
+pub fn emit_mov_reg_seg(asm: &mut X86_64Assembler, dst: GPR, src: Segment) -> Result<(), EmitError> {
+    emit_mov_segment(asm, 0x8C, src, dst)
+}
+
+pub fn emit_lgdt_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    emit_system_mem(asm, false, &[0x0F, 0x01], 2, src)
+}
+
+pub fn emit_lidt_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    emit_system_mem(asm, false, &[0x0F, 0x01], 3, src)
+}
+
+pub fn emit_sgdt_mem(asm: &mut X86_64Assembler, dst: &Memory) -> Result<(), EmitError> {
+    emit_system_mem(asm, false, &[0x0F, 0x01], 0, dst)
+}
+
+pub fn emit_sidt_mem(asm: &mut X86_64Assembler, dst: &Memory) -> Result<(), EmitError> {
+    emit_system_mem(asm, false, &[0x0F, 0x01], 1, dst)
+}
+
+pub fn emit_ltr_reg(asm: &mut X86_64Assembler, src: GPR) -> Result<(), EmitError> {
+    if src.size() != Size::Bit16 {
+        return Err(EmitError::OperandSizeMismatch);
+    }
+
+    let rm = RmOperand::Register(RawRegister::from_gpr(src));
+    let instr = encode_legacy(&[], false, &[0x0F, 0x00], RawRegister::new(3), &rm, &[])?;
+    asm._emit_bytes(instr.as_slice())
+}
+
+pub fn emit_ltr_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    emit_system_mem(asm, false, &[0x0F, 0x00], 3, src)
+}
+
+pub fn emit_invlpg_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    emit_system_mem(asm, false, &[0x0F, 0x01], 7, src)
+}
+
+/// Returns the operand size prefix and opcode of port I/O instruction,
+/// based on the accumulator register. The `opcode` is the 8-bit variant.
+fn port_io_encoding(accumulator: GPR, opcode: u8) -> Result<(&'static [u8], u8), EmitError> {
+    if accumulator == GPR::AL {
+        Ok((&[], opcode))
+    } else if accumulator == GPR::AX {
+        Ok((PREFIX_66, opcode + 1))
+    } else if accumulator == GPR::EAX {
+        Ok((&[], opcode + 1))
+    } else {
+        Err(EmitError::InvalidImplicitOperand)
+    }
+}
+
+fn emit_port_io_imm(
+    asm: &mut X86_64Assembler,
+    opcode: u8,
+    accumulator: GPR,
+    port: Immediate32,
+) -> Result<(), EmitError> {
+    let (prefixes, opcode) = port_io_encoding(accumulator, opcode)?;
+    let port = immediate_u8(port, u8::MAX)?;
+    asm._emit_bytes(prefixes)?;
+    asm._emit_bytes(&[opcode, port])
+}
+
+fn emit_port_io_reg(asm: &mut X86_64Assembler, opcode: u8, accumulator: GPR, port: GPR) -> Result<(), EmitError> {
+    if port != GPR::DX {
+        return Err(EmitError::InvalidImplicitOperand);
+    }
+
+    let (prefixes, opcode) = port_io_encoding(accumulator, opcode)?;
+    asm._emit_bytes(prefixes)?;
+    asm._emit_bytes(&[opcode])
+}
+
+pub fn emit_in_reg_imm(asm: &mut X86_64Assembler, dst: GPR, port: Immediate32) -> Result<(), EmitError> {
+    emit_port_io_imm(asm, 0xE4, dst, port)
+}
+
+pub fn emit_in_reg_reg(asm: &mut X86_64Assembler, dst: GPR, port: GPR) -> Result<(), EmitError> {
+    emit_port_io_reg(asm, 0xEC, dst, port)
+}
+
+pub fn emit_out_imm_reg(asm: &mut X86_64Assembler, port: Immediate32, src: GPR) -> Result<(), EmitError> {
+    emit_port_io_imm(asm, 0xE6, src, port)
+}
+
+pub fn emit_out_reg_reg(asm: &mut X86_64Assembler, port: GPR, src: GPR) -> Result<(), EmitError> {
+    emit_port_io_reg(asm, 0xEE, src, port)
+}
+
+pub fn emit_xsave_mem(asm: &mut X86_64Assembler, dst: &Memory, is_64: bool) -> Result<(), EmitError> {
+    emit_system_mem(asm, is_64, &[0x0F, 0xAE], 4, dst)
+}
+
+pub fn emit_xrstor_mem(asm: &mut X86_64Assembler, src: &Memory, is_64: bool) -> Result<(), EmitError> {
+    emit_system_mem(asm, is_64, &[0x0F, 0xAE], 5, src)
+}
+
+/// Returns whether REX.W is needed for the far pointer offset of given size.
+fn far_pointer_rex_w(size: Size) -> Result<bool, EmitError> {
+    match size {
+        Size::Bit32 => Ok(false),
+        Size::Bit64 => Ok(true),
+        Size::Bit8 | Size::Bit16 => Err(EmitError::OperandSizeMismatch),
+    }
+}
+
+pub fn emit_far_jump_mem(asm: &mut X86_64Assembler, dst: &Memory, size: Size) -> Result<(), EmitError> {
+    emit_system_mem(asm, far_pointer_rex_w(size)?, &[0xFF], 5, dst)
+}
+
+pub fn emit_far_call_mem(asm: &mut X86_64Assembler, dst: &Memory, size: Size) -> Result<(), EmitError> {
+    emit_system_mem(asm, far_pointer_rex_w(size)?, &[0xFF], 3, dst)
+}
+
+pub fn emit_far_ret(asm: &mut X86_64Assembler, size: Size) -> Result<(), EmitError> {
+    if far_pointer_rex_w(size)? {
+        asm._emit_bytes(&[REX_W, 0xCB])
+    } else {
+        asm._emit_bytes(&[0xCB])
+    }
+}
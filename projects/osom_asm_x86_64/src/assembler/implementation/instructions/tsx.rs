@@ -0,0 +1,26 @@
+use crate::assembler::implementation::instructions::helpers::immediate_u8;
+use crate::assembler::implementation::{PatchKind, PatchableImm32Instruction};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{Immediate32, Label};
+
+const XBEGIN_OPCODE: [u8; 2] = [0xC7, 0xF8];
+const XABORT_OPCODE: [u8; 2] = [0xC6, 0xF8];
+
+pub fn emit_xbegin_label(asm: &mut X86_64Assembler, dst: Label) -> Result<(), EmitError> {
+    let [first, second] = XBEGIN_OPCODE;
+    let instr = [first, second, 0, 0, 0, 0];
+    let patchable_instruction = PatchableImm32Instruction {
+        instruction_position: asm._instruction_position(),
+        instruction_length: instr.len() as u8,
+        imm32_offset: XBEGIN_OPCODE.len() as u8,
+        kind: PatchKind::Relative,
+    };
+    asm._push_patchable_instruction(dst, patchable_instruction);
+    asm._emit_bytes(&instr)
+}
+
+pub fn emit_xabort_imm(asm: &mut X86_64Assembler, src: Immediate32) -> Result<(), EmitError> {
+    let value = immediate_u8(src, u8::MAX)?;
+    let [first, second] = XABORT_OPCODE;
+    asm._emit_bytes(&[first, second, value])
+}
@@ -0,0 +1,155 @@
+use crate::assembler::implementation::instructions::helpers::emit_raw_instruction;
+use crate::assembler::implementation::instructions::raw_encoding::{RawRegister, RmOperand, encode_legacy};
+use crate::assembler::{EmitError, X86_64Assembler};
+use crate::models::{Memory, ST, Size, X87ArithKind, X87Constant, X87FloatSize};
+
+/// Emits x87 instruction with memory operand, where `ModRM.reg` is an opcode extension.
+fn emit_x87_mem(asm: &mut X86_64Assembler, opcode: u8, extension: u8, mem: &Memory) -> Result<(), EmitError> {
+    let rm = RmOperand::Memory(mem);
+    let instr = encode_legacy(&[], false, &[opcode], RawRegister::new(extension), &rm, &[])?;
+    emit_raw_instruction(asm, &rm, &instr)
+}
+
+/// Emits x87 instruction with `st(i)` operand, encoded in the second opcode byte.
+fn emit_x87_st(asm: &mut X86_64Assembler, opcode: u8, base: u8, st: ST) -> Result<(), EmitError> {
+    asm._emit_bytes(&[opcode, base + st.index()])
+}
+
+pub fn emit_fld_mem(asm: &mut X86_64Assembler, size: X87FloatSize, src: &Memory) -> Result<(), EmitError> {
+    let (opcode, extension) = match size {
+        X87FloatSize::Single => (0xD9, 0),
+        X87FloatSize::Double => (0xDD, 0),
+        X87FloatSize::Extended => (0xDB, 5),
+    };
+    emit_x87_mem(asm, opcode, extension, src)
+}
+
+pub fn emit_fld_st(asm: &mut X86_64Assembler, src: ST) -> Result<(), EmitError> {
+    emit_x87_st(asm, 0xD9, 0xC0, src)
+}
+
+pub fn emit_fst_mem(asm: &mut X86_64Assembler, size: X87FloatSize, dst: &Memory) -> Result<(), EmitError> {
+    let opcode = match size {
+        X87FloatSize::Single => 0xD9,
+        X87FloatSize::Double => 0xDD,
+        X87FloatSize::Extended => return Err(EmitError::OperandSizeMismatch),
+    };
+    emit_x87_mem(asm, opcode, 2, dst)
+}
+
+pub fn emit_fst_st(asm: &mut X86_64Assembler, dst: ST) -> Result<(), EmitError> {
+    emit_x87_st(asm, 0xDD, 0xD0, dst)
+}
+
+pub fn emit_fstp_mem(asm: &mut X86_64Assembler, size: X87FloatSize, dst: &Memory) -> Result<(), EmitError> {
+    let (opcode, extension) = match size {
+        X87FloatSize::Single => (0xD9, 3),
+        X87FloatSize::Double => (0xDD, 3),
+        X87FloatSize::Extended => (0xDB, 7),
+    };
+    emit_x87_mem(asm, opcode, extension, dst)
+}
+
+pub fn emit_fstp_st(asm: &mut X86_64Assembler, dst: ST) -> Result<(), EmitError> {
+    emit_x87_st(asm, 0xDD, 0xD8, dst)
+}
+
+pub fn emit_fild_mem(asm: &mut X86_64Assembler, size: Size, src: &Memory) -> Result<(), EmitError> {
+    let (opcode, extension) = match size {
+        Size::Bit16 => (0xDF, 0),
+        Size::Bit32 => (0xDB, 0),
+        Size::Bit64 => (0xDF, 5),
+        Size::Bit8 => return Err(EmitError::OperandSizeMismatch),
+    };
+    emit_x87_mem(asm, opcode, extension, src)
+}
+
+pub fn emit_fistp_mem(asm: &mut X86_64Assembler, size: Size, dst: &Memory) -> Result<(), EmitError> {
+    let (opcode, extension) = match size {
+        Size::Bit16 => (0xDF, 3),
+        Size::Bit32 => (0xDB, 3),
+        Size::Bit64 => (0xDF, 7),
+        Size::Bit8 => return Err(EmitError::OperandSizeMismatch),
+    };
+    emit_x87_mem(asm, opcode, extension, dst)
+}
+
+/// The `ModRM.reg` extension of arithmetic with `st(0)` as destination.
+const fn arith_extension(kind: X87ArithKind) -> u8 {
+    match kind {
+        X87ArithKind::Add => 0,
+        X87ArithKind::Mul => 1,
+        X87ArithKind::Sub => 4,
+        X87ArithKind::SubReversed => 5,
+        X87ArithKind::Div => 6,
+        X87ArithKind::DivReversed => 7,
+    }
+}
+
+pub fn emit_x87_arith_mem(
+    asm: &mut X86_64Assembler,
+    kind: X87ArithKind,
+    size: X87FloatSize,
+    src: &Memory,
+) -> Result<(), EmitError> {
+    let opcode = match size {
+        X87FloatSize::Single => 0xD8,
+        X87FloatSize::Double => 0xDC,
+        X87FloatSize::Extended => return Err(EmitError::OperandSizeMismatch),
+    };
+    emit_x87_mem(asm, opcode, arith_extension(kind), src)
+}
+
+pub fn emit_x87_arith_st0_st(asm: &mut X86_64Assembler, kind: X87ArithKind, src: ST) -> Result<(), EmitError> {
+    emit_x87_st(asm, 0xD8, 0xC0 | (arith_extension(kind) << 3), src)
+}
+
+pub fn emit_x87_arith_st_st0(
+    asm: &mut X86_64Assembler,
+    kind: X87ArithKind,
+    dst: ST,
+    pop: bool,
+) -> Result<(), EmitError> {
+    // With `st(i)` as destination the encodings of the direct and reversed
+    // subtraction/division are swapped compared to `st(0)` destination.
+    let extension = match kind {
+        X87ArithKind::Sub => arith_extension(X87ArithKind::SubReversed),
+        X87ArithKind::SubReversed => arith_extension(X87ArithKind::Sub),
+        X87ArithKind::Div => arith_extension(X87ArithKind::DivReversed),
+        X87ArithKind::DivReversed => arith_extension(X87ArithKind::Div),
+        X87ArithKind::Add | X87ArithKind::Mul => arith_extension(kind),
+    };
+    let opcode = if pop { 0xDE } else { 0xDC };
+    emit_x87_st(asm, opcode, 0xC0 | (extension << 3), dst)
+}
+
+pub fn emit_fxch_st(asm: &mut X86_64Assembler, src: ST) -> Result<(), EmitError> {
+    emit_x87_st(asm, 0xD9, 0xC8, src)
+}
+
+pub fn emit_fcomi_st(asm: &mut X86_64Assembler, src: ST, unordered: bool, pop: bool) -> Result<(), EmitError> {
+    let opcode = if pop { 0xDF } else { 0xDB };
+    let base = if unordered { 0xE8 } else { 0xF0 };
+    emit_x87_st(asm, opcode, base, src)
+}
+
+pub fn emit_fldcw_mem(asm: &mut X86_64Assembler, src: &Memory) -> Result<(), EmitError> {
+    emit_x87_mem(asm, 0xD9, 5, src)
+}
+
+pub fn emit_fnstcw_mem(asm: &mut X86_64Assembler, dst: &Memory) -> Result<(), EmitError> {
+    emit_x87_mem(asm, 0xD9, 7, dst)
+}
+
+pub fn emit_fld_const(asm: &mut X86_64Assembler, constant: X87Constant) -> Result<(), EmitError> {
+    let second = match constant {
+        X87Constant::One => 0xE8,
+        X87Constant::Log2Ten => 0xE9,
+        X87Constant::Log2E => 0xEA,
+        X87Constant::Pi => 0xEB,
+        X87Constant::Log10Two => 0xEC,
+        X87Constant::LnTwo => 0xED,
+        X87Constant::Zero => 0xEE,
+    };
+    asm._emit_bytes(&[0xD9, second])
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::models::Label;
+
+/// Map keyed by labels. Labels of a [`LabelAllocator`][`crate::models::LabelAllocator`]
+/// are stored in a vector indexed by their dense index, counted from the first such
+/// label inserted, so that assemblers sharing an allocator only pay for their own
+/// labels. The global labels, and the allocated ones below the first, are stored
+/// in a hash map. The order of insertion is kept as well, so that the map can be
+/// truncated.
+#[derive(Debug, Clone)]
+#[must_use]
+pub(super) struct LabelMap<T> {
+    allocated_base: u32,
+    allocated: Vec<Option<T>>,
+    sparse: HashMap<Label, T>,
+    order: Vec<Label>,
+}
+
+// Labels are taken by reference, like in `HashMap`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+impl<T> LabelMap<T> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            allocated_base: 0,
+            allocated: Vec::new(),
+            sparse: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns the position of the label in the `allocated` vector, if it is stored there.
+    #[inline(always)]
+    fn dense_index(&self, label: Label) -> Option<usize> {
+        let index = label.allocated_index()?;
+        if index < self.allocated_base {
+            return None;
+        }
+        Some((index - self.allocated_base) as usize)
+    }
+
+    #[inline]
+    pub fn get(&self, label: &Label) -> Option<&T> {
+        match self.dense_index(*label) {
+            Some(index) => self.allocated.get(index)?.as_ref(),
+            None => self.sparse.get(label),
+        }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, label: &Label) -> Option<&mut T> {
+        match self.dense_index(*label) {
+            Some(index) => self.allocated.get_mut(index)?.as_mut(),
+            None => self.sparse.get_mut(label),
+        }
+    }
+
+    #[inline(always)]
+    pub fn contains_key(&self, label: &Label) -> bool {
+        self.get(label).is_some()
+    }
+
+    /// Inserts the value, returning the previous one, if any.
+    pub fn insert(&mut self, label: Label, value: T) -> Option<T> {
+        if self.allocated.is_empty() {
+            if let Some(index) = label.allocated_index() {
+                self.allocated_base = index;
+            }
+        }
+
+        let previous = match self.dense_index(label) {
+            Some(index) => {
+                if index >= self.allocated.len() {
+                    self.allocated.resize_with(index + 1, || None);
+                }
+                self.allocated[index].replace(value)
+            }
+            None => self.sparse.insert(label, value),
+        };
+        if previous.is_none() {
+            self.order.push(label);
+        }
+        previous
+    }
+
+    /// Removes the values of the labels inserted after the first `len` ones.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.order.len() {
+            return;
+        }
+        let removed: Vec<Label> = self.order.drain(len..).collect();
+        for label in removed {
+            match self.dense_index(label) {
+                Some(index) => self.allocated[index] = None,
+                None => {
+                    self.sparse.remove(&label);
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the label, inserting the default one if there is none.
+    pub fn get_or_insert_default(&mut self, label: Label) -> &mut T
+    where
+        T: Default,
+    {
+        if !self.contains_key(&label) {
+            self.insert(label, T::default());
+        }
+        self.get_mut(&label).expect("The value is inserted above.")
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Label, &T)> {
+        let allocated = self.allocated.iter().enumerate().filter_map(|(index, value)| {
+            #[allow(clippy::cast_possible_truncation)]
+            let label = Label::from_allocated_index(self.allocated_base + index as u32);
+            value.as_ref().map(|value| (label, value))
+        });
+        let sparse = self.sparse.iter().map(|(label, value)| (*label, value));
+        allocated.chain(sparse)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.allocated
+            .iter_mut()
+            .filter_map(Option::as_mut)
+            .chain(self.sparse.values_mut())
+    }
+}
@@ -1,5 +1,11 @@
+mod code_mode;
+pub use code_mode::*;
+
+mod code_mode_bit16;
+
 mod fragment;
 mod instructions;
+mod label_map;
 mod macros;
 
 mod x86_64_assembler_assemble;
@@ -11,3 +17,6 @@ pub use x86_64_assembler::*;
 
 mod x86_64_assembler_builder;
 pub use x86_64_assembler_builder::*;
+
+mod section_layout;
+pub use section_layout::*;
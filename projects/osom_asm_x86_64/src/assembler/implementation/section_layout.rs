@@ -0,0 +1,49 @@
+use crate::models::{Alignment, Section};
+
+/// The order and alignment of [`Section`]s in the assembled code,
+/// see [`X86_64Assembler::assemble_with_layout`][`super::X86_64Assembler::assemble_with_layout`].
+///
+/// Sections that are used but missing from the layout are placed after
+/// the listed ones, in the order of their first use, without alignment.
+/// Listed sections that are never used are skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct SectionLayout {
+    sections: Vec<(Section, Option<Alignment>)>,
+}
+
+impl SectionLayout {
+    /// Creates an empty [`SectionLayout`], i.e. sections are placed in the
+    /// order of their first use.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { sections: Vec::new() }
+    }
+
+    /// Places `section` after the previously added sections. Adding the
+    /// same section again moves it to the end.
+    #[inline]
+    pub fn with_section(self, section: Section) -> Self {
+        self.push(section, None)
+    }
+
+    /// Same as [`SectionLayout::with_section`], but the section starts at
+    /// a multiple of `alignment`, e.g. a page boundary so that it can be
+    /// mapped with its own permissions. The padding is filled with zeros.
+    #[inline]
+    pub fn with_aligned_section(self, section: Section, alignment: Alignment) -> Self {
+        self.push(section, Some(alignment))
+    }
+
+    /// Returns the sections in the order of the layout.
+    #[inline(always)]
+    pub fn sections(&self) -> &[(Section, Option<Alignment>)] {
+        &self.sections
+    }
+
+    fn push(mut self, section: Section, alignment: Option<Alignment>) -> Self {
+        self.sections.retain(|(listed, _)| *listed != section);
+        self.sections.push((section, alignment));
+        self
+    }
+}
@@ -1,29 +1,128 @@
 #![allow(clippy::cast_ptr_alignment)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use osom_tools_runtime::InlineVec;
 
 use crate::assembler::EmitError;
+use crate::assembler::implementation::CodeMode;
 use crate::assembler::implementation::fragment::RelaxationVariant;
-use crate::models::Label;
+use crate::models::{AlignFill, Alignment, Data, Instruction, Label, LabelAllocator, Section, Symbol};
 
+use super::SectionLayout;
 use super::fragment::{Fragment, FragmentOrderId};
+use super::label_map::LabelMap;
 use super::macros::{fragment_at_index, fragment_at_index_mut};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[must_use]
 pub(super) struct FragmentRelativePosition {
     pub fragment_id: FragmentOrderId,
     pub in_fragment_offset: i32,
 }
 
+/// How the patched imm32 is computed from the label position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(super) enum PatchKind {
+    /// Distance from the end of the instruction to the label.
+    Relative,
+
+    /// Position of the label, i.e. its absolute address.
+    Absolute,
+
+    /// Same as [`PatchKind::Relative`], but 16-bit wide.
+    Relative16,
+
+    /// Same as [`PatchKind::Absolute`], but 16-bit wide.
+    Absolute16,
+
+    /// Same as [`PatchKind::Absolute`], but 64-bit wide.
+    Absolute64,
+
+    /// Distance from the given label to the label.
+    Difference(Label),
+}
+
+/// The encoding of a single instruction, captured instead of being
+/// written to the fragments, so that it can be rewritten for 16-bit code.
+#[derive(Debug, Clone, Default)]
+pub(super) struct InstructionCapture {
+    pub bytes: Vec<u8>,
+
+    /// Offset of the RIP-relative displacement in `bytes`, if any.
+    pub displacement_offset: Option<usize>,
+}
+
 #[derive(Debug, Clone)]
 #[must_use]
 pub(super) struct PatchableImm32Instruction {
     pub instruction_position: FragmentRelativePosition,
     pub instruction_length: u8,
     pub imm32_offset: u8,
+    pub kind: PatchKind,
+}
+
+/// Veneer for calls and jumps to absolute addresses: `jmp [rip + 0]`
+/// followed by the 8-byte address.
+const VENEER_JUMP: [u8; 6] = [0xFF, 0x25, 0x00, 0x00, 0x00, 0x00];
+#[allow(clippy::cast_possible_wrap)]
+const VENEER_LENGTH: i64 = (VENEER_JUMP.len() + size_of::<u64>()) as i64;
+
+/// A call or jump to an absolute address, see
+/// [`Instruction::Call_Absolute`][`crate::models::Instruction::Call_Absolute`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub(super) struct AbsoluteTargetInstruction {
+    pub address: u64,
+    pub instruction: PatchableImm32Instruction,
+}
+
+/// A constant stored in the constant pool, which is emitted after the code.
+#[derive(Debug, Clone)]
+#[must_use]
+pub(super) struct PoolConstant {
+    pub label: Label,
+    pub bytes: Box<[u8]>,
+    pub alignment: Alignment,
+}
+
+/// A part of the fragments that belongs to a single section. It spans
+/// from `start` to the start of the next run.
+#[derive(Debug, Clone)]
+#[must_use]
+pub(super) struct SectionRun {
+    pub section: Section,
+    pub start: i32,
+}
+
+/// The fragments of a section after [`X86_64Assembler::_arrange_sections`],
+/// from the `start` fragment up to, but excluding, the `end` fragment.
+#[derive(Debug, Clone)]
+#[must_use]
+pub(super) struct SectionBounds {
+    pub section: Section,
+    pub start: FragmentOrderId,
+    pub end: FragmentOrderId,
+}
+
+/// The state of an [`X86_64Assembler`] at some point of the emission,
+/// see [`X86_64Assembler::checkpoint`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Checkpoint {
+    position: FragmentRelativePosition,
+    fragments_length: usize,
+    fragments_count: u32,
+    last_fragment_bytes: Option<(i32, i32)>,
+    labels_count: usize,
+    moved_labels_count: usize,
+    patchable_labels_count: usize,
+    public_labels_count: usize,
+    symbols_count: usize,
+    absolute_targets_count: usize,
+    section_runs_count: usize,
+    current_section: Section,
 }
 
 /// The main `X86_64` assembler.
@@ -34,16 +133,36 @@ pub(super) struct PatchableImm32Instruction {
 /// free, and in fact of quadratic complexity. Thus the option is switchable.
 ///
 /// Note that relaxation is enabled by default.
+///
+/// Despite the name, the assembler can also emit 32-bit code,
+/// see [`CodeMode`].
 #[derive(Clone)]
 #[must_use]
 pub struct X86_64Assembler {
-    pub(super) label_offsets: HashMap<Label, FragmentRelativePosition>,
-    pub(super) patchable_addresses: HashMap<Label, InlineVec<PatchableImm32Instruction, 5>>,
+    pub(super) label_allocator: LabelAllocator,
+    pub(super) label_offsets: LabelMap<FragmentRelativePosition>,
+    /// The labels moved by aligned data, with their positions before the move.
+    pub(super) moved_labels: Vec<(Label, FragmentRelativePosition)>,
+    pub(super) predefined_labels: LabelMap<i32>,
+    pub(super) patchable_addresses: LabelMap<InlineVec<PatchableImm32Instruction, 5>>,
+    pub(super) external_labels: LabelMap<()>,
+    pub(super) undefined_labels_as_external: bool,
     pub(super) public_labels: Vec<Label>,
+    pub(super) symbols: HashMap<String, Symbol>,
     pub(super) fragments: Vec<u8>,
     pub(super) last_fragment_offset: i32,
     pub(super) fragments_count: u32,
     pub(super) with_relaxation: bool,
+    pub(super) with_apx: bool,
+    pub(super) mode: CodeMode,
+    pub(super) base_address: Option<u64>,
+    pub(super) start_offset: i32,
+    pub(super) absolute_targets: Vec<AbsoluteTargetInstruction>,
+    pub(super) constants: Vec<PoolConstant>,
+    pub(super) constant_labels: HashMap<(Box<[u8]>, Alignment), Label>,
+    pub(super) current_section: Section,
+    pub(super) section_runs: Vec<SectionRun>,
+    pub(super) capture: Option<InstructionCapture>,
 }
 
 #[allow(clippy::cast_possible_wrap)]
@@ -74,8 +193,25 @@ impl X86_64Assembler {
     /// # Arguments
     ///
     /// * `with_relaxation` - whether to enable relaxation optimization or not.
+    /// * `with_apx` - whether to allow APX encodings or not.
+    /// * `mode` - the processor mode the code is emitted for.
+    /// * `base_address` - the address the buffer of the code is going to be loaded at, if known.
+    /// * `start_offset` - the position of the code in its buffer.
+    /// * `undefined_labels_as_external` - whether labels that are never set are external.
+    /// * `label_allocator` - the allocator of [`X86_64Assembler::new_label`].
+    /// * `predefined_labels` - the positions of labels outside of the code, in the buffer.
     #[inline(always)]
-    pub(super) fn new(with_relaxation: bool, predefined_labels: HashMap<Label, FragmentRelativePosition>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new(
+        with_relaxation: bool,
+        with_apx: bool,
+        mode: CodeMode,
+        base_address: Option<u64>,
+        start_offset: i32,
+        undefined_labels_as_external: bool,
+        label_allocator: LabelAllocator,
+        predefined_labels: LabelMap<i32>,
+    ) -> Self {
         let mut fragments = Vec::<u8>::with_capacity(1 << 12);
         let initial_fragment = Fragment::Bytes {
             data_length: 0,
@@ -84,13 +220,32 @@ impl X86_64Assembler {
         fragments.extend_from_slice(initial_fragment.slice_of_header());
 
         Self {
-            label_offsets: predefined_labels,
-            patchable_addresses: HashMap::with_capacity(16),
+            label_allocator,
+            label_offsets: LabelMap::new(),
+            moved_labels: Vec::new(),
+            predefined_labels,
+            patchable_addresses: LabelMap::new(),
+            external_labels: LabelMap::new(),
+            undefined_labels_as_external,
             public_labels: Vec::with_capacity(4),
+            symbols: HashMap::new(),
             fragments: fragments,
             last_fragment_offset: 0,
             fragments_count: 1,
             with_relaxation,
+            with_apx,
+            mode,
+            base_address,
+            start_offset,
+            absolute_targets: Vec::new(),
+            constants: Vec::new(),
+            constant_labels: HashMap::new(),
+            current_section: Section::TEXT,
+            section_runs: vec![SectionRun {
+                section: Section::TEXT,
+                start: 0,
+            }],
+            capture: None,
         }
     }
 
@@ -145,6 +300,20 @@ impl X86_64Assembler {
         }
     }
 
+    /// Returns the position of the next instruction bytes. Unlike
+    /// [`Self::_current_position`], this always points into a bytes fragment,
+    /// so that the instruction can be patched later.
+    pub(super) fn _instruction_position(&mut self) -> FragmentRelativePosition {
+        let current_fragment = fragment_at_index!(self, self.last_fragment_offset);
+        if !matches!(current_fragment, Fragment::Bytes { .. }) {
+            self._push_new_fragment(Fragment::Bytes {
+                data_length: 0,
+                capacity: FRAGMENT_SIZE,
+            });
+        }
+        self._current_position()
+    }
+
     #[allow(clippy::needless_pass_by_value, clippy::checked_conversions)]
     pub(super) fn _push_new_fragment(&mut self, fragment: Fragment) {
         let current_fragment = fragment_at_index!(self, self.last_fragment_offset);
@@ -196,12 +365,20 @@ impl X86_64Assembler {
         if self.with_relaxation {
             RelaxationVariant::Short
         } else {
-            RelaxationVariant::Long
+            self._long_relaxation_variant()
+        }
+    }
+
+    #[inline(always)]
+    pub(super) const fn _long_relaxation_variant(&self) -> RelaxationVariant {
+        match self.mode {
+            CodeMode::Bit64 | CodeMode::Bit32 => RelaxationVariant::Long,
+            CodeMode::Bit16 => RelaxationVariant::Long16,
         }
     }
 
     pub(super) fn _insert_label(&mut self, label: Label) -> Result<(), EmitError> {
-        if self.label_offsets.contains_key(&label) {
+        if self._is_defined(label) {
             return Err(EmitError::LabelAlreadyDefined(label));
         }
         let label_offset = self._current_position();
@@ -209,8 +386,378 @@ impl X86_64Assembler {
         Ok(())
     }
 
+    pub(super) fn _checkpoint(&self) -> Checkpoint {
+        let last_fragment_bytes = match fragment_at_index!(self, self.last_fragment_offset) {
+            Fragment::Bytes { data_length, capacity } => Some((*data_length, *capacity)),
+            _ => None,
+        };
+        Checkpoint {
+            position: self._current_position(),
+            fragments_length: self.fragments.len(),
+            fragments_count: self.fragments_count,
+            last_fragment_bytes,
+            labels_count: self.label_offsets.len(),
+            moved_labels_count: self.moved_labels.len(),
+            patchable_labels_count: self.patchable_addresses.len(),
+            public_labels_count: self.public_labels.len(),
+            symbols_count: self.symbols.len(),
+            absolute_targets_count: self.absolute_targets.len(),
+            section_runs_count: self.section_runs.len(),
+            current_section: self.current_section,
+        }
+    }
+
+    pub(super) fn _rollback(&mut self, checkpoint: &Checkpoint) {
+        assert!(
+            checkpoint.fragments_length <= self.fragments.len()
+                && checkpoint.labels_count <= self.label_offsets.len()
+                && checkpoint.moved_labels_count <= self.moved_labels.len()
+                && checkpoint.section_runs_count <= self.section_runs.len(),
+            "Checkpoint is newer than the state of the assembler."
+        );
+        let is_after_checkpoint = |position: &FragmentRelativePosition| {
+            (position.fragment_id.index(), position.in_fragment_offset)
+                > (
+                    checkpoint.position.fragment_id.index(),
+                    checkpoint.position.in_fragment_offset,
+                )
+        };
+
+        self.fragments.truncate(checkpoint.fragments_length);
+        self.last_fragment_offset = checkpoint.position.fragment_id.index();
+        self.fragments_count = checkpoint.fragments_count;
+        if let Some((old_data_length, old_capacity)) = checkpoint.last_fragment_bytes {
+            let Fragment::Bytes { data_length, capacity } = fragment_at_index_mut!(self, self.last_fragment_offset)
+            else {
+                panic!("Last fragment of the checkpoint is not a bytes fragment.");
+            };
+            *data_length = old_data_length;
+            *capacity = old_capacity;
+        }
+
+        self.label_offsets.truncate(checkpoint.labels_count);
+        // Labels set before the checkpoint may be moved to aligned data after it.
+        for (label, position) in self.moved_labels.drain(checkpoint.moved_labels_count..).rev() {
+            if let Some(current) = self.label_offsets.get_mut(&label) {
+                *current = position;
+            }
+        }
+
+        self.patchable_addresses.truncate(checkpoint.patchable_labels_count);
+        for patchables in self.patchable_addresses.values_mut() {
+            let mut kept = InlineVec::default();
+            for patchable in patchables.as_slice() {
+                if !is_after_checkpoint(&patchable.instruction_position)
+                    && patchable.instruction_position != checkpoint.position
+                {
+                    kept.push(patchable.clone());
+                }
+            }
+            *patchables = kept;
+        }
+
+        self.public_labels.truncate(checkpoint.public_labels_count);
+        if self.symbols.len() > checkpoint.symbols_count {
+            let label_offsets = &self.label_offsets;
+            self.symbols
+                .retain(|_, symbol| label_offsets.contains_key(&symbol.label()));
+        }
+        self.absolute_targets.truncate(checkpoint.absolute_targets_count);
+        self.section_runs.truncate(checkpoint.section_runs_count);
+        self.current_section = checkpoint.current_section;
+    }
+
+    /// Returns `true` if the label is set in the code or predefined.
+    #[inline]
+    pub(super) fn _is_defined(&self, label: Label) -> bool {
+        self.label_offsets.contains_key(&label) || self.predefined_labels.contains_key(&label)
+    }
+
+    /// Returns `true` if the label is never set and it is declared as external,
+    /// explicitly or with [`X86_64AssemblerBuilder::with_undefined_labels_as_external`][`super::X86_64AssemblerBuilder::with_undefined_labels_as_external`].
+    #[inline]
+    pub(super) fn _is_external(&self, label: Label) -> bool {
+        !self._is_defined(label) && (self.undefined_labels_as_external || self.external_labels.contains_key(&label))
+    }
+
+    /// Returns how labeled memory operands are patched: RIP-relative
+    /// in 64-bit code and absolute otherwise.
     #[inline(always)]
+    pub(super) const fn _memory_patch_kind(&self) -> PatchKind {
+        match self.mode {
+            CodeMode::Bit64 => PatchKind::Relative,
+            CodeMode::Bit32 => PatchKind::Absolute,
+            CodeMode::Bit16 => PatchKind::Absolute16,
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn _push_absolute_target_instruction(&mut self, address: u64, patch_info: PatchableImm32Instruction) {
+        self.absolute_targets.push(AbsoluteTargetInstruction {
+            address,
+            instruction: patch_info,
+        });
+    }
+
+    /// Returns the label of the pool constant, adding it to the pool
+    /// unless an identical constant is already there.
+    pub(super) fn _pool_constant(&mut self, data: &Data, alignment: Alignment) -> Label {
+        let mut bytes = Vec::new();
+        data.write_to(&mut bytes);
+        let key = (bytes.into_boxed_slice(), alignment);
+        if let Some(label) = self.constant_labels.get(&key) {
+            return *label;
+        }
+
+        let label = self.label_allocator.allocate();
+        self.constants.push(PoolConstant {
+            label,
+            bytes: key.0.clone(),
+            alignment,
+        });
+        self.constant_labels.insert(key, label);
+        label
+    }
+
+    /// Emits the constant pool at the end of [`Section::RODATA`]. Constants are
+    /// ordered by decreasing alignment, so the padding is only needed before each
+    /// alignment group.
+    pub(super) fn _emit_constant_pool(&mut self) {
+        let mut constants = core::mem::take(&mut self.constants);
+        if constants.is_empty() {
+            return;
+        }
+        self._switch_section(Section::RODATA);
+        constants.sort_by_key(|constant| core::cmp::Reverse(constant.alignment.value()));
+
+        let mut last_alignment = None;
+        for constant in &constants {
+            if last_alignment != Some(constant.alignment) {
+                self._emit_instruction(&Instruction::Align {
+                    boundary: constant.alignment,
+                    fill: AlignFill::Zero,
+                })
+                .expect("Align is supported in every code mode.");
+                last_alignment = Some(constant.alignment);
+            }
+            self._insert_label(constant.label)
+                .expect("Pool constant labels are unique.");
+            self._write_bytes_internal(&constant.bytes);
+        }
+    }
+
     pub(super) fn _push_patchable_instruction(&mut self, label: Label, patch_info: PatchableImm32Instruction) {
-        self.patchable_addresses.entry(label).or_default().push(patch_info);
+        if let Some(capture) = &mut self.capture {
+            let offset = capture.bytes.len() + patch_info.imm32_offset as usize;
+            capture.displacement_offset = Some(offset);
+            return;
+        }
+        self.patchable_addresses.get_or_insert_default(label).push(patch_info);
+    }
+
+    /// Emits the veneers of calls and jumps to absolute addresses at the end of
+    /// [`Section::TEXT`], see [`Instruction::Call_Absolute`]. The calls and jumps
+    /// that can't reach their address directly are redirected to the veneers.
+    pub(super) fn _emit_veneers(&mut self, layout: &SectionLayout) {
+        if self.absolute_targets.is_empty() {
+            return;
+        }
+
+        // Addresses in the order of first use, so that the output is deterministic.
+        let mut addresses = Vec::new();
+        let mut seen = HashSet::new();
+        for target in &self.absolute_targets {
+            if seen.insert(target.address) {
+                addresses.push(target.address);
+            }
+        }
+
+        #[allow(clippy::cast_possible_wrap)]
+        let code_length_bound = self._code_length_bound(layout) + VENEER_LENGTH * addresses.len() as i64;
+        let mut veneer_labels = HashMap::new();
+        for address in addresses {
+            if self._reaches_directly(address, code_length_bound) {
+                continue;
+            }
+            self._switch_section(Section::TEXT);
+            let label = self.label_allocator.allocate();
+            self._insert_label(label).expect("Veneer labels are unique.");
+            self._write_bytes_internal(&VENEER_JUMP);
+            self._write_bytes_internal(&address.to_le_bytes());
+            veneer_labels.insert(address, label);
+        }
+
+        for target in core::mem::take(&mut self.absolute_targets) {
+            match veneer_labels.get(&target.address) {
+                Some(label) => self._push_patchable_instruction(*label, target.instruction),
+                None => self.absolute_targets.push(target),
+            }
+        }
+    }
+
+    /// Returns whether calls and jumps anywhere in the code, which ends before
+    /// `code_length_bound`, reach `address` with rel32.
+    fn _reaches_directly(&self, address: u64, code_length_bound: i64) -> bool {
+        match (self.mode, self.base_address) {
+            (CodeMode::Bit32, _) => true,
+            (_, None) => false,
+            (_, Some(base_address)) => {
+                let first_end = i128::from(base_address) + i128::from(self.start_offset);
+                let last_end = i128::from(base_address) + i128::from(code_length_bound);
+                [first_end, last_end]
+                    .iter()
+                    .all(|end| i32::try_from(i128::from(address) - end).is_ok())
+            }
+        }
+    }
+
+    /// Returns the upper bound of the end of the code, i.e. with every relaxable
+    /// jump long and every alignment padded to the maximum.
+    fn _code_length_bound(&self, layout: &SectionLayout) -> i64 {
+        let mut bound = i64::from(self.start_offset);
+        for (_, alignment) in layout.sections() {
+            bound += alignment.map_or(0, |alignment| i64::from(alignment.value()) - 1);
+        }
+
+        let end = self._fragment_end_index();
+        let mut index = 0;
+        while index < end {
+            bound += i64::from(fragment_at_index!(self, index).max_data_length());
+            index = self._next_fragment_index(index);
+        }
+        bound
+    }
+
+    /// Starts a new run of `section`, unless it is the current section already.
+    pub(super) fn _switch_section(&mut self, section: Section) {
+        if section == self.current_section {
+            return;
+        }
+        self._push_new_fragment(Fragment::Bytes {
+            data_length: 0,
+            capacity: FRAGMENT_SIZE,
+        });
+        self.section_runs.push(SectionRun {
+            section,
+            start: self.last_fragment_offset,
+        });
+        self.current_section = section;
+    }
+
+    /// Reorders the fragments, so that the runs of each section are contiguous
+    /// and the sections follow the `layout`. Every fragment id stored by the
+    /// assembler is updated to the new position of its fragment.
+    ///
+    /// Returns the bounds of each section, in the final order.
+    pub(super) fn _arrange_sections(&mut self, layout: &SectionLayout) -> Vec<SectionBounds> {
+        let mut order = Vec::<(Section, Option<Alignment>)>::new();
+        for (section, alignment) in layout.sections() {
+            if self.section_runs.iter().any(|run| run.section == *section) {
+                order.push((*section, *alignment));
+            }
+        }
+        for run in &self.section_runs {
+            if !order.iter().any(|(section, _)| *section == run.section) {
+                order.push((run.section, None));
+            }
+        }
+
+        let end_index = self._fragment_end_index();
+        if let [(section, None)] = order.as_slice() {
+            return vec![SectionBounds {
+                section: *section,
+                start: FragmentOrderId::from_index(0),
+                end: FragmentOrderId::from_index(end_index),
+            }];
+        }
+
+        let runs = &self.section_runs;
+        let mut new_starts = vec![0; runs.len()];
+        let mut fragments = Vec::<u8>::with_capacity(self.fragments.len() + order.len() * FRAGMENT_SIZE as usize);
+        let mut last_fragment_offset = 0;
+        let mut bounds = Vec::with_capacity(order.len());
+
+        for (section, alignment) in order {
+            if let Some(boundary) = alignment {
+                let align = Fragment::Align {
+                    boundary,
+                    fill: AlignFill::Zero,
+                    padding: 0,
+                };
+                fragments.extend_from_slice(align.slice_of_header());
+                self.fragments_count += 1;
+            }
+
+            #[allow(clippy::cast_possible_wrap)]
+            let section_start = fragments.len() as i32;
+            for (index, run) in runs.iter().enumerate() {
+                if run.section != section {
+                    continue;
+                }
+                let run_end = runs.get(index + 1).map_or(end_index, |next| next.start);
+
+                #[allow(clippy::cast_possible_wrap)]
+                let new_start = fragments.len() as i32;
+                new_starts[index] = new_start;
+
+                let mut current = run.start;
+                while current < run_end {
+                    last_fragment_offset = new_start + current - run.start;
+                    current = self._next_fragment_index(current);
+                }
+
+                #[allow(clippy::cast_sign_loss)]
+                let (start, end) = (run.start as usize, run_end as usize);
+                fragments.extend_from_slice(&self.fragments[start..end.min(self.fragments.len())]);
+                fragments.resize(fragments.len() + end.saturating_sub(self.fragments.len()), 0);
+            }
+
+            #[allow(clippy::cast_possible_wrap)]
+            let section_end = fragments.len() as i32;
+            bounds.push(SectionBounds {
+                section,
+                start: FragmentOrderId::from_index(section_start),
+                end: FragmentOrderId::from_index(section_end),
+            });
+        }
+
+        let remap = |id: &mut FragmentOrderId| {
+            let index = id.index();
+            let run = runs.partition_point(|run| run.start <= index) - 1;
+            *id = FragmentOrderId::from_index(index - runs[run].start + new_starts[run]);
+        };
+        for position in self.label_offsets.values_mut() {
+            remap(&mut position.fragment_id);
+        }
+        for patchables in self.patchable_addresses.values_mut() {
+            let mut remapped = InlineVec::default();
+            for patchable in patchables.as_slice() {
+                let mut patchable = patchable.clone();
+                remap(&mut patchable.instruction_position.fragment_id);
+                remapped.push(patchable);
+            }
+            *patchables = remapped;
+        }
+        for target in &mut self.absolute_targets {
+            remap(&mut target.instruction.instruction_position.fragment_id);
+        }
+
+        self.fragments = fragments;
+        self.last_fragment_offset = last_fragment_offset;
+        bounds
+    }
+
+    /// Returns the index right after the last fragment, including its capacity.
+    fn _fragment_end_index(&self) -> i32 {
+        self._next_fragment_index(self.last_fragment_offset)
+    }
+
+    fn _next_fragment_index(&self, index: i32) -> i32 {
+        let fragment = fragment_at_index!(self, index);
+        let size = match fragment {
+            Fragment::Bytes { capacity, .. } => *capacity,
+            _ => FRAGMENT_SIZE,
+        };
+        index + size
     }
 }
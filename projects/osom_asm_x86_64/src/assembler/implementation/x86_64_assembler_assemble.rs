@@ -15,12 +15,14 @@ use crate::assembler::implementation::fragment::FragmentOrderId;
 use crate::assembler::implementation::fragment::RelaxationVariant;
 use crate::assembler::implementation::fragment::const_sizes;
 use crate::assembler::implementation::macros::fragment_end;
-use crate::assembler::{AssembleError, EmissionData};
+use crate::assembler::{AssembleError, EmissionData, Relocation, RelocationKind, ResolvedSymbol, SectionPlacement};
+use crate::models::AlignFill;
 use crate::models::Condition;
 use crate::models::Label;
+use crate::models::SymbolSize;
 
 use super::macros::{fragment_at_index, fragment_at_index_mut};
-use super::{X86_64Assembler, fragment::Fragment};
+use super::{CodeMode, PatchKind, PatchableImm32Instruction, SectionBounds, X86_64Assembler, fragment::Fragment};
 
 pub(super) fn calculate_initial_offsets(asm: &X86_64Assembler) -> Result<HashMap<FragmentOrderId, i32>, AssembleError> {
     let mut result = HashMap::with_capacity(asm.fragments_count as usize);
@@ -35,7 +37,7 @@ pub(super) fn calculate_initial_offsets(asm: &X86_64Assembler) -> Result<HashMap
     };
 
     let mut current_fragment = start;
-    let mut current_offset = 0;
+    let mut current_offset = asm.start_offset;
     let fragment_id = get_id(current_fragment);
     result.insert(fragment_id, current_offset);
 
@@ -53,10 +55,13 @@ pub(super) fn calculate_initial_offsets(asm: &X86_64Assembler) -> Result<HashMap
 /// But it is needed, so I won't dive deep into it.
 const MAGIC_SHIFT: isize = 3;
 
+#[allow(clippy::too_many_lines)]
 pub(super) fn relax_instructions_and_update_offsets(
     asm: &mut X86_64Assembler,
     offsets: &mut HashMap<FragmentOrderId, i32>,
 ) -> Result<(), AssembleError> {
+    let long_variant = asm._long_relaxation_variant();
+    let base_address = asm.base_address.unwrap_or(0);
     let start = fragment_at_index_mut!(asm, 0) as *mut Fragment;
     let end = fragment_end!(asm);
 
@@ -74,6 +79,9 @@ pub(super) fn relax_instructions_and_update_offsets(
     };
 
     let get_position = |label: &Label, offsets: &HashMap<FragmentOrderId, i32>| -> Result<i32, AssembleError> {
+        if let Some(position) = asm.predefined_labels.get(label) {
+            return Ok(*position);
+        }
         let Some(label_offset) = asm.label_offsets.get(label) else {
             return Err(AssembleError::LabelNotSet(*label));
         };
@@ -100,9 +108,32 @@ pub(super) fn relax_instructions_and_update_offsets(
                 *offsets.get_mut(&current_id).unwrap() += add;
                 current = unsafe { (*current).next() };
             }
+            // The end of the code is tracked as well, it is the end of the last section.
+            *offsets.get_mut(&get_id(end)).unwrap() += add;
         }};
     }
 
+    // Jumps to labels that are never set have to be external. Their distance
+    // is unknown, so they are always long.
+    let mut current_fragment = start;
+    while current_fragment.cast_const() < end {
+        let current_fragment_ref = unsafe { &mut *current_fragment };
+        let short_length = current_fragment_ref.short_data_length();
+        if let Some((variant, label)) = current_fragment_ref.relaxable_target_mut() {
+            if !asm._is_defined(*label) {
+                if !asm._is_external(*label) {
+                    return Err(AssembleError::LabelNotSet(*label));
+                }
+                if *variant == RelaxationVariant::Short {
+                    *variant = long_variant;
+                    let add = current_fragment_ref.data_length() - short_length.unwrap();
+                    update_subsequent_offsets!(current_fragment, add);
+                }
+            }
+        }
+        current_fragment = unsafe { current_fragment_ref.next() };
+    }
+
     loop {
         let mut has_changes = false;
 
@@ -123,9 +154,9 @@ pub(super) fn relax_instructions_and_update_offsets(
                         let label_position = get_position(label, offsets)? as isize;
                         let diff = current_fragment_offset - label_position - const_sizes::SHORT_JUMP as isize;
                         if (diff < i8::MIN as isize - MAGIC_SHIFT) || (diff > i8::MAX as isize - MAGIC_SHIFT) {
-                            *variant = RelaxationVariant::Long;
+                            *variant = long_variant;
                             has_changes = true;
-                            let add = const_sizes::LONG_JUMP - const_sizes::SHORT_JUMP;
+                            let add = current_fragment_ref.data_length() - const_sizes::SHORT_JUMP;
                             update_subsequent_offsets!(current_fragment, add);
                         }
                     }
@@ -135,13 +166,35 @@ pub(super) fn relax_instructions_and_update_offsets(
                         let label_position = get_position(label, offsets)? as isize;
                         let diff = current_fragment_offset - label_position - const_sizes::SHORT_COND_JUMP as isize;
                         if (diff < i8::MIN as isize - MAGIC_SHIFT) || (diff > i8::MAX as isize - MAGIC_SHIFT) {
-                            *variant = RelaxationVariant::Long;
+                            *variant = long_variant;
+                            has_changes = true;
+                            let add = current_fragment_ref.data_length() - const_sizes::SHORT_COND_JUMP;
+                            update_subsequent_offsets!(current_fragment, add);
+                        }
+                    }
+                }
+                Fragment::Relaxable_CounterJump { variant, label, .. } => {
+                    if *variant == RelaxationVariant::Short {
+                        let label_position = get_position(label, offsets)? as isize;
+                        let diff = current_fragment_offset - label_position - const_sizes::SHORT_COUNTER_JUMP as isize;
+                        if (diff < i8::MIN as isize - MAGIC_SHIFT) || (diff > i8::MAX as isize - MAGIC_SHIFT) {
+                            *variant = long_variant;
                             has_changes = true;
-                            let add = const_sizes::LONG_COND_JUMP - const_sizes::SHORT_COND_JUMP;
+                            let add = current_fragment_ref.data_length() - const_sizes::SHORT_COUNTER_JUMP;
                             update_subsequent_offsets!(current_fragment, add);
                         }
                     }
                 }
+                Fragment::Align { boundary, padding, .. } => {
+                    let position = base_address.wrapping_add(current_fragment_offset as u64);
+                    let new_padding = boundary.padding(position) as i32;
+                    if new_padding != *padding {
+                        let add = new_padding - *padding;
+                        *padding = new_padding;
+                        has_changes = true;
+                        update_subsequent_offsets!(current_fragment, add);
+                    }
+                }
                 Fragment::Bytes { .. } => unreachable!(),
             }
 
@@ -160,9 +213,12 @@ pub(super) fn calculate_labels_map(
     asm: &X86_64Assembler,
     offsets: &HashMap<FragmentOrderId, i32>,
 ) -> Result<HashMap<Label, i32>, AssembleError> {
-    let mut result = HashMap::with_capacity(asm.label_offsets.len());
+    let mut result = HashMap::with_capacity(asm.label_offsets.len() + asm.predefined_labels.len());
+    for (label, position) in asm.predefined_labels.iter() {
+        result.insert(label, *position);
+    }
 
-    for (label, label_offset) in &asm.label_offsets {
+    for (label, label_offset) in asm.label_offsets.iter() {
         let fragment_index = label_offset.fragment_id.index();
         let fragment = fragment_at_index!(asm, fragment_index);
         let relaxation_offset = match fragment {
@@ -172,20 +228,61 @@ pub(super) fn calculate_labels_map(
 
         let fragment_offset = offsets.get(&label_offset.fragment_id).unwrap();
         let position = *fragment_offset + relaxation_offset + label_offset.in_fragment_offset;
-        result.insert(*label, position);
+        result.insert(label, position);
     }
 
     Ok(result)
 }
 
+/// Returns the pointer to the patchable immediate of the instruction.
+///
+/// # Safety
+///
+/// The instruction has to be registered in `asm`, i.e. point into a bytes fragment.
+unsafe fn patchable_imm_ptr(asm: &mut X86_64Assembler, patchable: &PatchableImm32Instruction) -> *mut u8 {
+    let patchable_fragment_index = patchable.instruction_position.fragment_id.index();
+    let patchable_fragment = fragment_at_index!(asm, patchable_fragment_index);
+    debug_assert!(
+        matches!(patchable_fragment, Fragment::Bytes { .. }),
+        "Patchable fragment is not a bytes fragment. Got: {patchable_fragment:?}"
+    );
+    let patchable_fragment_data_offset = size_of::<Fragment>();
+    let patchable_imm32_offset = patchable_fragment_index as usize
+        + patchable_fragment_data_offset
+        + patchable.instruction_position.in_fragment_offset as usize
+        + patchable.imm32_offset as usize;
+
+    unsafe { asm.fragments.as_mut_ptr().add(patchable_imm32_offset) }
+}
+
+/// Returns the final position of the end of the instruction.
+fn instruction_end(patchable: &PatchableImm32Instruction, offsets: &HashMap<FragmentOrderId, i32>) -> isize {
+    let final_fragment_offset = *offsets.get(&patchable.instruction_position.fragment_id).unwrap() as isize;
+    final_fragment_offset
+        + patchable.instruction_length as isize
+        + patchable.instruction_position.in_fragment_offset as isize
+}
+
 pub(super) fn patch_addresses(
     asm: &mut X86_64Assembler,
     labels_map: &HashMap<Label, i32>,
     offsets: &HashMap<FragmentOrderId, i32>,
-) -> Result<(), AssembleError> {
+) -> Result<Vec<Relocation>, AssembleError> {
+    let base_address = i128::from(asm.base_address.unwrap_or(0));
+    let mut relocations = Vec::new();
     unsafe {
-        for (label, patchable_addresses) in &asm.patchable_addresses {
-            let final_label_position = *labels_map.get(label).unwrap() as isize;
+        for (label, patchable_addresses) in asm.patchable_addresses.iter() {
+            let Some(final_label_position) = labels_map.get(&label) else {
+                if !asm._is_external(label) {
+                    return Err(AssembleError::LabelNotSet(label));
+                }
+                for patchable_address in patchable_addresses.as_slice() {
+                    relocations.push(external_relocation(label, patchable_address, offsets)?);
+                }
+                continue;
+            };
+            let final_label_position = *final_label_position as isize;
+            let absolute_address = base_address + final_label_position as i128;
             for patchable_address in patchable_addresses.as_slice() {
                 let patchable_fragment_id = patchable_address.instruction_position.fragment_id;
                 let patchable_fragment_index = patchable_fragment_id.index();
@@ -207,26 +304,222 @@ pub(super) fn patch_addresses(
                     + patchable_address.instruction_length as isize
                     + patchable_address.instruction_position.in_fragment_offset as isize;
                 let distance = final_label_position - final_end_of_instruction;
-                debug_assert!(
-                    distance >= i32::MIN as isize && distance <= i32::MAX as isize,
-                    "Patchable distance is too far. Got: {distance}"
-                );
-                let distance = distance as i32;
-                let imm32 = enc_models::Immediate32::from_i32(distance).encode();
-                patchable_imm32.copy_from_nonoverlapping(imm32.as_ptr(), imm32.len());
+                let final_imm32_offset = final_fragment_offset
+                    + patchable_address.instruction_position.in_fragment_offset as isize
+                    + patchable_address.imm32_offset as isize;
+                let mut relocate = |kind: RelocationKind| {
+                    relocations.push(Relocation::new(
+                        final_imm32_offset as i32,
+                        kind,
+                        label,
+                        final_label_position as i64,
+                    ));
+                };
+                match patchable_address.kind {
+                    PatchKind::Relative => {
+                        debug_assert!(
+                            distance >= i32::MIN as isize && distance <= i32::MAX as isize,
+                            "Patchable distance is too far. Got: {distance}"
+                        );
+                        let imm32 = enc_models::Immediate32::from_i32(distance as i32).encode();
+                        patchable_imm32.copy_from_nonoverlapping(imm32.as_ptr(), imm32.len());
+                    }
+                    PatchKind::Absolute => {
+                        let Ok(address) = u32::try_from(absolute_address) else {
+                            return Err(AssembleError::AddressOutOfRange(label));
+                        };
+                        let imm32 = address.to_le_bytes();
+                        patchable_imm32.copy_from_nonoverlapping(imm32.as_ptr(), imm32.len());
+                        relocate(RelocationKind::Absolute32);
+                    }
+                    PatchKind::Absolute64 => {
+                        let Ok(address) = u64::try_from(absolute_address) else {
+                            return Err(AssembleError::AddressOutOfRange(label));
+                        };
+                        let imm64 = address.to_le_bytes();
+                        patchable_imm32.copy_from_nonoverlapping(imm64.as_ptr(), imm64.len());
+                        relocate(RelocationKind::Absolute64);
+                    }
+                    PatchKind::Relative16 => {
+                        let Ok(distance) = i16::try_from(distance) else {
+                            return Err(AssembleError::AddressOutOfRange(label));
+                        };
+                        let imm16 = distance.to_le_bytes();
+                        patchable_imm32.copy_from_nonoverlapping(imm16.as_ptr(), imm16.len());
+                    }
+                    PatchKind::Absolute16 => {
+                        let Ok(address) = u16::try_from(absolute_address) else {
+                            return Err(AssembleError::AddressOutOfRange(label));
+                        };
+                        let imm16 = address.to_le_bytes();
+                        patchable_imm32.copy_from_nonoverlapping(imm16.as_ptr(), imm16.len());
+                        relocate(RelocationKind::Absolute16);
+                    }
+                    PatchKind::Difference(base) => {
+                        let Some(base_position) = labels_map.get(&base) else {
+                            return Err(missing_label_error(asm, base));
+                        };
+                        let difference = final_label_position - *base_position as isize;
+                        let imm32 = (difference as i32).to_le_bytes();
+                        patchable_imm32.copy_from_nonoverlapping(imm32.as_ptr(), imm32.len());
+                    }
+                }
             }
         }
     }
 
+    push_external_jump_relocations(asm, labels_map, offsets, &mut relocations);
+    relocations.sort_unstable_by_key(Relocation::offset);
+    Ok(relocations)
+}
+
+/// Pushes the relocations of relaxable jumps to external labels. These
+/// are long, with the displacement at the end of the fragment.
+fn push_external_jump_relocations(
+    asm: &X86_64Assembler,
+    labels_map: &HashMap<Label, i32>,
+    offsets: &HashMap<FragmentOrderId, i32>,
+    relocations: &mut Vec<Relocation>,
+) {
+    let mut current_fragment = fragment_at_index!(asm, 0) as *const Fragment;
+    let end = fragment_end!(asm);
+    while current_fragment < end {
+        let current_fragment_ref = unsafe { &*current_fragment };
+        let target = match current_fragment_ref {
+            Fragment::Relaxable_Jump { variant, label }
+            | Fragment::Relaxable_CondJump { variant, label, .. }
+            | Fragment::Relaxable_CounterJump { variant, label, .. } => Some((*variant, *label)),
+            Fragment::Bytes { .. } | Fragment::Align { .. } => None,
+        };
+        if let Some((variant, label)) = target.filter(|(_, label)| !labels_map.contains_key(label)) {
+            let kind = match variant {
+                RelaxationVariant::Long16 => RelocationKind::Relative16,
+                _ => RelocationKind::Relative32,
+            };
+            let size = kind.size() as i32;
+            let fragment_id = FragmentOrderId::from_index(unsafe {
+                current_fragment.cast::<u8>().offset_from(asm.fragments.as_ptr()) as i32
+            });
+            let offset = *offsets.get(&fragment_id).unwrap() + current_fragment_ref.data_length() - size;
+            relocations.push(Relocation::external(offset, kind, label, -i64::from(size)));
+        }
+        current_fragment = unsafe { current_fragment_ref.next() };
+    }
+}
+
+/// Returns the error for the base `label` of a distance, when the label is not set.
+fn missing_label_error(asm: &X86_64Assembler, label: Label) -> AssembleError {
+    if asm._is_external(label) {
+        AssembleError::ExternalLabelDifference(label)
+    } else {
+        AssembleError::LabelNotSet(label)
+    }
+}
+
+/// Returns the relocation of the use site of the external `label`.
+fn external_relocation(
+    label: Label,
+    patchable: &PatchableImm32Instruction,
+    offsets: &HashMap<FragmentOrderId, i32>,
+) -> Result<Relocation, AssembleError> {
+    let imm_offset = *offsets.get(&patchable.instruction_position.fragment_id).unwrap()
+        + patchable.instruction_position.in_fragment_offset
+        + i32::from(patchable.imm32_offset);
+    let addend = i64::from(imm_offset) - instruction_end(patchable, offsets) as i64;
+    let relocation = match patchable.kind {
+        PatchKind::Relative => Relocation::external(imm_offset, RelocationKind::Relative32, label, addend),
+        PatchKind::Relative16 => Relocation::external(imm_offset, RelocationKind::Relative16, label, addend),
+        PatchKind::Absolute => Relocation::external(imm_offset, RelocationKind::Absolute32, label, 0),
+        PatchKind::Absolute16 => Relocation::external(imm_offset, RelocationKind::Absolute16, label, 0),
+        PatchKind::Absolute64 => Relocation::external(imm_offset, RelocationKind::Absolute64, label, 0),
+        // The distance to an external label is not known until linking.
+        PatchKind::Difference(_) => return Err(AssembleError::ExternalLabelDifference(label)),
+    };
+    Ok(relocation)
+}
+
+/// Patches calls and jumps to absolute addresses that reach their targets
+/// directly. The others go through veneers, see [`X86_64Assembler::_emit_veneers`].
+pub(super) fn patch_absolute_targets(
+    asm: &mut X86_64Assembler,
+    offsets: &HashMap<FragmentOrderId, i32>,
+) -> Result<(), AssembleError> {
+    let Some(base_address) = asm.base_address else {
+        debug_assert!(
+            asm.absolute_targets.is_empty(),
+            "Absolute targets without a base address have to go through veneers."
+        );
+        return Ok(());
+    };
+
+    let absolute_targets = std::mem::take(&mut asm.absolute_targets);
+    for target in &absolute_targets {
+        let site = &target.instruction;
+        let distance = i128::from(target.address) - i128::from(base_address) - instruction_end(site, offsets) as i128;
+        let distance = match asm.mode {
+            // The distance wraps around the 32-bit address space.
+            CodeMode::Bit32 => distance as u32 as i32,
+            _ => i32::try_from(distance).expect("Targets out of rel32 range go through veneers."),
+        };
+
+        let imm32 = distance.to_le_bytes();
+        unsafe {
+            let patchable_imm32 = patchable_imm_ptr(asm, site);
+            patchable_imm32.copy_from_nonoverlapping(imm32.as_ptr(), imm32.len());
+        }
+    }
+
+    asm.absolute_targets = absolute_targets;
     Ok(())
 }
 
+pub(super) fn calculate_section_placements(
+    bounds: &[SectionBounds],
+    offsets: &HashMap<FragmentOrderId, i32>,
+) -> Vec<SectionPlacement> {
+    bounds
+        .iter()
+        .map(|bounds| {
+            let start = offsets[&bounds.start];
+            let end = offsets[&bounds.end];
+            SectionPlacement::new(bounds.section, start, end - start)
+        })
+        .collect()
+}
+
+fn resolve_symbols(
+    asm: &X86_64Assembler,
+    labels_map: &HashMap<Label, i32>,
+) -> Result<HashMap<String, ResolvedSymbol>, AssembleError> {
+    let mut result = HashMap::with_capacity(asm.symbols.len());
+    for (name, symbol) in &asm.symbols {
+        let label = symbol.label();
+        let offset = *labels_map.get(&label).ok_or(AssembleError::LabelNotSet(label))?;
+        let size = match symbol.size() {
+            SymbolSize::Unknown => None,
+            SymbolSize::Fixed(size) => Some(size),
+            SymbolSize::UntilLabel(end) => {
+                let end_offset = *labels_map.get(&end).ok_or(AssembleError::LabelNotSet(end))?;
+                let size = u32::try_from(end_offset - offset).map_err(|_| AssembleError::InvalidSymbolSize(end))?;
+                Some(size)
+            }
+        };
+        result.insert(name.clone(), ResolvedSymbol::new(label, symbol.kind(), offset, size));
+    }
+    Ok(result)
+}
+
 pub(super) fn emit_fragments(
     asm: &X86_64Assembler,
     labels_map: &HashMap<Label, i32>,
     offsets: &HashMap<FragmentOrderId, i32>,
+    relocations: Vec<Relocation>,
+    sections: Vec<SectionPlacement>,
     stream: &mut impl std::io::Write,
 ) -> Result<EmissionData, AssembleError> {
+    // Resolved first, so that nothing is written on error.
+    let symbols = resolve_symbols(asm, labels_map)?;
+
     let start = fragment_at_index!(asm, 0) as *const Fragment;
     let end = fragment_end!(asm);
 
@@ -249,10 +542,18 @@ pub(super) fn emit_fragments(
         public_labels.insert(*item, *position);
     }
 
-    let emission_data = EmissionData::new(emitted_bytes as i32, public_labels);
+    let emission_data = EmissionData::new(
+        emitted_bytes as i32,
+        public_labels,
+        relocations,
+        asm.base_address,
+        sections,
+        symbols,
+    );
     Ok(emission_data)
 }
 
+#[allow(clippy::too_many_lines)]
 fn encode_fragment(
     asm: &X86_64Assembler,
     fragment: &Fragment,
@@ -286,7 +587,12 @@ fn encode_fragment(
         }
         Fragment::Relaxable_Jump { variant, label } => {
             let position = get_fragment_position(fragment);
-            let label_position = (*labels_map.get(label).unwrap()) as isize;
+            // External labels are encoded with zero displacement.
+            let label_position = labels_map
+                .get(label)
+                .map_or(position + fragment.data_length() as isize, |label_position| {
+                    *label_position as isize
+                });
             let diff = label_position - position;
             match variant {
                 RelaxationVariant::Short => {
@@ -311,6 +617,15 @@ fn encode_fragment(
                     stream.write_all(encoded.as_slice())?;
                     const_sizes::LONG_JUMP as usize
                 }
+                RelaxationVariant::Long16 => {
+                    let diff = diff - const_sizes::LONG16_JUMP as isize;
+                    let Ok(rel16) = i16::try_from(diff) else {
+                        return Err(AssembleError::AddressOutOfRange(*label));
+                    };
+                    let encoded = enc::jmp::encode_jmp_imm32(enc_models::Immediate32::from_i32(0));
+                    write_rel16_jump(stream, encoded.as_slice(), rel16)?;
+                    const_sizes::LONG16_JUMP as usize
+                }
             }
         }
         Fragment::Relaxable_CondJump {
@@ -319,7 +634,12 @@ fn encode_fragment(
             label,
         } => {
             let position = get_fragment_position(fragment);
-            let label_position = (*labels_map.get(label).unwrap()) as isize;
+            // External labels are encoded with zero displacement.
+            let label_position = labels_map
+                .get(label)
+                .map_or(position + fragment.data_length() as isize, |label_position| {
+                    *label_position as isize
+                });
             let diff = label_position - position;
             match variant {
                 RelaxationVariant::Short => {
@@ -344,13 +664,119 @@ fn encode_fragment(
                     stream.write_all(encoded.as_slice())?;
                     const_sizes::LONG_COND_JUMP as usize
                 }
+                RelaxationVariant::Long16 => {
+                    let diff = diff - const_sizes::LONG16_COND_JUMP as isize;
+                    let Ok(rel16) = i16::try_from(diff) else {
+                        return Err(AssembleError::AddressOutOfRange(*label));
+                    };
+                    let encoded = encode_long_cond_jump(*condition, enc_models::Immediate32::from_i32(0));
+                    write_rel16_jump(stream, encoded.as_slice(), rel16)?;
+                    const_sizes::LONG16_COND_JUMP as usize
+                }
             }
         }
+        Fragment::Relaxable_CounterJump { variant, kind, label } => {
+            let position = get_fragment_position(fragment);
+            // External labels are encoded with zero displacement.
+            let label_position = labels_map
+                .get(label)
+                .map_or(position + fragment.data_length() as isize, |label_position| {
+                    *label_position as isize
+                });
+            let diff = label_position - position;
+            let opcode = kind.opcode();
+            match variant {
+                RelaxationVariant::Short => {
+                    let diff = diff - const_sizes::SHORT_COUNTER_JUMP as isize;
+                    debug_assert!(
+                        diff >= i8::MIN as isize && diff <= i8::MAX as isize,
+                        "Short relaxable counter jump is too far. Got: {diff}"
+                    );
+                    stream.write_all(&[opcode, diff as i8 as u8])?;
+                    const_sizes::SHORT_COUNTER_JUMP as usize
+                }
+                RelaxationVariant::Long => {
+                    let diff = diff - const_sizes::LONG_COUNTER_JUMP as isize;
+                    debug_assert!(
+                        diff >= i32::MIN as isize && diff <= i32::MAX as isize,
+                        "Long relaxable counter jump is too far. Got: {diff}"
+                    );
+                    write_counter_jump_trampoline(stream, opcode, const_sizes::LONG_JUMP)?;
+                    let imm32 = enc_models::Immediate32::from_i32(diff as i32);
+                    let encoded = enc::jmp::encode_jmp_imm32(imm32);
+                    stream.write_all(encoded.as_slice())?;
+                    const_sizes::LONG_COUNTER_JUMP as usize
+                }
+                RelaxationVariant::Long16 => {
+                    let diff = diff - const_sizes::LONG16_COUNTER_JUMP as isize;
+                    let Ok(rel16) = i16::try_from(diff) else {
+                        return Err(AssembleError::AddressOutOfRange(*label));
+                    };
+                    write_counter_jump_trampoline(stream, opcode, const_sizes::LONG16_JUMP)?;
+                    let encoded = enc::jmp::encode_jmp_imm32(enc_models::Immediate32::from_i32(0));
+                    write_rel16_jump(stream, encoded.as_slice(), rel16)?;
+                    const_sizes::LONG16_COUNTER_JUMP as usize
+                }
+            }
+        }
+        Fragment::Align { fill, padding, .. } => {
+            let padding = *padding as usize;
+            write_padding(stream, *fill, padding, asm.mode)?;
+            padding
+        }
     };
 
     Ok(emitted_bytes)
 }
 
+fn write_padding(
+    stream: &mut impl std::io::Write,
+    fill: AlignFill,
+    padding: usize,
+    mode: CodeMode,
+) -> Result<(), AssembleError> {
+    const MAX_NOP_LENGTH: usize = 9;
+
+    match fill {
+        // Multi-byte nops have 32-bit memory operand, which is longer in 16-bit code.
+        AlignFill::Nop if mode == CodeMode::Bit16 => stream.write_all(&vec![0x90; padding])?,
+        AlignFill::Nop => {
+            let mut remaining = padding;
+            while remaining > 0 {
+                let length = remaining.min(MAX_NOP_LENGTH);
+                let encoded = enc::miscellaneous::encode_nop_with_length(length as u8);
+                stream.write_all(encoded.as_slice())?;
+                remaining -= length;
+            }
+        }
+        AlignFill::Int3 => stream.write_all(&vec![0xCC; padding])?,
+        AlignFill::Zero => stream.write_all(&vec![0x00; padding])?,
+    }
+    Ok(())
+}
+
+/// Writes `op +2; jmp +long_jump_length`, i.e. the counter jump `opcode`
+/// taken to the following long jump, and skipping it otherwise.
+fn write_counter_jump_trampoline(
+    stream: &mut impl std::io::Write,
+    opcode: u8,
+    long_jump_length: i32,
+) -> Result<(), AssembleError> {
+    let skip = enc::jmp::encode_jmp_imm8(enc_models::Immediate8::from_i8(long_jump_length as i8));
+    stream.write_all(&[opcode, skip.as_slice().len() as u8])?;
+    stream.write_all(skip.as_slice())?;
+    Ok(())
+}
+
+/// Writes the rel32 jump `encoded` as rel16 jump, i.e. replaces the trailing rel32
+/// with `rel16`. In 16-bit code the same opcodes take rel16 operand.
+fn write_rel16_jump(stream: &mut impl std::io::Write, encoded: &[u8], rel16: i16) -> Result<(), AssembleError> {
+    let opcode = &encoded[..encoded.len() - 4];
+    stream.write_all(opcode)?;
+    stream.write_all(&rel16.to_le_bytes())?;
+    Ok(())
+}
+
 fn encode_short_cond_jump(cond: Condition, imm8: enc_models::Immediate8) -> enc_models::EncodedX86_64Instruction {
     match cond {
         Condition::Equal => enc::jcc::encode_jcc_E_imm8(imm8),
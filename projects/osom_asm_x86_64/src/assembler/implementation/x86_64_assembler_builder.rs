@@ -1,17 +1,23 @@
 use std::collections::HashMap;
 
 use crate::{
-    assembler::implementation::{FragmentRelativePosition, fragment::FragmentOrderId},
-    models::Label,
+    assembler::implementation::label_map::LabelMap,
+    models::{Label, LabelAllocator},
 };
 
-use super::X86_64Assembler;
+use super::{CodeMode, X86_64Assembler};
 
 /// Builder for the [`X86_64Assembler`].
 #[must_use]
 pub struct X86_64AssemblerBuilder {
     with_relaxation: bool,
-    predefined_labels: Option<HashMap<Label, FragmentRelativePosition>>,
+    with_apx: bool,
+    mode: CodeMode,
+    base_address: Option<u64>,
+    start_offset: i32,
+    undefined_labels_as_external: bool,
+    label_allocator: LabelAllocator,
+    predefined_labels: Option<LabelMap<i32>>,
 }
 
 impl X86_64AssemblerBuilder {
@@ -20,6 +26,12 @@ impl X86_64AssemblerBuilder {
     pub const fn new() -> Self {
         Self {
             with_relaxation: true,
+            with_apx: false,
+            mode: CodeMode::Bit64,
+            base_address: None,
+            start_offset: 0,
+            undefined_labels_as_external: false,
+            label_allocator: LabelAllocator::new(),
             predefined_labels: None,
         }
     }
@@ -35,26 +47,104 @@ impl X86_64AssemblerBuilder {
         self
     }
 
+    /// Toggles Intel APX support for the underlying [`X86_64Assembler`].
+    ///
+    /// With APX enabled the assembler accepts the extended `R16` to `R31`
+    /// registers, encoded with REX2 prefix, and the new data destination
+    /// forms of ALU instructions. Disabled by default, since the produced
+    /// code only runs on APX capable CPUs.
+    #[inline(always)]
+    pub const fn with_apx(mut self, with_apx: bool) -> Self {
+        self.with_apx = with_apx;
+        self
+    }
+
+    /// Sets the processor mode the code is emitted for. Defaults to [`CodeMode::Bit64`].
+    ///
+    /// See [`CodeMode`] for the differences between the modes.
+    #[inline(always)]
+    pub const fn with_code_mode(mut self, mode: CodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the address the code is going to be loaded at, like `ORG` directive.
+    ///
+    /// The base address is used to resolve absolute label references, i.e.
+    /// [`Memory::label`][`crate::models::Memory::label`] operands in 32-bit
+    /// and 16-bit code, and label addresses, e.g.
+    /// [`Instruction::LabelAddress64`][`crate::models::Instruction::LabelAddress64`].
+    /// These default to `0` otherwise. In 64-bit code it allows
+    /// [`Instruction::Call_Absolute`][`crate::models::Instruction::Call_Absolute`]
+    /// to reach nearby addresses directly, instead of through a veneer.
+    ///
+    /// The [`EmissionData`][`crate::assembler::EmissionData`] then reports
+    /// absolute addresses of public labels as well.
+    ///
+    /// With [`X86_64AssemblerBuilder::with_start_offset`] this is the address
+    /// of the buffer, not of the code itself.
+    #[inline(always)]
+    pub const fn with_base_address(mut self, base_address: u64) -> Self {
+        self.base_address = Some(base_address);
+        self
+    }
+
+    /// Sets the position of the code in its buffer, for code that is appended
+    /// to previously emitted code, e.g. by a JIT. Defaults to `0`.
+    ///
+    /// All the positions, i.e. of the predefined labels, of the public labels and
+    /// symbols, sections and relocations in [`EmissionData`][`crate::assembler::EmissionData`],
+    /// are relative to the beginning of the buffer, and the base address is the address
+    /// of the buffer. So the public labels of the previous code can be passed as
+    /// the predefined labels of the next one, see [`X86_64AssemblerBuilder::with_predefined_labels`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_offset` is negative.
+    #[inline(always)]
+    pub const fn with_start_offset(mut self, start_offset: i32) -> Self {
+        assert!(start_offset >= 0, "Start offset has to be non-negative.");
+        self.start_offset = start_offset;
+        self
+    }
+
+    /// Toggles treating every label that is referred to, but never set, as external.
+    /// Disabled by default, so that such labels fail the assembly with
+    /// [`AssembleError::LabelNotSet`][`crate::assembler::AssembleError::LabelNotSet`].
+    ///
+    /// See [`X86_64Assembler::declare_external`] for external labels.
+    #[inline(always)]
+    pub const fn with_undefined_labels_as_external(mut self, undefined_labels_as_external: bool) -> Self {
+        self.undefined_labels_as_external = undefined_labels_as_external;
+        self
+    }
+
     /// Sets the predefined labels for the underlying [`X86_64Assembler`].
     ///
     /// The predefined labels are used to emit jump instructions to the given labels.
-    /// The offset is relative to the beginning of the code that is supposed to be emitted.
+    /// The offset is relative to the beginning of the buffer of the code, i.e. to the
+    /// beginning of the code itself, unless [`X86_64AssemblerBuilder::with_start_offset`]
+    /// is set.
     ///
     /// By predefining labels we allow the newly generated code to jump to labels outside
     /// of the code itself.
     pub fn with_predefined_labels(mut self, predefined_labels: &HashMap<Label, i32>) -> Self {
-        let capacity = core::cmp::max(predefined_labels.len(), 16);
-        let mut label_offsets = HashMap::with_capacity(capacity);
-        for (label, offset) in predefined_labels {
-            label_offsets.insert(
-                *label,
-                FragmentRelativePosition {
-                    fragment_id: FragmentOrderId::from_index(0),
-                    in_fragment_offset: *offset,
-                },
-            );
+        let mut label_positions = LabelMap::new();
+        for (label, position) in predefined_labels {
+            label_positions.insert(*label, *position);
         }
-        self.predefined_labels = Some(label_offsets);
+        self.predefined_labels = Some(label_positions);
+        self
+    }
+
+    /// Sets the allocator of [`X86_64Assembler::new_label`]. Defaults to a new
+    /// [`LabelAllocator`].
+    ///
+    /// Pass the allocator of a previous assembler, see [`X86_64Assembler::label_allocator`],
+    /// to refer to its labels, e.g. through [`X86_64AssemblerBuilder::with_predefined_labels`].
+    #[inline(always)]
+    pub fn with_label_allocator(mut self, label_allocator: LabelAllocator) -> Self {
+        self.label_allocator = label_allocator;
         self
     }
 
@@ -63,9 +153,18 @@ impl X86_64AssemblerBuilder {
         let predefined_labels = if let Some(predefined_labels) = self.predefined_labels {
             predefined_labels
         } else {
-            HashMap::with_capacity(16)
+            LabelMap::new()
         };
-        X86_64Assembler::new(self.with_relaxation, predefined_labels)
+        X86_64Assembler::new(
+            self.with_relaxation,
+            self.with_apx,
+            self.mode,
+            self.base_address,
+            self.start_offset,
+            self.undefined_labels_as_external,
+            self.label_allocator,
+            predefined_labels,
+        )
     }
 }
 
@@ -1,39 +1,123 @@
 use osom_encoders_x86_64::encoders as enc;
 use osom_encoders_x86_64::models as enc_models;
 
-use super::fragment::Fragment;
+use super::fragment::{CounterJumpKind, Fragment};
 use crate::assembler::implementation::instructions;
-use crate::{assembler::EmitError, models::Instruction};
+use crate::{
+    assembler::EmitError,
+    models::{AlignFill, DataDirective, Instruction, Label, Symbol},
+};
 
 use super::X86_64Assembler;
+use super::code_mode::{self, CodeMode};
+use super::code_mode_bit16;
 
 mod const_encodings {
     pub(super) const RET: &[u8] = super::enc::ret::encode_ret().as_slice();
     pub(super) const CPUID: &[u8] = super::enc::singleton::encode_cpuid().as_slice();
     pub(super) const SYSCALL: &[u8] = super::enc::singleton::encode_syscall().as_slice();
     pub(super) const LOCK: &[u8] = super::enc::singleton::encode_lock().as_slice();
+    pub(super) const FSQRT: &[u8] = &[0xD9, 0xFA];
+    pub(super) const RDMSR: &[u8] = &[0x0F, 0x32];
+    pub(super) const WRMSR: &[u8] = &[0x0F, 0x30];
+    pub(super) const IRETQ: &[u8] = &[0x48, 0xCF];
+    pub(super) const SWAPGS: &[u8] = &[0x0F, 0x01, 0xF8];
+    pub(super) const SYSRETQ: &[u8] = &[0x48, 0x0F, 0x07];
+    pub(super) const CLI: &[u8] = &[0xFA];
+    pub(super) const STI: &[u8] = &[0xFB];
+    pub(super) const HLT: &[u8] = &[0xF4];
+    pub(super) const XGETBV: &[u8] = &[0x0F, 0x01, 0xD0];
+    pub(super) const XSETBV: &[u8] = &[0x0F, 0x01, 0xD1];
+    pub(super) const XEND: &[u8] = &[0x0F, 0x01, 0xD5];
+    pub(super) const XTEST: &[u8] = &[0x0F, 0x01, 0xD6];
 }
 
 impl X86_64Assembler {
-    #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
+    #[allow(clippy::needless_pass_by_value)]
     #[inline(always)]
     pub(crate) fn _emit_encoded_instruction(
         &mut self,
         encoded_instruction: enc_models::EncodedX86_64Instruction,
     ) -> Result<(), EmitError> {
-        self._write_bytes_internal(encoded_instruction.as_slice());
+        self._emit_bytes(encoded_instruction.as_slice())
+    }
+
+    /// Emits the encoded instruction bytes, verifying that they are valid in the current mode.
+    #[inline(always)]
+    pub(crate) fn _emit_bytes(&mut self, bytes: &[u8]) -> Result<(), EmitError> {
+        if let Some(capture) = &mut self.capture {
+            capture.bytes.extend_from_slice(bytes);
+            return Ok(());
+        }
+        if self.mode != CodeMode::Bit64 {
+            code_mode::check_bit32_encoding(bytes)?;
+        }
+        self._write_bytes_internal(bytes);
         Ok(())
     }
 
+    /// Emits user provided bytes as they are.
     #[allow(clippy::unnecessary_wraps)]
     #[inline(always)]
-    pub(crate) fn _emit_bytes(&mut self, bytes: &[u8]) -> Result<(), EmitError> {
+    pub(crate) fn _emit_raw_bytes(&mut self, bytes: &[u8]) -> Result<(), EmitError> {
         self._write_bytes_internal(bytes);
         Ok(())
     }
 
-    #[allow(clippy::too_many_lines)]
+    pub(crate) fn _emit_data(&mut self, directive: &DataDirective) -> Result<(), EmitError> {
+        if let Some(boundary) = directive.alignment() {
+            let unaligned_position = self._current_position();
+            self._emit_instruction(&Instruction::Align {
+                boundary,
+                fill: AlignFill::Zero,
+            })?;
+
+            // Labels set right before the data have to point at the data, not at the padding.
+            // The original positions are kept, so that a rollback can restore them.
+            let aligned_position = self._current_position();
+            let moved_labels: Vec<Label> = self
+                .label_offsets
+                .iter()
+                .filter(|(_, position)| **position == unaligned_position)
+                .map(|(label, _)| label)
+                .collect();
+            for label in moved_labels {
+                if let Some(position) = self.label_offsets.get_mut(&label) {
+                    *position = aligned_position.clone();
+                }
+                self.moved_labels.push((label, unaligned_position.clone()));
+            }
+        }
+
+        let mut bytes = Vec::new();
+        directive.data().write_to(&mut bytes);
+        self._emit_raw_bytes(&bytes.repeat(directive.count() as usize))
+    }
+
+    pub(crate) fn _emit_symbol(&mut self, symbol: Symbol) -> Result<(), EmitError> {
+        if self.symbols.contains_key(symbol.name()) {
+            return Err(EmitError::SymbolAlreadyDefined(symbol.name().to_owned()));
+        }
+        self._emit_instruction(&Instruction::SetPublic_Label { label: symbol.label() })?;
+        self.symbols.insert(symbol.name().to_owned(), symbol);
+        Ok(())
+    }
+
     pub(crate) fn _emit_instruction(&mut self, instruction: &Instruction) -> Result<(), EmitError> {
+        if self.mode == CodeMode::Bit16 {
+            return code_mode_bit16::emit_bit16_instruction(self, instruction);
+        }
+        code_mode::check_address_size(self.mode, instruction)?;
+        if self.mode == CodeMode::Bit32 {
+            if let Some(translated) = code_mode::translate_bit32_instruction(instruction)? {
+                return self._dispatch_instruction(&translated);
+            }
+        }
+        self._dispatch_instruction(instruction)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    pub(super) fn _dispatch_instruction(&mut self, instruction: &Instruction) -> Result<(), EmitError> {
         match instruction {
             Instruction::SetPrivate_Label { label } => {
                 self._insert_label(*label)?;
@@ -61,6 +145,37 @@ impl X86_64Assembler {
                 self._push_new_fragment(new_fragment);
                 Ok(())
             }
+            Instruction::Loop_Label { kind, dst } => {
+                let new_fragment = Fragment::Relaxable_CounterJump {
+                    variant: self._relaxation_variant(),
+                    kind: CounterJumpKind::Loop(*kind),
+                    label: *dst,
+                };
+                self._push_new_fragment(new_fragment);
+                Ok(())
+            }
+            Instruction::Jrcxz_Label { dst } => {
+                let new_fragment = Fragment::Relaxable_CounterJump {
+                    variant: self._relaxation_variant(),
+                    kind: CounterJumpKind::Jrcxz,
+                    label: *dst,
+                };
+                self._push_new_fragment(new_fragment);
+                Ok(())
+            }
+            Instruction::Align { boundary, fill } => {
+                let new_fragment = Fragment::Align {
+                    boundary: *boundary,
+                    fill: *fill,
+                    padding: 0,
+                };
+                self._push_new_fragment(new_fragment);
+                Ok(())
+            }
+            Instruction::SwitchSection { section } => {
+                self._switch_section(*section);
+                Ok(())
+            }
             Instruction::Ret => self._emit_bytes(const_encodings::RET),
             Instruction::Cpuid => self._emit_bytes(const_encodings::CPUID),
             Instruction::Nop { length } => instructions::emit_nop_with_length(self, *length),
@@ -103,6 +218,273 @@ impl X86_64Assembler {
             Instruction::Int_Imm { src } => instructions::emit_int_imm(self, *src),
             Instruction::Syscall => self._emit_bytes(const_encodings::SYSCALL),
             Instruction::Lock => self._emit_bytes(const_encodings::LOCK),
+            Instruction::Fma_XmmXmmXmm {
+                kind,
+                order,
+                ty,
+                dst,
+                src1,
+                src2,
+            } => instructions::emit_fma_xmm_xmm_xmm(self, *kind, *order, *ty, *dst, *src1, *src2),
+            Instruction::Fma_XmmXmmMem {
+                kind,
+                order,
+                ty,
+                dst,
+                src1,
+                src2,
+            } => instructions::emit_fma_xmm_xmm_mem(self, *kind, *order, *ty, *dst, *src1, src2),
+            Instruction::Fma_YmmYmmYmm {
+                kind,
+                order,
+                ty,
+                dst,
+                src1,
+                src2,
+            } => instructions::emit_fma_ymm_ymm_ymm(self, *kind, *order, *ty, *dst, *src1, *src2),
+            Instruction::Fma_YmmYmmMem {
+                kind,
+                order,
+                ty,
+                dst,
+                src1,
+                src2,
+            } => instructions::emit_fma_ymm_ymm_mem(self, *kind, *order, *ty, *dst, *src1, src2),
+            Instruction::Aes_XmmXmm { kind, dst, src } => instructions::emit_aes_xmm_xmm(self, *kind, *dst, *src),
+            Instruction::Aes_XmmMem { kind, dst, src } => instructions::emit_aes_xmm_mem(self, *kind, *dst, src),
+            Instruction::AesImc_XmmXmm { dst, src } => instructions::emit_aesimc_xmm_xmm(self, *dst, *src),
+            Instruction::AesImc_XmmMem { dst, src } => instructions::emit_aesimc_xmm_mem(self, *dst, src),
+            Instruction::AesKeyGenAssist_XmmXmmImm { dst, src, imm } => {
+                instructions::emit_aeskeygenassist_xmm_xmm_imm(self, *dst, *src, *imm)
+            }
+            Instruction::AesKeyGenAssist_XmmMemImm { dst, src, imm } => {
+                instructions::emit_aeskeygenassist_xmm_mem_imm(self, *dst, src, *imm)
+            }
+            Instruction::Pclmulqdq_XmmXmmImm { dst, src, imm } => {
+                instructions::emit_pclmulqdq_xmm_xmm_imm(self, *dst, *src, *imm)
+            }
+            Instruction::Pclmulqdq_XmmMemImm { dst, src, imm } => {
+                instructions::emit_pclmulqdq_xmm_mem_imm(self, *dst, src, *imm)
+            }
+            Instruction::Sha_XmmXmm { kind, dst, src } => instructions::emit_sha_xmm_xmm(self, *kind, *dst, *src),
+            Instruction::Sha_XmmMem { kind, dst, src } => instructions::emit_sha_xmm_mem(self, *kind, *dst, src),
+            Instruction::Sha1Rnds4_XmmXmmImm { dst, src, imm } => {
+                instructions::emit_sha1rnds4_xmm_xmm_imm(self, *dst, *src, *imm)
+            }
+            Instruction::Sha1Rnds4_XmmMemImm { dst, src, imm } => {
+                instructions::emit_sha1rnds4_xmm_mem_imm(self, *dst, src, *imm)
+            }
+            Instruction::Crc32_RegReg { dst, src } => instructions::emit_crc32_reg_reg(self, *dst, *src),
+            Instruction::Crc32_RegMem { dst, src, size } => instructions::emit_crc32_reg_mem(self, *dst, src, *size),
+            Instruction::Movd_XmmReg { dst, src } => instructions::emit_movd_xmm_reg(self, *dst, *src),
+            Instruction::Movd_RegXmm { dst, src } => instructions::emit_movd_reg_xmm(self, *dst, *src),
+            Instruction::CvtIntToFloat_XmmReg { ty, dst, src } => {
+                instructions::emit_cvt_int_to_float_xmm_reg(self, *ty, *dst, *src)
+            }
+            Instruction::CvtIntToFloat_XmmMem { ty, dst, src, size } => {
+                instructions::emit_cvt_int_to_float_xmm_mem(self, *ty, *dst, src, *size)
+            }
+            Instruction::CvtFloatToInt_RegXmm { ty, truncate, dst, src } => {
+                instructions::emit_cvt_float_to_int_reg_xmm(self, *ty, *truncate, *dst, *src)
+            }
+            Instruction::CvtFloatToInt_RegMem { ty, truncate, dst, src } => {
+                instructions::emit_cvt_float_to_int_reg_mem(self, *ty, *truncate, *dst, src)
+            }
+            Instruction::Cvtss2sd_XmmXmm { dst, src } => instructions::emit_cvtss2sd_xmm_xmm(self, *dst, *src),
+            Instruction::Cvtss2sd_XmmMem { dst, src } => instructions::emit_cvtss2sd_xmm_mem(self, *dst, src),
+            Instruction::Cvtsd2ss_XmmXmm { dst, src } => instructions::emit_cvtsd2ss_xmm_xmm(self, *dst, *src),
+            Instruction::Cvtsd2ss_XmmMem { dst, src } => instructions::emit_cvtsd2ss_xmm_mem(self, *dst, src),
+            Instruction::Round_XmmXmm { ty, mode, dst, src } => {
+                instructions::emit_round_xmm_xmm(self, *ty, *mode, *dst, *src)
+            }
+            Instruction::Round_XmmMem { ty, mode, dst, src } => {
+                instructions::emit_round_xmm_mem(self, *ty, *mode, *dst, src)
+            }
+            Instruction::Vcvtph2ps_XmmXmm { dst, src } => instructions::emit_vcvtph2ps_xmm_xmm(self, *dst, *src),
+            Instruction::Vcvtph2ps_XmmMem { dst, src } => instructions::emit_vcvtph2ps_xmm_mem(self, *dst, src),
+            Instruction::Vcvtph2ps_YmmXmm { dst, src } => instructions::emit_vcvtph2ps_ymm_xmm(self, *dst, *src),
+            Instruction::Vcvtph2ps_YmmMem { dst, src } => instructions::emit_vcvtph2ps_ymm_mem(self, *dst, src),
+            Instruction::Vcvtps2ph_XmmXmm { mode, dst, src } => {
+                instructions::emit_vcvtps2ph_xmm_xmm(self, *mode, *dst, *src)
+            }
+            Instruction::Vcvtps2ph_MemXmm { mode, dst, src } => {
+                instructions::emit_vcvtps2ph_mem_xmm(self, *mode, dst, *src)
+            }
+            Instruction::Vcvtps2ph_XmmYmm { mode, dst, src } => {
+                instructions::emit_vcvtps2ph_xmm_ymm(self, *mode, *dst, *src)
+            }
+            Instruction::Vcvtps2ph_MemYmm { mode, dst, src } => {
+                instructions::emit_vcvtps2ph_mem_ymm(self, *mode, dst, *src)
+            }
+            Instruction::Pcmpistri_XmmXmmImm { dst, src, imm } => {
+                instructions::emit_pcmpistri_xmm_xmm_imm(self, *dst, *src, *imm)
+            }
+            Instruction::Pcmpistri_XmmMemImm { dst, src, imm } => {
+                instructions::emit_pcmpistri_xmm_mem_imm(self, *dst, src, *imm)
+            }
+            Instruction::Pcmpestri_XmmXmmImm { dst, src, imm } => {
+                instructions::emit_pcmpestri_xmm_xmm_imm(self, *dst, *src, *imm)
+            }
+            Instruction::Pcmpestri_XmmMemImm { dst, src, imm } => {
+                instructions::emit_pcmpestri_xmm_mem_imm(self, *dst, src, *imm)
+            }
+            Instruction::Ptest_XmmXmm { dst, src } => instructions::emit_ptest_xmm_xmm(self, *dst, *src),
+            Instruction::Ptest_XmmMem { dst, src } => instructions::emit_ptest_xmm_mem(self, *dst, src),
+            Instruction::Pblendvb_XmmXmm { dst, src, mask } => {
+                instructions::emit_pblendvb_xmm_xmm(self, *dst, *src, *mask)
+            }
+            Instruction::Pblendvb_XmmMem { dst, src, mask } => {
+                instructions::emit_pblendvb_xmm_mem(self, *dst, src, *mask)
+            }
+            Instruction::Blendps_XmmXmmImm { dst, src, imm } => {
+                instructions::emit_blendps_xmm_xmm_imm(self, *dst, *src, *imm)
+            }
+            Instruction::Blendps_XmmMemImm { dst, src, imm } => {
+                instructions::emit_blendps_xmm_mem_imm(self, *dst, src, *imm)
+            }
+            Instruction::PackedMinMax_XmmXmm { kind, dst, src } => {
+                instructions::emit_packed_min_max_xmm_xmm(self, *kind, *dst, *src)
+            }
+            Instruction::PackedMinMax_XmmMem { kind, dst, src } => {
+                instructions::emit_packed_min_max_xmm_mem(self, *kind, *dst, src)
+            }
+            Instruction::Pinsr_XmmRegImm { size, dst, src, imm } => {
+                instructions::emit_pinsr_xmm_reg_imm(self, *size, *dst, *src, *imm)
+            }
+            Instruction::Pinsr_XmmMemImm { size, dst, src, imm } => {
+                instructions::emit_pinsr_xmm_mem_imm(self, *size, *dst, src, *imm)
+            }
+            Instruction::Pextr_RegXmmImm { size, dst, src, imm } => {
+                instructions::emit_pextr_reg_xmm_imm(self, *size, *dst, *src, *imm)
+            }
+            Instruction::Pextr_MemXmmImm { size, dst, src, imm } => {
+                instructions::emit_pextr_mem_xmm_imm(self, *size, dst, *src, *imm)
+            }
+            Instruction::Pmovx_XmmXmm {
+                signed,
+                from,
+                to,
+                dst,
+                src,
+            } => instructions::emit_pmovx_xmm_xmm(self, *signed, *from, *to, *dst, *src),
+            Instruction::Pmovx_XmmMem {
+                signed,
+                from,
+                to,
+                dst,
+                src,
+            } => instructions::emit_pmovx_xmm_mem(self, *signed, *from, *to, *dst, src),
+            Instruction::Fld_Mem { size, src } => instructions::emit_fld_mem(self, *size, src),
+            Instruction::Fld_St { src } => instructions::emit_fld_st(self, *src),
+            Instruction::Fst_Mem { size, dst } => instructions::emit_fst_mem(self, *size, dst),
+            Instruction::Fst_St { dst } => instructions::emit_fst_st(self, *dst),
+            Instruction::Fstp_Mem { size, dst } => instructions::emit_fstp_mem(self, *size, dst),
+            Instruction::Fstp_St { dst } => instructions::emit_fstp_st(self, *dst),
+            Instruction::Fild_Mem { size, src } => instructions::emit_fild_mem(self, *size, src),
+            Instruction::Fistp_Mem { size, dst } => instructions::emit_fistp_mem(self, *size, dst),
+            Instruction::X87Arith_Mem { kind, size, src } => instructions::emit_x87_arith_mem(self, *kind, *size, src),
+            Instruction::X87Arith_St0St { kind, src } => instructions::emit_x87_arith_st0_st(self, *kind, *src),
+            Instruction::X87Arith_StSt0 { kind, dst, pop } => {
+                instructions::emit_x87_arith_st_st0(self, *kind, *dst, *pop)
+            }
+            Instruction::Fxch_St { src } => instructions::emit_fxch_st(self, *src),
+            Instruction::Fcomi_St { src, unordered, pop } => instructions::emit_fcomi_st(self, *src, *unordered, *pop),
+            Instruction::Fldcw_Mem { src } => instructions::emit_fldcw_mem(self, src),
+            Instruction::Fnstcw_Mem { dst } => instructions::emit_fnstcw_mem(self, dst),
+            Instruction::Fsqrt => self._emit_bytes(const_encodings::FSQRT),
+            Instruction::FldConst { constant } => instructions::emit_fld_const(self, *constant),
+            Instruction::Mov_CrReg { dst, src } => instructions::emit_mov_cr_reg(self, *dst, *src),
+            Instruction::Mov_RegCr { dst, src } => instructions::emit_mov_reg_cr(self, *dst, *src),
+            Instruction::Mov_DrReg { dst, src } => instructions::emit_mov_dr_reg(self, *dst, *src),
+            Instruction::Mov_RegDr { dst, src } => instructions::emit_mov_reg_dr(self, *dst, *src),
+            Instruction::Mov_SegReg { dst, src } => instructions::emit_mov_seg_reg(self, *dst, *src),
+            Instruction::Mov_RegSeg { dst, src } => instructions::emit_mov_reg_seg(self, *dst, *src),
+            Instruction::Rdmsr => self._emit_bytes(const_encodings::RDMSR),
+            Instruction::Wrmsr => self._emit_bytes(const_encodings::WRMSR),
+            Instruction::Lgdt_Mem { src } => instructions::emit_lgdt_mem(self, src),
+            Instruction::Lidt_Mem { src } => instructions::emit_lidt_mem(self, src),
+            Instruction::Sgdt_Mem { dst } => instructions::emit_sgdt_mem(self, dst),
+            Instruction::Sidt_Mem { dst } => instructions::emit_sidt_mem(self, dst),
+            Instruction::Ltr_Reg { src } => instructions::emit_ltr_reg(self, *src),
+            Instruction::Ltr_Mem { src } => instructions::emit_ltr_mem(self, src),
+            Instruction::Invlpg_Mem { src } => instructions::emit_invlpg_mem(self, src),
+            Instruction::Iretq => self._emit_bytes(const_encodings::IRETQ),
+            Instruction::Swapgs => self._emit_bytes(const_encodings::SWAPGS),
+            Instruction::Sysret => self._emit_bytes(const_encodings::SYSRETQ),
+            Instruction::Cli => self._emit_bytes(const_encodings::CLI),
+            Instruction::Sti => self._emit_bytes(const_encodings::STI),
+            Instruction::Hlt => self._emit_bytes(const_encodings::HLT),
+            Instruction::In_RegImm { dst, port } => instructions::emit_in_reg_imm(self, *dst, *port),
+            Instruction::In_RegReg { dst, port } => instructions::emit_in_reg_reg(self, *dst, *port),
+            Instruction::Out_ImmReg { port, src } => instructions::emit_out_imm_reg(self, *port, *src),
+            Instruction::Out_RegReg { port, src } => instructions::emit_out_reg_reg(self, *port, *src),
+            Instruction::Xsave_Mem { dst, is_64 } => instructions::emit_xsave_mem(self, dst, *is_64),
+            Instruction::Xrstor_Mem { src, is_64 } => instructions::emit_xrstor_mem(self, src, *is_64),
+            Instruction::Xgetbv => self._emit_bytes(const_encodings::XGETBV),
+            Instruction::Xsetbv => self._emit_bytes(const_encodings::XSETBV),
+            Instruction::FarJump_Mem { dst, size } => instructions::emit_far_jump_mem(self, dst, *size),
+            Instruction::FarCall_Mem { dst, size } => instructions::emit_far_call_mem(self, dst, *size),
+            Instruction::FarRet { size } => instructions::emit_far_ret(self, *size),
+            Instruction::Add_RegRegReg { dst, src1, src2 } => {
+                instructions::emit_add_reg_reg_reg(self, *dst, *src1, *src2)
+            }
+            Instruction::Add_RegRegMem { dst, src1, src2 } => {
+                instructions::emit_add_reg_reg_mem(self, *dst, *src1, src2)
+            }
+            Instruction::Add_RegMemReg { dst, src1, src2 } => {
+                instructions::emit_add_reg_mem_reg(self, *dst, src1, *src2)
+            }
+            Instruction::Add_RegRegImm { dst, src1, src2 } => {
+                instructions::emit_add_reg_reg_imm(self, *dst, *src1, *src2)
+            }
+            Instruction::Add_RegMemImm { dst, src1, src2 } => {
+                instructions::emit_add_reg_mem_imm(self, *dst, src1, *src2)
+            }
+            Instruction::Sub_RegRegReg { dst, src1, src2 } => {
+                instructions::emit_sub_reg_reg_reg(self, *dst, *src1, *src2)
+            }
+            Instruction::Sub_RegRegMem { dst, src1, src2 } => {
+                instructions::emit_sub_reg_reg_mem(self, *dst, *src1, src2)
+            }
+            Instruction::Sub_RegMemReg { dst, src1, src2 } => {
+                instructions::emit_sub_reg_mem_reg(self, *dst, src1, *src2)
+            }
+            Instruction::Sub_RegRegImm { dst, src1, src2 } => {
+                instructions::emit_sub_reg_reg_imm(self, *dst, *src1, *src2)
+            }
+            Instruction::Sub_RegMemImm { dst, src1, src2 } => {
+                instructions::emit_sub_reg_mem_imm(self, *dst, src1, *src2)
+            }
+            Instruction::Xor_RegRegReg { dst, src1, src2 } => {
+                instructions::emit_xor_reg_reg_reg(self, *dst, *src1, *src2)
+            }
+            Instruction::Xor_RegRegMem { dst, src1, src2 } => {
+                instructions::emit_xor_reg_reg_mem(self, *dst, *src1, src2)
+            }
+            Instruction::Xor_RegMemReg { dst, src1, src2 } => {
+                instructions::emit_xor_reg_mem_reg(self, *dst, src1, *src2)
+            }
+            Instruction::Xor_RegRegImm { dst, src1, src2 } => {
+                instructions::emit_xor_reg_reg_imm(self, *dst, *src1, *src2)
+            }
+            Instruction::Xor_RegMemImm { dst, src1, src2 } => {
+                instructions::emit_xor_reg_mem_imm(self, *dst, src1, *src2)
+            }
+            Instruction::Xbegin_Label { dst } => instructions::emit_xbegin_label(self, *dst),
+            Instruction::Xend => self._emit_bytes(const_encodings::XEND),
+            Instruction::Xabort_Imm { src } => instructions::emit_xabort_imm(self, *src),
+            Instruction::Xtest => self._emit_bytes(const_encodings::XTEST),
+            Instruction::Call_Absolute { address } => instructions::emit_call_absolute(self, *address),
+            Instruction::Jump_Absolute { address } => instructions::emit_jmp_absolute(self, *address),
+            Instruction::LabelDifference32 { target, base } => {
+                instructions::emit_label_difference32(self, *target, *base)
+            }
+            Instruction::LabelAddress32 { label } => instructions::emit_label_address32(self, *label),
+            Instruction::LabelAddress64 { label } => instructions::emit_label_address64(self, *label),
+            Instruction::Mov_RegLabel { dst, label } => instructions::emit_mov_reg_label(self, *dst, *label),
+            Instruction::JumpTable_Dispatch { table, index, base } => {
+                instructions::emit_jump_table_dispatch(self, *table, *index, *base)
+            }
         }
     }
 }
@@ -1,29 +1,154 @@
 use crate::assembler::implementation::x86_64_assembler_assemble::{
-    calculate_initial_offsets, calculate_labels_map, emit_fragments, patch_addresses,
-    relax_instructions_and_update_offsets,
+    calculate_initial_offsets, calculate_labels_map, calculate_section_placements, emit_fragments,
+    patch_absolute_targets, patch_addresses, relax_instructions_and_update_offsets,
 };
 use crate::assembler::traits::X86_64Emitable;
 use crate::assembler::{AssembleError, EmissionData, EmitError};
+use crate::models::{Data, Label, LabelAllocator, Memory};
 
-use super::X86_64Assembler;
+use super::{Checkpoint, SectionLayout, X86_64Assembler};
 
 impl X86_64Assembler {
     /// Emits the given value to the underlying [`X86_64Assembler`].
     ///
     /// The method accepts the private `X86_64Emitable` trait. At the moment
-    /// the following types implement it: arrays, slices and [`Instruction`][`crate::models::Instruction`].
+    /// the following types implement it: arrays, slices, [`Instruction`][`crate::models::Instruction`],
+    /// [`Data`], [`DataDirective`][`crate::models::DataDirective`] and [`Symbol`][`crate::models::Symbol`].
     #[allow(private_bounds)]
     #[inline(always)]
     pub fn emit(&mut self, value: impl X86_64Emitable) -> Result<(), EmitError> {
         value.emit_to(self)
     }
 
+    /// Returns a new label from the [`LabelAllocator`] of the assembler.
+    ///
+    /// Unlike [`Label::new`], this doesn't touch the global label counter,
+    /// and the positions of such labels are kept in plain vectors.
+    #[inline(always)]
+    pub fn new_label(&mut self) -> Label {
+        self.label_allocator.allocate()
+    }
+
+    /// Returns the [`LabelAllocator`] of the assembler, e.g. to continue
+    /// the allocation in another assembler, see
+    /// [`X86_64AssemblerBuilder::with_label_allocator`][`crate::assembler::X86_64AssemblerBuilder::with_label_allocator`].
+    #[inline(always)]
+    pub const fn label_allocator(&self) -> &LabelAllocator {
+        &self.label_allocator
+    }
+
+    /// Declares the label as external, i.e. defined outside of the assembled code,
+    /// e.g. another function that is assembled separately.
+    ///
+    /// # Notes
+    ///
+    /// References to the label are not resolved during [`X86_64Assembler::assemble`].
+    /// Instead each use site is encoded in its long form, with zero displacement
+    /// or address, and reported as an external [`Relocation`][`crate::assembler::Relocation`]
+    /// for later linking. Setting the label in the code overrides the declaration.
+    ///
+    /// The distance between labels, e.g. [`Instruction::LabelDifference32`][`crate::models::Instruction::LabelDifference32`],
+    /// can't refer to external labels, assembling such code fails with
+    /// [`AssembleError::ExternalLabelDifference`][`crate::assembler::AssembleError::ExternalLabelDifference`].
+    #[inline]
+    pub fn declare_external(&mut self, label: Label) {
+        self.external_labels.insert(label, ());
+    }
+
+    /// Returns the current state of the assembler, so that the code emitted
+    /// after it can be abandoned with [`X86_64Assembler::rollback`], e.g.
+    /// when trying speculative sequences of instructions.
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self._checkpoint()
+    }
+
+    /// Restores the state of the `checkpoint`, i.e. removes everything emitted
+    /// after it: the instructions, the labels and the symbols.
+    ///
+    /// # Notes
+    ///
+    /// The labels allocated with [`X86_64Assembler::new_label`], the external label
+    /// declarations and the constants of the constant pool are kept, so labels and
+    /// memory operands obtained after the checkpoint stay valid.
+    ///
+    /// The same checkpoint can be rolled back to many times. Rolling back to an earlier
+    /// checkpoint invalidates the later ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `checkpoint` is newer than the state of the assembler.
+    #[inline]
+    pub fn rollback(&mut self, checkpoint: &Checkpoint) {
+        self._rollback(checkpoint);
+    }
+
+    /// Returns memory operand that refers to the `data` in the constant pool.
+    ///
+    /// The constant pool is emitted after the code during [`X86_64Assembler::assemble`],
+    /// with each constant aligned to its [`Data::natural_alignment`]. Identical constants
+    /// are stored only once.
+    #[inline]
+    pub fn constant(&mut self, data: Data<'_>) -> Memory {
+        let label = self._pool_constant(&data, data.natural_alignment());
+        Memory::label(label)
+    }
+
+    /// Same as [`X86_64Assembler::constant`] with [`Data::F32`].
+    #[inline(always)]
+    pub fn constant_f32(&mut self, value: f32) -> Memory {
+        self.constant(Data::F32(value))
+    }
+
+    /// Same as [`X86_64Assembler::constant`] with [`Data::F64`].
+    #[inline(always)]
+    pub fn constant_f64(&mut self, value: f64) -> Memory {
+        self.constant(Data::F64(value))
+    }
+
+    /// Same as [`X86_64Assembler::constant`] with [`Data::U32`].
+    #[inline(always)]
+    pub fn constant_u32(&mut self, value: u32) -> Memory {
+        self.constant(Data::U32(value))
+    }
+
+    /// Same as [`X86_64Assembler::constant`] with [`Data::U64`].
+    #[inline(always)]
+    pub fn constant_u64(&mut self, value: u64) -> Memory {
+        self.constant(Data::U64(value))
+    }
+
     /// Finalizes emitted code, optimizes it and writes the raw binary machine code back to the passed stream.
-    pub fn assemble(mut self, stream: &mut impl std::io::Write) -> Result<EmissionData, AssembleError> {
+    ///
+    /// The sections are placed in the order of their first use, see
+    /// [`X86_64Assembler::assemble_with_layout`].
+    #[inline(always)]
+    pub fn assemble(self, stream: &mut impl std::io::Write) -> Result<EmissionData, AssembleError> {
+        self.assemble_with_layout(&SectionLayout::new(), stream)
+    }
+
+    /// Same as [`X86_64Assembler::assemble`], but the sections are placed
+    /// according to the `layout`.
+    ///
+    /// # Notes
+    ///
+    /// The positions of the sections are reported by [`EmissionData::sections`],
+    /// so that e.g. [`Section::RODATA`][`crate::models::Section::RODATA`] can be
+    /// mapped non-executable.
+    pub fn assemble_with_layout(
+        mut self,
+        layout: &SectionLayout,
+        stream: &mut impl std::io::Write,
+    ) -> Result<EmissionData, AssembleError> {
+        self._emit_constant_pool();
+        self._emit_veneers(layout);
+        let section_bounds = self._arrange_sections(layout);
         let mut offsets = calculate_initial_offsets(&self)?;
         relax_instructions_and_update_offsets(&mut self, &mut offsets)?;
         let labels_map = calculate_labels_map(&self, &offsets)?;
-        patch_addresses(&mut self, &labels_map, &offsets)?;
-        emit_fragments(&self, &labels_map, &offsets, stream)
+        let relocations = patch_addresses(&mut self, &labels_map, &offsets)?;
+        patch_absolute_targets(&mut self, &offsets)?;
+        let sections = calculate_section_placements(&section_bounds, &offsets);
+        emit_fragments(&self, &labels_map, &offsets, relocations, sections, stream)
     }
 }
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use crate::assembler::{AssembleError, EmissionData, Relocation, RelocationKind, X86_64Assembler};
+use crate::models::Alignment;
+
+/// The code and the [`EmissionData`] of an assembled module.
+#[derive(Debug)]
+#[must_use]
+struct LinkerModule {
+    code: Vec<u8>,
+    emission_data: EmissionData,
+}
+
+/// Combines separately assembled modules, e.g. functions, into a single blob
+/// and resolves the references between them.
+///
+/// References to labels of other modules have to be external in the referring
+/// module, see [`X86_64Assembler::declare_external`], and the labels have to be
+/// public in the defining module. The modules have to use labels that are unique
+/// across them, i.e. created with [`Label::new`][`crate::models::Label::new`] or with a shared
+/// [`LabelAllocator`][`crate::models::LabelAllocator`].
+#[derive(Debug, Default)]
+#[must_use]
+pub struct Linker {
+    alignment: Option<Alignment>,
+    base_address: Option<u64>,
+    modules: Vec<LinkerModule>,
+}
+
+impl Linker {
+    /// Creates a new [`Linker`] that places the modules right after each other.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            alignment: None,
+            base_address: None,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Sets the alignment of the beginning of each module. The padding between
+    /// the modules is filled with `int3`s.
+    #[inline(always)]
+    pub const fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Sets the address the linked code is going to be loaded at. Absolute
+    /// addresses in the linked code are resolved against it, or against zero
+    /// if it is not set.
+    #[inline(always)]
+    pub const fn with_base_address(mut self, base_address: u64) -> Self {
+        self.base_address = Some(base_address);
+        self
+    }
+
+    /// Returns the number of added modules.
+    #[inline(always)]
+    #[must_use]
+    pub fn modules_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Assembles the `assembler` and adds it as the next module.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`X86_64Assembler::assemble`], or the one of
+    /// [`Linker::add_assembled`].
+    pub fn add(&mut self, assembler: X86_64Assembler) -> Result<(), AssembleError> {
+        let mut code = Vec::new();
+        let emission_data = assembler.assemble(&mut code)?;
+        self.add_assembled(code, emission_data)
+    }
+
+    /// Adds the already assembled `code`, together with its `emission_data`,
+    /// as the next module. The code has to be assembled without a start offset,
+    /// see [`X86_64AssemblerBuilder::with_start_offset`][`crate::assembler::X86_64AssemblerBuilder::with_start_offset`],
+    /// and without a base address, see
+    /// [`X86_64AssemblerBuilder::with_base_address`][`crate::assembler::X86_64AssemblerBuilder::with_base_address`].
+    /// Use [`Linker::with_base_address`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AssembleError::ModuleWithBaseAddress`] if the module was
+    /// assembled with a base address.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `code` is shorter than the emitted code.
+    pub fn add_assembled(&mut self, mut code: Vec<u8>, emission_data: EmissionData) -> Result<(), AssembleError> {
+        if emission_data.base_address().is_some() {
+            return Err(AssembleError::ModuleWithBaseAddress);
+        }
+        #[allow(clippy::cast_sign_loss)]
+        let emitted_bytes = emission_data.emitted_bytes() as usize;
+        assert!(
+            code.len() >= emitted_bytes,
+            "The code is shorter than the emitted code, got {} bytes instead of {emitted_bytes}.",
+            code.len()
+        );
+        code.truncate(emitted_bytes);
+        self.modules.push(LinkerModule { code, emission_data });
+        Ok(())
+    }
+
+    /// Links the modules in the order they were added and writes the linked
+    /// code to `stream`.
+    ///
+    /// # Notes
+    ///
+    /// The returned [`EmissionData`] describes the linked code: the public labels
+    /// and the symbols of all the modules, and the relocations that still depend
+    /// on the placement of the code. References to labels that no module defines
+    /// are reported as external relocations, so that the linked code can be linked
+    /// further. Sections are not reported, since the sections of different modules
+    /// are interleaved.
+    ///
+    /// # Errors
+    ///
+    /// * [`AssembleError::LabelAlreadyDefined`] if more than one module defines the same public label.
+    /// * [`AssembleError::SymbolAlreadyDefined`] if more than one module defines a symbol with the same name.
+    /// * [`AssembleError::AddressOutOfRange`] if a resolved address doesn't fit in its use site.
+    /// * [`AssembleError::IoError`] if writing to the `stream` fails.
+    pub fn link(self, stream: &mut impl std::io::Write) -> Result<EmissionData, AssembleError> {
+        let base_address = self.base_address.unwrap_or(0);
+        let mut code = Vec::new();
+        let mut public_labels_positions = HashMap::new();
+        let mut symbols = HashMap::new();
+        let mut starts = Vec::with_capacity(self.modules.len());
+
+        for module in &self.modules {
+            if let Some(alignment) = self.alignment {
+                let padding = alignment.padding(base_address.wrapping_add(code.len() as u64));
+                code.resize(code.len() + padding as usize, 0xCC);
+            }
+            let start = code_position(&code);
+            starts.push(start);
+            code.extend_from_slice(&module.code);
+
+            for (label, position) in module.emission_data.public_labels_positions() {
+                if public_labels_positions.insert(*label, start + *position).is_some() {
+                    return Err(AssembleError::LabelAlreadyDefined(*label));
+                }
+            }
+            for (name, symbol) in module.emission_data.symbols() {
+                if symbols.contains_key(name) {
+                    return Err(AssembleError::SymbolAlreadyDefined(name.clone()));
+                }
+                symbols.insert(name.clone(), symbol.moved_by(start));
+            }
+        }
+
+        let mut relocations = Vec::new();
+        for (module, start) in self.modules.iter().zip(starts) {
+            for relocation in module.emission_data.relocations() {
+                let offset = start + relocation.offset();
+                let target = relocation.target();
+                let addend = relocation.addend();
+                if !relocation.is_external() {
+                    relocations.push(Relocation::new(
+                        offset,
+                        relocation.kind(),
+                        target,
+                        i64::from(start) + addend,
+                    ));
+                    continue;
+                }
+
+                let Some(position) = public_labels_positions.get(&target) else {
+                    relocations.push(Relocation::external(offset, relocation.kind(), target, addend));
+                    continue;
+                };
+                let position = i64::from(*position) + addend;
+                match relocation.kind() {
+                    RelocationKind::Relative32 => {
+                        let Ok(distance) = i32::try_from(position - i64::from(offset)) else {
+                            return Err(AssembleError::AddressOutOfRange(target));
+                        };
+                        write_at(&mut code, offset, &distance.to_le_bytes());
+                    }
+                    RelocationKind::Relative16 => {
+                        let Ok(distance) = i16::try_from(position - i64::from(offset)) else {
+                            return Err(AssembleError::AddressOutOfRange(target));
+                        };
+                        write_at(&mut code, offset, &distance.to_le_bytes());
+                    }
+                    kind @ (RelocationKind::Absolute64 | RelocationKind::Absolute32 | RelocationKind::Absolute16) => {
+                        relocations.push(Relocation::new(offset, kind, target, position));
+                    }
+                }
+            }
+        }
+        relocations.sort_unstable_by_key(Relocation::offset);
+
+        let emission_data = EmissionData::new(
+            code_position(&code),
+            public_labels_positions,
+            relocations,
+            self.base_address,
+            Vec::new(),
+            symbols,
+        );
+        emission_data.apply_relocations(&mut code, base_address)?;
+        stream.write_all(&code)?;
+        Ok(emission_data)
+    }
+}
+
+#[inline(always)]
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn code_position(code: &[u8]) -> i32 {
+    debug_assert!(
+        i32::try_from(code.len()).is_ok(),
+        "Linked code is too long, got {} bytes.",
+        code.len()
+    );
+    code.len() as i32
+}
+
+#[inline(always)]
+fn write_at(code: &mut [u8], offset: i32, bytes: &[u8]) {
+    #[allow(clippy::cast_sign_loss)]
+    let offset = offset as usize;
+    code[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
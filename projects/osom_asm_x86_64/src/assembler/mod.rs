@@ -5,6 +5,9 @@ pub use emission_data::*;
 mod errors;
 pub use errors::*;
 
+mod linker;
+pub use linker::*;
+
 mod traits;
 
 mod implementation;
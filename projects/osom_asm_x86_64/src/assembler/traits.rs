@@ -1,6 +1,9 @@
 use osom_encoders_x86_64::models::EncodedX86_64Instruction;
 
-use crate::{assembler::X86_64Assembler, models::Instruction};
+use crate::{
+    assembler::X86_64Assembler,
+    models::{Data, DataDirective, Instruction, Symbol},
+};
 
 use super::EmitError;
 
@@ -13,19 +16,19 @@ pub(crate) trait X86_64Emitable {
 
 impl X86_64Emitable for &[u8] {
     fn emit_to(self, assembler: &mut X86_64Assembler) -> Result<(), EmitError> {
-        assembler._emit_bytes(self)
+        assembler._emit_raw_bytes(self)
     }
 }
 
 impl<const N: usize> X86_64Emitable for [u8; N] {
     fn emit_to(self, assembler: &mut X86_64Assembler) -> Result<(), EmitError> {
-        assembler._emit_bytes(&self)
+        assembler._emit_raw_bytes(&self)
     }
 }
 
 impl<const N: usize> X86_64Emitable for &[u8; N] {
     fn emit_to(self, assembler: &mut X86_64Assembler) -> Result<(), EmitError> {
-        assembler._emit_bytes(self)
+        assembler._emit_raw_bytes(self)
     }
 }
 
@@ -40,3 +43,21 @@ impl X86_64Emitable for EncodedX86_64Instruction {
         assembler._emit_encoded_instruction(self)
     }
 }
+
+impl X86_64Emitable for Data<'_> {
+    fn emit_to(self, assembler: &mut X86_64Assembler) -> Result<(), EmitError> {
+        assembler._emit_data(&DataDirective::new(self))
+    }
+}
+
+impl X86_64Emitable for DataDirective<'_> {
+    fn emit_to(self, assembler: &mut X86_64Assembler) -> Result<(), EmitError> {
+        assembler._emit_data(&self)
+    }
+}
+
+impl X86_64Emitable for Symbol {
+    fn emit_to(self, assembler: &mut X86_64Assembler) -> Result<(), EmitError> {
+        assembler._emit_symbol(self)
+    }
+}
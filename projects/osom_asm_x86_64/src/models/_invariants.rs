@@ -1,6 +1,10 @@
 use core::mem::size_of;
 
-use super::{Condition, GPR, GPRKind, Immediate32, Immediate64, Instruction, Label, Memory, Scale, Size};
+use super::{
+    AesKind, AlignFill, Alignment, CR, Condition, DR, FloatType, FmaKind, FmaOrder, GPR, GPRKind, Immediate32,
+    Immediate64, Instruction, Label, LoopKind, Memory, PackedMinMaxKind, RoundingMode, ST, Scale, Section, Segment,
+    ShaKind, Size, X87ArithKind, X87Constant, X87FloatSize, XMM, YMM,
+};
 
 const _: () = const {
     // Checks some invariants about the size of the models.
@@ -15,10 +19,33 @@ const _: () = const {
     assert!(size_of::<Immediate64>() == 8, "Immediate64 size must be 8 bytes");
     assert!(size_of::<Memory>() <= 8, "Memory size must be at most 8 bytes");
     assert!(size_of::<Label>() == 4, "Label size must be 4 bytes");
+    assert!(size_of::<Section>() == 4, "Section size must be 4 bytes");
     assert!(size_of::<Scale>() == 1, "Scale size must be 1 byte");
     assert!(
         size_of::<Instruction>() <= 16,
         "Instruction size must be at most 16 bytes"
     );
     assert!(size_of::<Condition>() == 1, "Condition size must be 1 byte");
+    assert!(size_of::<LoopKind>() == 1, "LoopKind size must be 1 byte");
+    assert!(size_of::<Alignment>() == 4, "Alignment size must be 4 bytes");
+    assert!(size_of::<AlignFill>() == 1, "AlignFill size must be 1 byte");
+    assert!(size_of::<XMM>() == 1, "XMM size must be 1 byte");
+    assert!(size_of::<YMM>() == 1, "YMM size must be 1 byte");
+    assert!(size_of::<FloatType>() == 1, "FloatType size must be 1 byte");
+    assert!(size_of::<FmaKind>() == 1, "FmaKind size must be 1 byte");
+    assert!(size_of::<FmaOrder>() == 1, "FmaOrder size must be 1 byte");
+    assert!(size_of::<AesKind>() == 1, "AesKind size must be 1 byte");
+    assert!(size_of::<ShaKind>() == 1, "ShaKind size must be 1 byte");
+    assert!(size_of::<RoundingMode>() == 1, "RoundingMode size must be 1 byte");
+    assert!(
+        size_of::<PackedMinMaxKind>() == 1,
+        "PackedMinMaxKind size must be 1 byte"
+    );
+    assert!(size_of::<ST>() == 1, "ST size must be 1 byte");
+    assert!(size_of::<X87FloatSize>() == 1, "X87FloatSize size must be 1 byte");
+    assert!(size_of::<X87ArithKind>() == 1, "X87ArithKind size must be 1 byte");
+    assert!(size_of::<X87Constant>() == 1, "X87Constant size must be 1 byte");
+    assert!(size_of::<CR>() == 1, "CR size must be 1 byte");
+    assert!(size_of::<DR>() == 1, "DR size must be 1 byte");
+    assert!(size_of::<Segment>() == 1, "Segment size must be 1 byte");
 };
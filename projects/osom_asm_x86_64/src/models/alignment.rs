@@ -0,0 +1,54 @@
+/// Represents an error that occurs when creating a new [`Alignment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum NewAlignmentError {
+    /// The boundary is not a power of two.
+    NotPowerOfTwo,
+}
+
+/// Represents an alignment boundary in bytes, which is a power of two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+#[must_use]
+pub struct Alignment {
+    value: u32,
+}
+
+impl Alignment {
+    #[inline]
+    pub const fn new(value: u32) -> Result<Self, NewAlignmentError> {
+        if !value.is_power_of_two() {
+            return Err(NewAlignmentError::NotPowerOfTwo);
+        }
+
+        Ok(Self { value })
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn value(self) -> u32 {
+        self.value
+    }
+
+    /// Returns the number of bytes needed to move `position` to the boundary.
+    #[inline(always)]
+    pub(crate) const fn padding(self, position: u64) -> u32 {
+        let mask = self.value as u64 - 1;
+        ((self.value as u64 - (position & mask)) & mask) as u32
+    }
+}
+
+/// Represents the bytes that fill the padding of [`Instruction::Align`][`super::Instruction::Align`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum AlignFill {
+    /// Multi-byte `nop`s, so that the padding can be executed.
+    Nop = 1,
+
+    /// `int3`s, so that executing the padding traps.
+    Int3,
+
+    /// Zero bytes, for data.
+    Zero,
+}
@@ -0,0 +1,42 @@
+/// Represents a single AES round performed by AES-NI instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum AesKind {
+    /// `aesenc`
+    Encrypt = 1,
+
+    /// `aesenclast`
+    EncryptLast,
+
+    /// `aesdec`
+    Decrypt,
+
+    /// `aesdeclast`
+    DecryptLast,
+}
+
+/// Represents the SHA extension instructions that take
+/// two operands and no immediate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum ShaKind {
+    /// `sha1nexte`
+    Sha1NextE = 1,
+
+    /// `sha1msg1`
+    Sha1Msg1,
+
+    /// `sha1msg2`
+    Sha1Msg2,
+
+    /// `sha256rnds2`, uses `xmm0` as implicit third operand.
+    Sha256Rnds2,
+
+    /// `sha256msg1`
+    Sha256Msg1,
+
+    /// `sha256msg2`
+    Sha256Msg2,
+}
@@ -0,0 +1,204 @@
+use super::Alignment;
+
+/// Represents a data value emitted into the code as is, e.g. a constant
+/// loaded through [`Memory::label`][`super::Memory::label`].
+///
+/// Numbers are emitted in little endian. See [`DataDirective`]
+/// for repeating and aligning the data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[must_use]
+pub enum Data<'a> {
+    /// `db`
+    U8(u8),
+
+    /// `dw`
+    U16(u16),
+
+    /// `dd`
+    U32(u32),
+
+    /// `dq`
+    U64(u64),
+
+    /// `db`
+    I8(i8),
+
+    /// `dw`
+    I16(i16),
+
+    /// `dd`
+    I32(i32),
+
+    /// `dq`
+    I64(i64),
+
+    /// `dd` with IEEE 754 single precision float.
+    F32(f32),
+
+    /// `dq` with IEEE 754 double precision float.
+    F64(f64),
+
+    /// 128-bit vector constant, e.g. `xmm` operand.
+    Vector128([u8; 16]),
+
+    /// 256-bit vector constant, e.g. `ymm` operand.
+    Vector256([u8; 32]),
+
+    /// Raw bytes.
+    Bytes(&'a [u8]),
+
+    /// The string followed by a NUL byte, like C strings.
+    NulTerminatedString(&'a str),
+
+    /// The string preceded by its length in bytes, as `u32`.
+    LengthPrefixedString(&'a str),
+}
+
+macro_rules! vector_from_lanes {
+    ($name:ident, $variant:ident, $lane:ty, $count:literal) => {
+        #[doc = concat!("Creates [`Data::", stringify!($variant), "`] from `", stringify!($lane), "` lanes.")]
+        pub fn $name(lanes: [$lane; $count]) -> Self {
+            let mut bytes = [0; $count * size_of::<$lane>()];
+            for (chunk, lane) in bytes.chunks_exact_mut(size_of::<$lane>()).zip(lanes) {
+                chunk.copy_from_slice(&lane.to_le_bytes());
+            }
+            Self::$variant(bytes)
+        }
+    };
+}
+
+impl<'a> Data<'a> {
+    vector_from_lanes!(u32x4, Vector128, u32, 4);
+    vector_from_lanes!(u64x2, Vector128, u64, 2);
+    vector_from_lanes!(f32x4, Vector128, f32, 4);
+    vector_from_lanes!(f64x2, Vector128, f64, 2);
+    vector_from_lanes!(u32x8, Vector256, u32, 8);
+    vector_from_lanes!(u64x4, Vector256, u64, 4);
+    vector_from_lanes!(f32x8, Vector256, f32, 8);
+    vector_from_lanes!(f64x4, Vector256, f64, 4);
+
+    /// Returns the alignment that loads of the data need to be aligned,
+    /// i.e. the size of the value, or of the length prefix for strings.
+    pub const fn natural_alignment(&self) -> Alignment {
+        let value = match self {
+            Data::U8(_) | Data::I8(_) | Data::Bytes(_) | Data::NulTerminatedString(_) => 1,
+            Data::U16(_) | Data::I16(_) => 2,
+            Data::U32(_) | Data::I32(_) | Data::F32(_) | Data::LengthPrefixedString(_) => 4,
+            Data::U64(_) | Data::I64(_) | Data::F64(_) => 8,
+            Data::Vector128(_) => 16,
+            Data::Vector256(_) => 32,
+        };
+        match Alignment::new(value) {
+            Ok(alignment) => alignment,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Repeats the data `count` times, like `times` directive.
+    #[inline(always)]
+    pub const fn times(self, count: u32) -> DataDirective<'a> {
+        DataDirective::new(self).times(count)
+    }
+
+    /// Aligns the data to its [`Data::natural_alignment`].
+    #[inline(always)]
+    pub const fn aligned(self) -> DataDirective<'a> {
+        DataDirective::new(self).aligned()
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Data::U8(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::U16(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::U32(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::U64(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::I8(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::I16(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::I32(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::I64(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::F32(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::F64(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Data::Vector128(bytes) => buffer.extend_from_slice(bytes),
+            Data::Vector256(bytes) => buffer.extend_from_slice(bytes),
+            Data::Bytes(bytes) => buffer.extend_from_slice(bytes),
+            Data::NulTerminatedString(value) => {
+                buffer.extend_from_slice(value.as_bytes());
+                buffer.push(0);
+            }
+            Data::LengthPrefixedString(value) => {
+                let length = u32::try_from(value.len()).expect("String length doesn't fit in u32.");
+                buffer.extend_from_slice(&length.to_le_bytes());
+                buffer.extend_from_slice(value.as_bytes());
+            }
+        }
+    }
+}
+
+/// Represents [`Data`] repeated a number of times, optionally aligned.
+///
+/// The alignment applies to the first copy of the data, the padding
+/// is filled with zeros. Labels set right before aligned data point at
+/// the data, after the padding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[must_use]
+pub struct DataDirective<'a> {
+    data: Data<'a>,
+    count: u32,
+    alignment: Option<Alignment>,
+}
+
+impl<'a> DataDirective<'a> {
+    /// Creates a directive that emits the `data` once, without alignment.
+    #[inline(always)]
+    pub const fn new(data: Data<'a>) -> Self {
+        Self {
+            data,
+            count: 1,
+            alignment: None,
+        }
+    }
+
+    /// Repeats the data `count` times, like `times` directive.
+    #[inline(always)]
+    pub const fn times(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Aligns the data to its [`Data::natural_alignment`].
+    #[inline(always)]
+    pub const fn aligned(mut self) -> Self {
+        self.alignment = Some(self.data.natural_alignment());
+        self
+    }
+
+    /// Aligns the data to the given `alignment`.
+    #[inline(always)]
+    pub const fn aligned_to(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    #[inline(always)]
+    pub const fn data(&self) -> &Data<'a> {
+        &self.data
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn alignment(&self) -> Option<Alignment> {
+        self.alignment
+    }
+}
+
+impl<'a> From<Data<'a>> for DataDirective<'a> {
+    fn from(data: Data<'a>) -> Self {
+        Self::new(data)
+    }
+}
@@ -0,0 +1,32 @@
+/// Represents the floating point data layout that SSE/AVX
+/// instructions operate on, i.e. the `ps`/`pd`/`ss`/`sd` suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum FloatType {
+    /// Packed 32-bit floats, `ps` suffix.
+    PackedSingle = 1,
+
+    /// Packed 64-bit floats, `pd` suffix.
+    PackedDouble,
+
+    /// A single 32-bit float in the lowest lane, `ss` suffix.
+    ScalarSingle,
+
+    /// A single 64-bit float in the lowest lane, `sd` suffix.
+    ScalarDouble,
+}
+
+impl FloatType {
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_scalar(self) -> bool {
+        matches!(self, Self::ScalarSingle | Self::ScalarDouble)
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_double(self) -> bool {
+        matches!(self, Self::PackedDouble | Self::ScalarDouble)
+    }
+}
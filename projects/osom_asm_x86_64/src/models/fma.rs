@@ -0,0 +1,35 @@
+/// Represents the arithmetic performed by FMA3 instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum FmaKind {
+    /// `vfmadd`: `a * b + c`
+    MulAdd = 1,
+
+    /// `vfmsub`: `a * b - c`
+    MulSub,
+
+    /// `vfnmadd`: `-(a * b) + c`
+    NegMulAdd,
+
+    /// `vfnmsub`: `-(a * b) - c`
+    NegMulSub,
+}
+
+/// Represents the operand order of FMA3 instructions, i.e. the numeric
+/// suffix of the mnemonic. The digits say which operands (1 = `dst`,
+/// 2 = `src1`, 3 = `src2`) play the role of `a`, `b` and `c` in [`FmaKind`].
+/// The result is always written to `dst`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum FmaOrder {
+    /// `dst = dst * src2 + src1`
+    Order132 = 1,
+
+    /// `dst = src1 * dst + src2`
+    Order213,
+
+    /// `dst = src1 * src2 + dst`
+    Order231,
+}
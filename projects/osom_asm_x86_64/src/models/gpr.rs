@@ -9,7 +9,7 @@ pub enum NewGPRError {
     /// Error when creating a new `GPR` from a [`GPRKind::Bit8High`] and `index` outside of the `4..=7` range.
     InvalidBit8HighIndex,
 
-    /// Error when creating a new `GPR` from a `kind` and `index` outside of the `0..=15` range.
+    /// Error when creating a new `GPR` from a `kind` and `index` outside of the `0..=31` range.
     IndexOutOfRange,
 }
 
@@ -95,17 +95,91 @@ impl GPR {
     pub const DH: Self = unsafe { Self::new_unchecked(enc_models::GPR::DH) };
     pub const BH: Self = unsafe { Self::new_unchecked(enc_models::GPR::BH) };
 
+    // APX extended registers.
+
+    pub const R16: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 16) };
+    pub const R17: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 17) };
+    pub const R18: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 18) };
+    pub const R19: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 19) };
+    pub const R20: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 20) };
+    pub const R21: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 21) };
+    pub const R22: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 22) };
+    pub const R23: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 23) };
+    pub const R24: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 24) };
+    pub const R25: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 25) };
+    pub const R26: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 26) };
+    pub const R27: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 27) };
+    pub const R28: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 28) };
+    pub const R29: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 29) };
+    pub const R30: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 30) };
+    pub const R31: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, 31) };
+
+    pub const R16D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 16) };
+    pub const R17D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 17) };
+    pub const R18D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 18) };
+    pub const R19D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 19) };
+    pub const R20D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 20) };
+    pub const R21D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 21) };
+    pub const R22D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 22) };
+    pub const R23D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 23) };
+    pub const R24D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 24) };
+    pub const R25D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 25) };
+    pub const R26D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 26) };
+    pub const R27D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 27) };
+    pub const R28D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 28) };
+    pub const R29D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 29) };
+    pub const R30D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 30) };
+    pub const R31D: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit32, 31) };
+
+    pub const R16W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 16) };
+    pub const R17W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 17) };
+    pub const R18W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 18) };
+    pub const R19W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 19) };
+    pub const R20W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 20) };
+    pub const R21W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 21) };
+    pub const R22W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 22) };
+    pub const R23W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 23) };
+    pub const R24W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 24) };
+    pub const R25W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 25) };
+    pub const R26W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 26) };
+    pub const R27W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 27) };
+    pub const R28W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 28) };
+    pub const R29W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 29) };
+    pub const R30W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 30) };
+    pub const R31W: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit16, 31) };
+
+    pub const R16B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 16) };
+    pub const R17B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 17) };
+    pub const R18B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 18) };
+    pub const R19B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 19) };
+    pub const R20B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 20) };
+    pub const R21B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 21) };
+    pub const R22B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 22) };
+    pub const R23B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 23) };
+    pub const R24B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 24) };
+    pub const R25B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 25) };
+    pub const R26B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 26) };
+    pub const R27B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 27) };
+    pub const R28B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 28) };
+    pub const R29B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 29) };
+    pub const R30B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 30) };
+    pub const R31B: Self = unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit8, 31) };
+
     pub(crate) const unsafe fn new_unchecked(gpr: enc_models::GPR) -> Self {
         Self { value: gpr }
     }
 
+    const unsafe fn new_apx_unchecked(kind: enc_models::GPRKind, index: u8) -> Self {
+        unsafe { Self::new_unchecked(enc_models::GPR::new_unchecked(kind, index)) }
+    }
+
     #[inline]
     pub fn new(kind: GPRKind, index: u8) -> Result<Self, NewGPRError> {
         if kind.equals(GPRKind::Bit8High) && !(4..=7).contains(&index) {
             return Err(NewGPRError::InvalidBit8HighIndex);
         }
 
-        if index > 15 {
+        if index > 31 {
             return Err(NewGPRError::IndexOutOfRange);
         }
 
@@ -125,6 +199,42 @@ impl GPR {
         Size::from(self.value.size())
     }
 
+    /// Returns `true` for the APX extended registers, i.e. `R16` to `R31`
+    /// of any size. These can only be encoded with APX enabled.
+    #[inline]
+    #[must_use]
+    pub fn requires_apx(self) -> bool {
+        self.index() >= 16
+    }
+
+    /// Returns the register index as used in `ModRM`, `SIB` and prefix bits.
+    ///
+    /// The encoder doesn't expose the index, so we recover it through
+    /// the public constructor instead of relying on its internal layout.
+    #[inline]
+    #[must_use]
+    pub(crate) fn index(self) -> u8 {
+        let kind = self.value.kind();
+        let start = if kind.equals(enc_models::GPRKind::Bit8High) {
+            4
+        } else {
+            0
+        };
+        for index in start..32 {
+            let candidate = unsafe { enc_models::GPR::new_unchecked(kind, index) };
+            if candidate == self.value {
+                return index;
+            }
+        }
+        unreachable!("GPR index outside of the 0..32 range.")
+    }
+
+    /// Returns the 64-bit register with the same index, e.g. `RAX` for `EAX`.
+    #[inline]
+    pub(crate) fn as_bit64(self) -> Self {
+        unsafe { Self::new_apx_unchecked(enc_models::GPRKind::Bit64, self.index()) }
+    }
+
     #[inline(always)]
     pub(crate) fn as_enc_gpr(self) -> enc_models::GPR {
         self.value
@@ -15,16 +15,20 @@ impl GPRKind {
     /// Represents AH, BH, CH and DH registers.
     pub const Bit8High: Self = Self::new(enc_models::GPRKind::Bit8High);
 
-    /// Represents AL, CL, DL, BL, SPL, BPL, SIL, DIL, R8B, R9B, R10B, R11B, R12B, R13B, R14B and R15B registers.
+    /// Represents AL, CL, DL, BL, SPL, BPL, SIL, DIL, R8B, R9B, R10B, R11B, R12B, R13B, R14B and R15B registers,
+    /// as well as R16B to R31B with APX.
     pub const Bit8: Self = Self::new(enc_models::GPRKind::Bit8);
 
-    /// Represents AX, CX, DX, BX, SP, BP, SI, DI, R8W, R9W, R10W, R11W, R12W, R13W, R14W and R15W registers.
+    /// Represents AX, CX, DX, BX, SP, BP, SI, DI, R8W, R9W, R10W, R11W, R12W, R13W, R14W and R15W registers,
+    /// as well as R16W to R31W with APX.
     pub const Bit16: Self = Self::new(enc_models::GPRKind::Bit16);
 
-    /// Represents EAX, ECX, EDX, EBX, ESP, EBP, ESI, EDI, R8D, R9D, R10D, R11D, R12D, R13D, R14D and R15D registers.
+    /// Represents EAX, ECX, EDX, EBX, ESP, EBP, ESI, EDI, R8D, R9D, R10D, R11D, R12D, R13D, R14D and R15D registers,
+    /// as well as R16D to R31D with APX.
     pub const Bit32: Self = Self::new(enc_models::GPRKind::Bit32);
 
-    /// Represents RAX, RCX, RDX, RBX, RSP, RBP, RSI, RDI, R8, R9, R10, R11, R12, R13, R14 and R15 registers.
+    /// Represents RAX, RCX, RDX, RBX, RSP, RBP, RSI, RDI, R8, R9, R10, R11, R12, R13, R14 and R15 registers,
+    /// as well as R16 to R31 with APX.
     pub const Bit64: Self = Self::new(enc_models::GPRKind::Bit64);
 
     #[inline(always)]
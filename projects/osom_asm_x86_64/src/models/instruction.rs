@@ -2,7 +2,11 @@
 
 use core::num::NonZero;
 
-use super::{Condition, GPR, Immediate32, Label, Memory};
+use super::{
+    AesKind, AlignFill, Alignment, CR, Condition, DR, FloatType, FmaKind, FmaOrder, GPR, Immediate32, Label, LoopKind,
+    Memory, PackedMinMaxKind, RoundingMode, ST, Section, Segment, ShaKind, Size, X87ArithKind, X87Constant,
+    X87FloatSize, XMM, YMM,
+};
 
 /// Represents custom assembly language instructions.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -174,8 +178,972 @@ pub enum Instruction {
     /// `syscall`
     Syscall,
 
+    /// `vfmadd132ps xmm, xmm, xmm` and the rest of the FMA3 family.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `kind`, `order` and `ty`,
+    /// e.g. [`FmaKind::NegMulSub`], [`FmaOrder::Order231`] and
+    /// [`FloatType::ScalarDouble`] give `vfnmsub231sd`.
+    Fma_XmmXmmXmm {
+        kind: FmaKind,
+        order: FmaOrder,
+        ty: FloatType,
+        dst: XMM,
+        src1: XMM,
+        src2: XMM,
+    },
+
+    /// `vfmadd132ps xmm, xmm, [mem]` and the rest of the FMA3 family.
+    /// See [`Instruction::Fma_XmmXmmXmm`].
+    Fma_XmmXmmMem {
+        kind: FmaKind,
+        order: FmaOrder,
+        ty: FloatType,
+        dst: XMM,
+        src1: XMM,
+        src2: Memory,
+    },
+
+    /// `vfmadd132ps ymm, ymm, ymm` and the rest of the FMA3 family.
+    ///
+    /// # Notes
+    ///
+    /// Only packed `ty` is allowed with 256-bit registers.
+    Fma_YmmYmmYmm {
+        kind: FmaKind,
+        order: FmaOrder,
+        ty: FloatType,
+        dst: YMM,
+        src1: YMM,
+        src2: YMM,
+    },
+
+    /// `vfmadd132ps ymm, ymm, [mem]` and the rest of the FMA3 family.
+    ///
+    /// # Notes
+    ///
+    /// Only packed `ty` is allowed with 256-bit registers.
+    Fma_YmmYmmMem {
+        kind: FmaKind,
+        order: FmaOrder,
+        ty: FloatType,
+        dst: YMM,
+        src1: YMM,
+        src2: Memory,
+    },
+
+    /// `aesenc xmm, xmm` and the rest of the AES round family.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `kind`.
+    Aes_XmmXmm { kind: AesKind, dst: XMM, src: XMM },
+
+    /// `aesenc xmm, [mem]` and the rest of the AES round family.
+    /// See [`Instruction::Aes_XmmXmm`].
+    Aes_XmmMem { kind: AesKind, dst: XMM, src: Memory },
+
+    /// `aesimc xmm, xmm`
+    AesImc_XmmXmm { dst: XMM, src: XMM },
+
+    /// `aesimc xmm, [mem]`
+    AesImc_XmmMem { dst: XMM, src: Memory },
+
+    /// `aeskeygenassist xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The value of `imm` has to be an 8-bit unsigned integer.
+    AesKeyGenAssist_XmmXmmImm { dst: XMM, src: XMM, imm: Immediate32 },
+
+    /// `aeskeygenassist xmm, [mem], imm`
+    ///
+    /// # Notes
+    ///
+    /// The value of `imm` has to be an 8-bit unsigned integer.
+    AesKeyGenAssist_XmmMemImm { dst: XMM, src: Memory, imm: Immediate32 },
+
+    /// `pclmulqdq xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The value of `imm` has to be an 8-bit unsigned integer. Bit 0
+    /// selects the quadword of `dst` and bit 4 selects the quadword of `src`.
+    Pclmulqdq_XmmXmmImm { dst: XMM, src: XMM, imm: Immediate32 },
+
+    /// `pclmulqdq xmm, [mem], imm`
+    /// See [`Instruction::Pclmulqdq_XmmXmmImm`].
+    Pclmulqdq_XmmMemImm { dst: XMM, src: Memory, imm: Immediate32 },
+
+    /// `sha1msg1 xmm, xmm` and the rest of the two-operand SHA family.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `kind`.
+    Sha_XmmXmm { kind: ShaKind, dst: XMM, src: XMM },
+
+    /// `sha1msg1 xmm, [mem]` and the rest of the two-operand SHA family.
+    /// See [`Instruction::Sha_XmmXmm`].
+    Sha_XmmMem { kind: ShaKind, dst: XMM, src: Memory },
+
+    /// `sha1rnds4 xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The value of `imm` selects the round function and has to be in `0..=3` range.
+    Sha1Rnds4_XmmXmmImm { dst: XMM, src: XMM, imm: Immediate32 },
+
+    /// `sha1rnds4 xmm, [mem], imm`
+    /// See [`Instruction::Sha1Rnds4_XmmXmmImm`].
+    Sha1Rnds4_XmmMemImm { dst: XMM, src: Memory, imm: Immediate32 },
+
+    /// `crc32 reg, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` has to be either 32-bit or 64-bit [`GPR`]. With 64-bit `dst`
+    /// the `src` has to be either 8-bit or 64-bit.
+    Crc32_RegReg { dst: GPR, src: GPR },
+
+    /// `crc32 reg, [mem]`
+    ///
+    /// # Notes
+    ///
+    /// The `size` determines how many bytes are read from `src`. The same
+    /// restrictions as in [`Instruction::Crc32_RegReg`] apply.
+    Crc32_RegMem { dst: GPR, src: Memory, size: Size },
+
+    /// `movd xmm, r32` or `movq xmm, r64`
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by the size of `src`,
+    /// which has to be either 32-bit or 64-bit.
+    Movd_XmmReg { dst: XMM, src: GPR },
+
+    /// `movd r32, xmm` or `movq r64, xmm`
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by the size of `dst`,
+    /// which has to be either 32-bit or 64-bit.
+    Movd_RegXmm { dst: GPR, src: XMM },
+
+    /// `cvtsi2ss xmm, reg` or `cvtsi2sd xmm, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `ty` has to be scalar and the `src` has to be either 32-bit or 64-bit.
+    CvtIntToFloat_XmmReg { ty: FloatType, dst: XMM, src: GPR },
+
+    /// `cvtsi2ss xmm, [mem]` or `cvtsi2sd xmm, [mem]`
+    ///
+    /// # Notes
+    ///
+    /// The `ty` has to be scalar and the `size` of the integer
+    /// read from `src` has to be either 32-bit or 64-bit.
+    CvtIntToFloat_XmmMem {
+        ty: FloatType,
+        dst: XMM,
+        src: Memory,
+        size: Size,
+    },
+
+    /// `cvtss2si reg, xmm` or `cvtsd2si reg, xmm`, and their
+    /// `cvtt*` variants when `truncate` is set.
+    ///
+    /// # Notes
+    ///
+    /// The `ty` has to be scalar and the `dst` has to be either 32-bit or 64-bit.
+    /// Without `truncate` the value is rounded according to `MXCSR`.
+    CvtFloatToInt_RegXmm {
+        ty: FloatType,
+        truncate: bool,
+        dst: GPR,
+        src: XMM,
+    },
+
+    /// `cvtss2si reg, [mem]` or `cvtsd2si reg, [mem]`.
+    /// See [`Instruction::CvtFloatToInt_RegXmm`].
+    CvtFloatToInt_RegMem {
+        ty: FloatType,
+        truncate: bool,
+        dst: GPR,
+        src: Memory,
+    },
+
+    /// `cvtss2sd xmm, xmm`
+    Cvtss2sd_XmmXmm { dst: XMM, src: XMM },
+
+    /// `cvtss2sd xmm, [mem]`
+    Cvtss2sd_XmmMem { dst: XMM, src: Memory },
+
+    /// `cvtsd2ss xmm, xmm`
+    Cvtsd2ss_XmmXmm { dst: XMM, src: XMM },
+
+    /// `cvtsd2ss xmm, [mem]`
+    Cvtsd2ss_XmmMem { dst: XMM, src: Memory },
+
+    /// `roundss xmm, xmm, imm` and the rest of the SSE4.1 round family.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `ty`, e.g. [`FloatType::PackedDouble`]
+    /// gives `roundpd`. The immediate is encoded from `mode`.
+    Round_XmmXmm {
+        ty: FloatType,
+        mode: RoundingMode,
+        dst: XMM,
+        src: XMM,
+    },
+
+    /// `roundss xmm, [mem], imm` and the rest of the SSE4.1 round family.
+    /// See [`Instruction::Round_XmmXmm`].
+    Round_XmmMem {
+        ty: FloatType,
+        mode: RoundingMode,
+        dst: XMM,
+        src: Memory,
+    },
+
+    /// `vcvtph2ps xmm, xmm`
+    Vcvtph2ps_XmmXmm { dst: XMM, src: XMM },
+
+    /// `vcvtph2ps xmm, [mem]`
+    Vcvtph2ps_XmmMem { dst: XMM, src: Memory },
+
+    /// `vcvtph2ps ymm, xmm`
+    Vcvtph2ps_YmmXmm { dst: YMM, src: XMM },
+
+    /// `vcvtph2ps ymm, [mem]`
+    Vcvtph2ps_YmmMem { dst: YMM, src: Memory },
+
+    /// `vcvtps2ph xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The immediate is encoded from `mode`.
+    Vcvtps2ph_XmmXmm { mode: RoundingMode, dst: XMM, src: XMM },
+
+    /// `vcvtps2ph [mem], xmm, imm`
+    Vcvtps2ph_MemXmm { mode: RoundingMode, dst: Memory, src: XMM },
+
+    /// `vcvtps2ph xmm, ymm, imm`
+    Vcvtps2ph_XmmYmm { mode: RoundingMode, dst: XMM, src: YMM },
+
+    /// `vcvtps2ph [mem], ymm, imm`
+    Vcvtps2ph_MemYmm { mode: RoundingMode, dst: Memory, src: YMM },
+
+    /// `pcmpistri xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The `imm` is the 8-bit control byte. Implicitly writes the
+    /// resulting index to `ECX` and sets flags.
+    Pcmpistri_XmmXmmImm { dst: XMM, src: XMM, imm: Immediate32 },
+
+    /// `pcmpistri xmm, [mem], imm`
+    /// See [`Instruction::Pcmpistri_XmmXmmImm`].
+    Pcmpistri_XmmMemImm { dst: XMM, src: Memory, imm: Immediate32 },
+
+    /// `pcmpestri xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The `imm` is the 8-bit control byte. Implicitly reads the length of
+    /// `dst` from `EAX` and of `src` from `EDX`, writes the resulting index
+    /// to `ECX` and sets flags. With REX.W the lengths would be read from
+    /// `RAX`/`RDX`, which this variant doesn't emit.
+    Pcmpestri_XmmXmmImm { dst: XMM, src: XMM, imm: Immediate32 },
+
+    /// `pcmpestri xmm, [mem], imm`
+    /// See [`Instruction::Pcmpestri_XmmXmmImm`].
+    Pcmpestri_XmmMemImm { dst: XMM, src: Memory, imm: Immediate32 },
+
+    /// `ptest xmm, xmm`
+    Ptest_XmmXmm { dst: XMM, src: XMM },
+
+    /// `ptest xmm, [mem]`
+    Ptest_XmmMem { dst: XMM, src: Memory },
+
+    /// `pblendvb xmm, xmm, <xmm0>`
+    ///
+    /// # Notes
+    ///
+    /// The `mask` is implicit in the encoding and has to be [`XMM::XMM0`].
+    Pblendvb_XmmXmm { dst: XMM, src: XMM, mask: XMM },
+
+    /// `pblendvb xmm, [mem], <xmm0>`
+    /// See [`Instruction::Pblendvb_XmmXmm`].
+    Pblendvb_XmmMem { dst: XMM, src: Memory, mask: XMM },
+
+    /// `blendps xmm, xmm, imm`
+    ///
+    /// # Notes
+    ///
+    /// The value of `imm` has to be in `0..=15` range.
+    Blendps_XmmXmmImm { dst: XMM, src: XMM, imm: Immediate32 },
+
+    /// `blendps xmm, [mem], imm`
+    /// See [`Instruction::Blendps_XmmXmmImm`].
+    Blendps_XmmMemImm { dst: XMM, src: Memory, imm: Immediate32 },
+
+    /// `pminsb xmm, xmm` and the rest of the SSE4.1 packed integer min/max family.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `kind`.
+    PackedMinMax_XmmXmm { kind: PackedMinMaxKind, dst: XMM, src: XMM },
+
+    /// `pminsb xmm, [mem]` and the rest of the SSE4.1 packed integer min/max family.
+    /// See [`Instruction::PackedMinMax_XmmXmm`].
+    PackedMinMax_XmmMem {
+        kind: PackedMinMaxKind,
+        dst: XMM,
+        src: Memory,
+    },
+
+    /// `pinsrb xmm, r32, imm`, `pinsrw`, `pinsrd` or `pinsrq`
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by the lane `size`. The `src` has
+    /// to be a 64-bit [`GPR`] for [`Size::Bit64`] lanes and a 32-bit one
+    /// otherwise. The `imm` is the lane index and has to fit the lane count.
+    Pinsr_XmmRegImm {
+        size: Size,
+        dst: XMM,
+        src: GPR,
+        imm: Immediate32,
+    },
+
+    /// `pinsrb xmm, [mem], imm`, `pinsrw`, `pinsrd` or `pinsrq`
+    /// See [`Instruction::Pinsr_XmmRegImm`].
+    Pinsr_XmmMemImm {
+        size: Size,
+        dst: XMM,
+        src: Memory,
+        imm: Immediate32,
+    },
+
+    /// `pextrb r32, xmm, imm`, `pextrw`, `pextrd` or `pextrq`
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by the lane `size`. The `dst` has
+    /// to be a 64-bit [`GPR`] for [`Size::Bit64`] lanes and a 32-bit one
+    /// otherwise, in which case the value is zero extended. The `imm` is the
+    /// lane index and has to fit the lane count.
+    Pextr_RegXmmImm {
+        size: Size,
+        dst: GPR,
+        src: XMM,
+        imm: Immediate32,
+    },
+
+    /// `pextrb [mem], xmm, imm`, `pextrw`, `pextrd` or `pextrq`
+    /// See [`Instruction::Pextr_RegXmmImm`].
+    ///
+    /// # Notes
+    ///
+    /// The `src` field precedes `dst` only to keep [`Instruction`] within 16 bytes.
+    Pextr_MemXmmImm {
+        size: Size,
+        src: XMM,
+        dst: Memory,
+        imm: Immediate32,
+    },
+
+    /// `pmovzxbw xmm, xmm` and the rest of the `pmovzx`/`pmovsx` family.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `signed` and the lane sizes,
+    /// e.g. `signed` with [`Size::Bit8`] and [`Size::Bit32`] gives `pmovsxbd`.
+    /// The `to` size has to be greater than `from` size, and `from` can't be 64-bit.
+    Pmovx_XmmXmm {
+        signed: bool,
+        from: Size,
+        to: Size,
+        dst: XMM,
+        src: XMM,
+    },
+
+    /// `pmovzxbw xmm, [mem]` and the rest of the `pmovzx`/`pmovsx` family.
+    /// See [`Instruction::Pmovx_XmmXmm`].
+    Pmovx_XmmMem {
+        signed: bool,
+        from: Size,
+        to: Size,
+        dst: XMM,
+        src: Memory,
+    },
+
+    /// `fld [mem]`
+    ///
+    /// # Notes
+    ///
+    /// Pushes the float of given `size` onto the x87 stack.
+    Fld_Mem { size: X87FloatSize, src: Memory },
+
+    /// `fld st(i)`
+    Fld_St { src: ST },
+
+    /// `fst [mem]`
+    ///
+    /// # Notes
+    ///
+    /// The [`X87FloatSize::Extended`] size is only allowed with [`Instruction::Fstp_Mem`].
+    Fst_Mem { size: X87FloatSize, dst: Memory },
+
+    /// `fst st(i)`
+    Fst_St { dst: ST },
+
+    /// `fstp [mem]`
+    Fstp_Mem { size: X87FloatSize, dst: Memory },
+
+    /// `fstp st(i)`
+    Fstp_St { dst: ST },
+
+    /// `fild [mem]`
+    ///
+    /// # Notes
+    ///
+    /// The `size` of the integer has to be 16-bit, 32-bit or 64-bit.
+    Fild_Mem { size: Size, src: Memory },
+
+    /// `fistp [mem]`
+    ///
+    /// # Notes
+    ///
+    /// The `size` of the integer has to be 16-bit, 32-bit or 64-bit.
+    /// The value is rounded according to the x87 control word.
+    Fistp_Mem { size: Size, dst: Memory },
+
+    /// `fadd [mem]` and the rest of the x87 arithmetic family,
+    /// i.e. `st(0) = st(0) op [mem]`.
+    ///
+    /// # Notes
+    ///
+    /// The exact mnemonic is determined by `kind`. The `size` can't be
+    /// [`X87FloatSize::Extended`].
+    X87Arith_Mem {
+        kind: X87ArithKind,
+        size: X87FloatSize,
+        src: Memory,
+    },
+
+    /// `fadd st(0), st(i)` and the rest of the x87 arithmetic family,
+    /// i.e. `st(0) = st(0) op st(i)`.
+    X87Arith_St0St { kind: X87ArithKind, src: ST },
+
+    /// `fadd st(i), st(0)` and the rest of the x87 arithmetic family,
+    /// i.e. `st(i) = st(i) op st(0)`.
+    ///
+    /// # Notes
+    ///
+    /// With `pop` set this becomes `faddp st(i), st(0)` and the
+    /// stack is popped after the operation.
+    X87Arith_StSt0 { kind: X87ArithKind, dst: ST, pop: bool },
+
+    /// `fxch st(i)`
+    Fxch_St { src: ST },
+
+    /// `fcomi st(0), st(i)`, `fucomi`, `fcomip` or `fucomip`
+    ///
+    /// # Notes
+    ///
+    /// Compares `st(0)` with `src` and sets `ZF`, `PF` and `CF` like
+    /// unsigned integer comparison. With `unordered` quiet NaN operands don't
+    /// raise an exception. With `pop` the stack is popped afterwards.
+    Fcomi_St { src: ST, unordered: bool, pop: bool },
+
+    /// `fldcw [mem]`
+    Fldcw_Mem { src: Memory },
+
+    /// `fnstcw [mem]`
+    Fnstcw_Mem { dst: Memory },
+
+    /// `fsqrt`
+    Fsqrt,
+
+    /// `fld1`, `fldz`, `fldpi` and the rest of x87 constants.
+    FldConst { constant: X87Constant },
+
+    /// `mov cr, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `src` has to be a 64-bit [`GPR`].
+    Mov_CrReg { dst: CR, src: GPR },
+
+    /// `mov reg, cr`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` has to be a 64-bit [`GPR`].
+    Mov_RegCr { dst: GPR, src: CR },
+
+    /// `mov dr, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `src` has to be a 64-bit [`GPR`].
+    Mov_DrReg { dst: DR, src: GPR },
+
+    /// `mov reg, dr`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` has to be a 64-bit [`GPR`].
+    Mov_RegDr { dst: GPR, src: DR },
+
+    /// `mov sreg, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `src` can't be an 8-bit [`GPR`] and `dst` can't be [`Segment::CS`].
+    Mov_SegReg { dst: Segment, src: GPR },
+
+    /// `mov reg, sreg`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` can't be an 8-bit [`GPR`].
+    Mov_RegSeg { dst: GPR, src: Segment },
+
+    /// `rdmsr`
+    ///
+    /// # Notes
+    ///
+    /// Implicitly reads the MSR index from `ECX` and writes the value to `EDX:EAX`.
+    Rdmsr,
+
+    /// `wrmsr`
+    ///
+    /// # Notes
+    ///
+    /// Implicitly reads the MSR index from `ECX` and the value from `EDX:EAX`.
+    Wrmsr,
+
+    /// `lgdt [mem]`
+    Lgdt_Mem { src: Memory },
+
+    /// `lidt [mem]`
+    Lidt_Mem { src: Memory },
+
+    /// `sgdt [mem]`
+    Sgdt_Mem { dst: Memory },
+
+    /// `sidt [mem]`
+    Sidt_Mem { dst: Memory },
+
+    /// `ltr reg`
+    ///
+    /// # Notes
+    ///
+    /// The `src` has to be a 16-bit [`GPR`].
+    Ltr_Reg { src: GPR },
+
+    /// `ltr [mem]`
+    Ltr_Mem { src: Memory },
+
+    /// `invlpg [mem]`
+    Invlpg_Mem { src: Memory },
+
+    /// `iretq`
+    Iretq,
+
+    /// `swapgs`
+    Swapgs,
+
+    /// `sysretq`, i.e. `sysret` returning to 64-bit code.
+    Sysret,
+
+    /// `cli`
+    Cli,
+
+    /// `sti`
+    Sti,
+
+    /// `hlt`
+    Hlt,
+
+    /// `in reg, imm`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` has to be `AL`, `AX` or `EAX`, and `port` an 8-bit unsigned integer.
+    In_RegImm { dst: GPR, port: Immediate32 },
+
+    /// `in reg, dx`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` has to be `AL`, `AX` or `EAX`, and `port` has to be `DX`.
+    In_RegReg { dst: GPR, port: GPR },
+
+    /// `out imm, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `src` has to be `AL`, `AX` or `EAX`, and `port` an 8-bit unsigned integer.
+    Out_ImmReg { port: Immediate32, src: GPR },
+
+    /// `out dx, reg`
+    ///
+    /// # Notes
+    ///
+    /// The `src` has to be `AL`, `AX` or `EAX`, and `port` has to be `DX`.
+    Out_RegReg { port: GPR, src: GPR },
+
+    /// `xsave [mem]` or `xsave64 [mem]` if `is_64` is set.
+    ///
+    /// # Notes
+    ///
+    /// Implicitly reads the requested feature mask from `EDX:EAX`.
+    Xsave_Mem { dst: Memory, is_64: bool },
+
+    /// `xrstor [mem]` or `xrstor64 [mem]` if `is_64` is set.
+    ///
+    /// # Notes
+    ///
+    /// Implicitly reads the requested feature mask from `EDX:EAX`.
+    Xrstor_Mem { src: Memory, is_64: bool },
+
+    /// `xgetbv`
+    ///
+    /// # Notes
+    ///
+    /// Implicitly reads the register index from `ECX` and writes the value to `EDX:EAX`.
+    Xgetbv,
+
+    /// `xsetbv`
+    ///
+    /// # Notes
+    ///
+    /// Implicitly reads the register index from `ECX` and the value from `EDX:EAX`.
+    Xsetbv,
+
+    /// `jmp far [mem]`
+    ///
+    /// # Notes
+    ///
+    /// The `dst` points to the offset followed by the 16-bit segment selector.
+    /// The `size` of the offset has to be either 32-bit or 64-bit. Direct far
+    /// jumps can't be encoded in 64-bit mode, use [`Instruction::FarRet`]
+    /// with the selector and offset pushed on the stack instead.
+    FarJump_Mem { dst: Memory, size: Size },
+
+    /// `call far [mem]`. See [`Instruction::FarJump_Mem`].
+    FarCall_Mem { dst: Memory, size: Size },
+
+    /// `retf` or `retfq`
+    ///
+    /// # Notes
+    ///
+    /// Pops the offset and the segment selector. The `size` of the
+    /// popped offset has to be either 32-bit or 64-bit.
+    FarRet { size: Size },
+
+    /// `add reg, reg, reg`
+    ///
+    /// # Notes
+    ///
+    /// APX new data destination form, i.e. `dst = src1 + src2`.
+    /// All registers have to be of the same size.
+    Add_RegRegReg { dst: GPR, src1: GPR, src2: GPR },
+
+    /// `add reg, reg, [mem]`. See [`Instruction::Add_RegRegReg`].
+    Add_RegRegMem { dst: GPR, src1: GPR, src2: Memory },
+
+    /// `add reg, [mem], reg`. See [`Instruction::Add_RegRegReg`].
+    Add_RegMemReg { dst: GPR, src1: Memory, src2: GPR },
+
+    /// `add reg, reg, imm`. See [`Instruction::Add_RegRegReg`].
+    Add_RegRegImm { dst: GPR, src1: GPR, src2: Immediate32 },
+
+    /// `add reg, [mem], imm`. See [`Instruction::Add_RegRegReg`].
+    ///
+    /// # Notes
+    ///
+    /// The operand size is the size of `dst`.
+    Add_RegMemImm { dst: GPR, src1: Memory, src2: Immediate32 },
+
+    /// `sub reg, reg, reg`
+    ///
+    /// # Notes
+    ///
+    /// APX new data destination form, i.e. `dst = src1 - src2`.
+    /// All registers have to be of the same size.
+    Sub_RegRegReg { dst: GPR, src1: GPR, src2: GPR },
+
+    /// `sub reg, reg, [mem]`. See [`Instruction::Sub_RegRegReg`].
+    Sub_RegRegMem { dst: GPR, src1: GPR, src2: Memory },
+
+    /// `sub reg, [mem], reg`. See [`Instruction::Sub_RegRegReg`].
+    Sub_RegMemReg { dst: GPR, src1: Memory, src2: GPR },
+
+    /// `sub reg, reg, imm`. See [`Instruction::Sub_RegRegReg`].
+    Sub_RegRegImm { dst: GPR, src1: GPR, src2: Immediate32 },
+
+    /// `sub reg, [mem], imm`. See [`Instruction::Sub_RegRegReg`].
+    ///
+    /// # Notes
+    ///
+    /// The operand size is the size of `dst`.
+    Sub_RegMemImm { dst: GPR, src1: Memory, src2: Immediate32 },
+
+    /// `xor reg, reg, reg`
+    ///
+    /// # Notes
+    ///
+    /// APX new data destination form, i.e. `dst = src1 ^ src2`.
+    /// All registers have to be of the same size.
+    Xor_RegRegReg { dst: GPR, src1: GPR, src2: GPR },
+
+    /// `xor reg, reg, [mem]`. See [`Instruction::Xor_RegRegReg`].
+    Xor_RegRegMem { dst: GPR, src1: GPR, src2: Memory },
+
+    /// `xor reg, [mem], reg`. See [`Instruction::Xor_RegRegReg`].
+    Xor_RegMemReg { dst: GPR, src1: Memory, src2: GPR },
+
+    /// `xor reg, reg, imm`. See [`Instruction::Xor_RegRegReg`].
+    Xor_RegRegImm { dst: GPR, src1: GPR, src2: Immediate32 },
+
+    /// `xor reg, [mem], imm`. See [`Instruction::Xor_RegRegReg`].
+    ///
+    /// # Notes
+    ///
+    /// The operand size is the size of `dst`.
+    Xor_RegMemImm { dst: GPR, src1: Memory, src2: Immediate32 },
+
+    /// `xbegin label`
+    ///
+    /// # Notes
+    ///
+    /// Starts a transaction. On abort, the execution continues at `dst`
+    /// with the abort status in `EAX`. Compiled into relative `xbegin rel32`.
+    Xbegin_Label { dst: Label },
+
+    /// `xend`
+    Xend,
+
+    /// `xabort imm8`
+    ///
+    /// # Notes
+    ///
+    /// The value of `src` has to be an 8-bit unsigned integer.
+    Xabort_Imm { src: Immediate32 },
+
+    /// `xtest`
+    Xtest,
+
+    /// `loop label`, `loope label` or `loopne label`
+    ///
+    /// # Notes
+    ///
+    /// Pseudoinstruction: these only have rel8 form at the machine level.
+    /// If `dst` is out of range, or relaxation is disabled, the instruction
+    /// is expanded into `loop` over a short jump to `jmp rel32`, which keeps
+    /// the semantics, including flags. The counter is `ECX` in 32-bit code
+    /// and `CX` in 16-bit code.
+    Loop_Label { kind: LoopKind, dst: Label },
+
+    /// `jrcxz label`
+    ///
+    /// # Notes
+    ///
+    /// Pseudoinstruction: expanded the same way as [`Instruction::Loop_Label`].
+    /// This is `jecxz` in 32-bit code and `jcxz` in 16-bit code.
+    Jrcxz_Label { dst: Label },
+
+    /// Calls absolute address, e.g. a runtime function.
+    ///
+    /// # Notes
+    ///
+    /// Pseudoinstruction: it is compiled into relative call, either to
+    /// `address` directly, if the base address is known and `address` is
+    /// within rel32 range, or to a veneer placed at the end of
+    /// [`Section::TEXT`][`crate::models::Section::TEXT`], i.e.
+    /// `jmp [rip + slot]` followed by the 8-byte `address`. Veneers are
+    /// shared by all calls and jumps to the same address.
+    ///
+    /// In 32-bit code the call always reaches `address` directly, so
+    /// it has to fit in 32 bits and the base address has to be set.
+    Call_Absolute { address: u64 },
+
+    /// Jumps to absolute address. See [`Instruction::Call_Absolute`].
+    Jump_Absolute { address: u64 },
+
+    /// Pseudoinstruction: pads the code with `fill` bytes, so that the next
+    /// instruction starts at a multiple of `boundary`.
+    ///
+    /// # Notes
+    ///
+    /// The padding is recomputed during relaxation, so it stays correct when
+    /// preceding jumps change size. The boundary is relative to the base
+    /// address, if set, and to the beginning of the code otherwise.
+    Align { boundary: Alignment, fill: AlignFill },
+
+    /// Pseudoinstruction: `dd target - base`, i.e. the signed 32-bit distance
+    /// between two labels, e.g. an entry of a jump table, see
+    /// [`Instruction::JumpTable_Dispatch`].
+    ///
+    /// # Notes
+    ///
+    /// The distance is resolved after relaxation, so it stays correct when
+    /// jumps between the labels change size.
+    LabelDifference32 { target: Label, base: Label },
+
+    /// Pseudoinstruction: `dd label`, i.e. the 32-bit absolute address of
+    /// the label, which is its offset from the beginning of the code plus
+    /// the base address.
+    LabelAddress32 { label: Label },
+
+    /// Pseudoinstruction: `dq label`, i.e. the 64-bit absolute address of
+    /// the label, e.g. an entry of a vtable. See [`Instruction::LabelAddress32`].
+    ///
+    /// # Notes
+    ///
+    /// Absolute addresses are reported as relocations by the
+    /// [`EmissionData`][`crate::assembler::EmissionData`], so the code
+    /// can be moved to a different address.
+    LabelAddress64 { label: Label },
+
+    /// `mov reg, imm` with the absolute address of the label as the immediate.
+    ///
+    /// # Notes
+    ///
+    /// For 64-bit `dst` this is `mov reg, imm64`, which reaches any address.
+    /// For 32-bit `dst` the address has to fit in 32 bits. See
+    /// [`Instruction::LabelAddress64`] for the relocations.
+    Mov_RegLabel { dst: GPR, label: Label },
+
+    /// Pseudoinstruction: jumps to the `index`-th entry of the jump `table`, i.e.
+    /// `lea base, [rip + table]; movsxd index, [base + index * 4]; add base, index; jmp base`.
+    ///
+    /// # Notes
+    ///
+    /// The `table` is made of [`Instruction::LabelDifference32`] entries with the
+    /// `table` label as the base, so the code stays position independent. Both
+    /// registers have to be distinct 64-bit registers and both are clobbered.
+    /// The `index` has to be zero extended and within the table bounds.
+    ///
+    /// Only available in 64-bit code.
+    JumpTable_Dispatch { table: Label, index: GPR, base: GPR },
+
+    /// Pseudoinstruction: the following instructions and data are emitted
+    /// to `section`, until the next switch. The assembler starts with
+    /// [`Section::TEXT`].
+    ///
+    /// # Notes
+    ///
+    /// Labels can be referred to from any section. The order and alignment
+    /// of sections in the assembled code is set with
+    /// [`X86_64Assembler::assemble_with_layout`][`crate::assembler::X86_64Assembler::assemble_with_layout`].
+    SwitchSection { section: Section },
+
     /// Pseudoinstruction: this is lock prefix. It doesn't really
     /// exist as a standalone machine code instruction, but it should
     /// be followed by an instruction that it applies to.
     Lock,
 }
+
+/// Matches the memory operand of the instruction, if any. Works for both
+/// shared and mutable references thanks to default binding modes.
+macro_rules! match_memory_operand {
+    ($instruction:expr) => {
+        match $instruction {
+            Instruction::Mov_MemImm { dst: memory, .. }
+            | Instruction::Mov_MemReg { dst: memory, .. }
+            | Instruction::Cmp_MemImm { dst: memory, .. }
+            | Instruction::Cmp_MemReg { dst: memory, .. }
+            | Instruction::Add_MemImm { dst: memory, .. }
+            | Instruction::Add_MemReg { dst: memory, .. }
+            | Instruction::Sub_MemImm { dst: memory, .. }
+            | Instruction::Sub_MemReg { dst: memory, .. }
+            | Instruction::Xor_MemImm { dst: memory, .. }
+            | Instruction::Xor_MemReg { dst: memory, .. }
+            | Instruction::Jump_Mem { dst: memory, .. }
+            | Instruction::Call_Mem { dst: memory, .. }
+            | Instruction::Vcvtps2ph_MemXmm { dst: memory, .. }
+            | Instruction::Vcvtps2ph_MemYmm { dst: memory, .. }
+            | Instruction::Pextr_MemXmmImm { dst: memory, .. }
+            | Instruction::Fst_Mem { dst: memory, .. }
+            | Instruction::Fstp_Mem { dst: memory, .. }
+            | Instruction::Fistp_Mem { dst: memory, .. }
+            | Instruction::Fnstcw_Mem { dst: memory, .. }
+            | Instruction::Sgdt_Mem { dst: memory, .. }
+            | Instruction::Sidt_Mem { dst: memory, .. }
+            | Instruction::Xsave_Mem { dst: memory, .. }
+            | Instruction::FarJump_Mem { dst: memory, .. }
+            | Instruction::FarCall_Mem { dst: memory, .. }
+            | Instruction::Mov_RegMem { src: memory, .. }
+            | Instruction::Cmp_RegMem { src: memory, .. }
+            | Instruction::Add_RegMem { src: memory, .. }
+            | Instruction::Sub_RegMem { src: memory, .. }
+            | Instruction::Xor_RegMem { src: memory, .. }
+            | Instruction::Push_Mem { src: memory, .. }
+            | Instruction::Pop_Mem { src: memory, .. }
+            | Instruction::Aes_XmmMem { src: memory, .. }
+            | Instruction::AesImc_XmmMem { src: memory, .. }
+            | Instruction::AesKeyGenAssist_XmmMemImm { src: memory, .. }
+            | Instruction::Pclmulqdq_XmmMemImm { src: memory, .. }
+            | Instruction::Sha_XmmMem { src: memory, .. }
+            | Instruction::Sha1Rnds4_XmmMemImm { src: memory, .. }
+            | Instruction::Crc32_RegMem { src: memory, .. }
+            | Instruction::CvtIntToFloat_XmmMem { src: memory, .. }
+            | Instruction::CvtFloatToInt_RegMem { src: memory, .. }
+            | Instruction::Cvtss2sd_XmmMem { src: memory, .. }
+            | Instruction::Cvtsd2ss_XmmMem { src: memory, .. }
+            | Instruction::Round_XmmMem { src: memory, .. }
+            | Instruction::Vcvtph2ps_XmmMem { src: memory, .. }
+            | Instruction::Vcvtph2ps_YmmMem { src: memory, .. }
+            | Instruction::Pcmpistri_XmmMemImm { src: memory, .. }
+            | Instruction::Pcmpestri_XmmMemImm { src: memory, .. }
+            | Instruction::Ptest_XmmMem { src: memory, .. }
+            | Instruction::Pblendvb_XmmMem { src: memory, .. }
+            | Instruction::Blendps_XmmMemImm { src: memory, .. }
+            | Instruction::PackedMinMax_XmmMem { src: memory, .. }
+            | Instruction::Pinsr_XmmMemImm { src: memory, .. }
+            | Instruction::Pmovx_XmmMem { src: memory, .. }
+            | Instruction::Fld_Mem { src: memory, .. }
+            | Instruction::Fild_Mem { src: memory, .. }
+            | Instruction::X87Arith_Mem { src: memory, .. }
+            | Instruction::Fldcw_Mem { src: memory, .. }
+            | Instruction::Lgdt_Mem { src: memory, .. }
+            | Instruction::Lidt_Mem { src: memory, .. }
+            | Instruction::Ltr_Mem { src: memory, .. }
+            | Instruction::Invlpg_Mem { src: memory, .. }
+            | Instruction::Xrstor_Mem { src: memory, .. }
+            | Instruction::Add_RegMemReg { src1: memory, .. }
+            | Instruction::Add_RegMemImm { src1: memory, .. }
+            | Instruction::Sub_RegMemReg { src1: memory, .. }
+            | Instruction::Sub_RegMemImm { src1: memory, .. }
+            | Instruction::Xor_RegMemReg { src1: memory, .. }
+            | Instruction::Xor_RegMemImm { src1: memory, .. }
+            | Instruction::Fma_XmmXmmMem { src2: memory, .. }
+            | Instruction::Fma_YmmYmmMem { src2: memory, .. }
+            | Instruction::Add_RegRegMem { src2: memory, .. }
+            | Instruction::Sub_RegRegMem { src2: memory, .. }
+            | Instruction::Xor_RegRegMem { src2: memory, .. } => Some(memory),
+            _ => None,
+        }
+    };
+}
+
+impl Instruction {
+    /// Returns the memory operand of the instruction, if any.
+    ///
+    /// Each instruction has at most one memory operand.
+    pub(crate) const fn memory_operand(&self) -> Option<&Memory> {
+        match_memory_operand!(self)
+    }
+
+    /// Mutable version of [`Instruction::memory_operand`].
+    pub(crate) const fn memory_operand_mut(&mut self) -> Option<&mut Memory> {
+        match_memory_operand!(self)
+    }
+}
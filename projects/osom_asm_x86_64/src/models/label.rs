@@ -9,6 +9,9 @@ pub struct Label {
     value: u32,
 }
 
+/// The bit that distinguishes labels of a [`LabelAllocator`] from the global ones.
+const ALLOCATED_LABEL_FLAG: u32 = 1 << 31;
+
 static LABEL_COUNTER: AtomicU32 = AtomicU32::new(0);
 
 #[must_use]
@@ -16,7 +19,7 @@ fn get_next_label_value() -> u32 {
     loop {
         let value = LABEL_COUNTER.load(Ordering::SeqCst);
 
-        assert!(value != u32::MAX, "Label counter overflow.");
+        assert!(value != ALLOCATED_LABEL_FLAG, "Label counter overflow.");
 
         if LABEL_COUNTER
             .compare_exchange(value, value + 1, Ordering::SeqCst, Ordering::SeqCst)
@@ -34,8 +37,10 @@ impl Label {
     /// # Panics
     ///
     /// Panics if the number of labels overflows. At the moment
-    /// the limit is set to `u32::MAX` labels. Globally, during
-    /// the execution of the program.
+    /// the limit is set to `2^31` labels. Globally, during
+    /// the execution of the program. The limit used to be `u32::MAX`,
+    /// the upper half of the label space is taken by [`LabelAllocator`],
+    /// which has no global limit.
     #[inline(always)]
     pub fn new() -> Self {
         Self {
@@ -44,9 +49,86 @@ impl Label {
     }
 }
 
+impl Label {
+    /// Returns a label that is never produced by [`Label::new`] or
+    /// [`LabelAllocator::allocate`], since the counters stop before
+    /// reaching it. Used by the assembler as a stand-in operand that
+    /// is never set or patched.
+    #[inline(always)]
+    pub(crate) const fn placeholder() -> Self {
+        Self { value: u32::MAX }
+    }
+
+    /// Returns the index of the label in its [`LabelAllocator`], or `None`
+    /// for labels created with [`Label::new`].
+    #[inline(always)]
+    pub(crate) const fn allocated_index(self) -> Option<u32> {
+        if self.value & ALLOCATED_LABEL_FLAG == 0 || self.value == u32::MAX {
+            return None;
+        }
+        Some(self.value & !ALLOCATED_LABEL_FLAG)
+    }
+
+    #[inline(always)]
+    pub(crate) const fn from_allocated_index(index: u32) -> Self {
+        Self {
+            value: index | ALLOCATED_LABEL_FLAG,
+        }
+    }
+}
+
 impl Default for Label {
     #[inline(always)]
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Allocates labels with dense indices, without the global counter of [`Label::new`].
+///
+/// Each [`X86_64Assembler`][`crate::assembler::X86_64Assembler`] owns an allocator,
+/// see [`X86_64Assembler::new_label`][`crate::assembler::X86_64Assembler::new_label`],
+/// and keeps the positions of its labels in plain vectors. The allocated labels never
+/// collide with the ones created by [`Label::new`].
+///
+/// # Notes
+///
+/// Labels of different allocators can be equal. To refer to the labels of one assembler
+/// from another, e.g. through predefined labels, pass the allocator of the first to the
+/// second one with
+/// [`X86_64AssemblerBuilder::with_label_allocator`][`crate::assembler::X86_64AssemblerBuilder::with_label_allocator`],
+/// so that the allocation continues where it stopped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct LabelAllocator {
+    next_index: u32,
+}
+
+impl LabelAllocator {
+    /// Creates a new allocator, with no labels allocated yet.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { next_index: 0 }
+    }
+
+    /// Allocates a new label, unique within this allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of allocated labels overflows. The limit is
+    /// set to `2^31 - 1` labels per allocator.
+    #[inline]
+    pub fn allocate(&mut self) -> Label {
+        assert!(self.next_index < !ALLOCATED_LABEL_FLAG, "Label allocator overflow.");
+        let label = Label::from_allocated_index(self.next_index);
+        self.next_index += 1;
+        label
+    }
+
+    /// Returns the number of labels allocated so far.
+    #[inline(always)]
+    #[must_use]
+    pub const fn allocated(&self) -> u32 {
+        self.next_index
+    }
+}
@@ -0,0 +1,15 @@
+/// Represents the `loop` instruction family, which decrements
+/// `RCX` and jumps if the result is not zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum LoopKind {
+    /// `loop`: jumps if `RCX` is not zero.
+    Loop = 1,
+
+    /// `loope`: jumps if `RCX` is not zero and `ZF` is set.
+    LoopEqual,
+
+    /// `loopne`: jumps if `RCX` is not zero and `ZF` is clear.
+    LoopNotEqual,
+}
@@ -26,6 +26,22 @@ pub(crate) enum MemoryImpl {
     },
 }
 
+/// The `ModRM` parts of a 16-bit address.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bit16ModRM {
+    pub modrm_mod: u8,
+    pub rm: u8,
+    pub displacement: [u8; 2],
+    pub displacement_length: usize,
+}
+
+impl Bit16ModRM {
+    #[inline(always)]
+    pub fn displacement(&self) -> &[u8] {
+        &self.displacement[..self.displacement_length]
+    }
+}
+
 /// Represents a general `X86_64` memory operand.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[must_use]
@@ -39,18 +55,75 @@ pub struct Memory {
 #[repr(u8)]
 #[must_use]
 pub enum NewMemoryError {
-    /// The base or index register is not 64-bit.
+    /// The base or index register is 8-bit.
     GPRNotBit64,
 
-    /// `RSP` register is not allowed as an index register.
+    /// `RSP` (or `ESP`) register is not allowed as an index register.
     RSPNotAllowedAsIndex,
+
+    /// The base and index registers are of different sizes.
+    AddressSizeMismatch,
+
+    /// The 16-bit address is not one of `[bx + si]`, `[bx + di]`, `[bp + si]`,
+    /// `[bp + di]`, `[si]`, `[di]`, `[bp]` or `[bx]` with an optional offset,
+    /// or the offset doesn't fit in 16 bits.
+    InvalidBit16Address,
+}
+
+#[inline]
+fn check_address_register(gpr: GPR) -> Result<(), NewMemoryError> {
+    match gpr.size() {
+        Size::Bit64 | Size::Bit32 | Size::Bit16 => Ok(()),
+        Size::Bit8 => Err(NewMemoryError::GPRNotBit64),
+    }
+}
+
+#[inline]
+fn check_index_register(index: GPR) -> Result<(), NewMemoryError> {
+    check_address_register(index)?;
+    if index == GPR::RSP || index == GPR::ESP {
+        return Err(NewMemoryError::RSPNotAllowedAsIndex);
+    }
+    Ok(())
+}
+
+/// Returns the `ModRM.rm` value of the 16-bit addressing form, if there is one.
+#[inline]
+fn bit16_rm(base: GPR, index: Option<GPR>) -> Option<u8> {
+    let rm = match (base, index) {
+        (GPR::BX, Some(GPR::SI)) => 0b000,
+        (GPR::BX, Some(GPR::DI)) => 0b001,
+        (GPR::BP, Some(GPR::SI)) => 0b010,
+        (GPR::BP, Some(GPR::DI)) => 0b011,
+        (GPR::SI, None) => 0b100,
+        (GPR::DI, None) => 0b101,
+        (GPR::BP, None) => 0b110,
+        (GPR::BX, None) => 0b111,
+        _ => return None,
+    };
+    Some(rm)
+}
+
+#[inline]
+fn check_bit16_address(base: GPR, index: Option<GPR>, offset: Immediate32) -> Result<(), NewMemoryError> {
+    if bit16_rm(base, index).is_none() || !(-0x8000..=0xFFFF).contains(&offset.value()) {
+        return Err(NewMemoryError::InvalidBit16Address);
+    }
+    Ok(())
 }
 
 impl Memory {
+    /// Creates `[base + offset]` memory operand.
+    ///
+    /// The `base` has to be 64-bit, 32-bit or 16-bit, which are
+    /// the address sizes of 64-bit, 32-bit and 16-bit code respectively.
+    /// 16-bit `base` is one of `BX`, `BP`, `SI` or `DI`.
     #[inline]
     pub fn based(base: GPR, offset: Immediate32) -> Result<Self, NewMemoryError> {
-        if base.size() != Size::Bit64 {
-            return Err(NewMemoryError::GPRNotBit64);
+        check_address_register(base)?;
+
+        if base.size() == Size::Bit16 {
+            check_bit16_address(base, None, offset)?;
         }
 
         Ok(Self {
@@ -58,14 +131,15 @@ impl Memory {
         })
     }
 
+    /// Creates `[index * scale + offset]` memory operand.
+    ///
+    /// There is no such 16-bit addressing form.
     #[inline]
     pub fn scaled(index: GPR, scale: Scale, offset: Immediate32) -> Result<Self, NewMemoryError> {
-        if index.size() != Size::Bit64 {
-            return Err(NewMemoryError::GPRNotBit64);
-        }
+        check_index_register(index)?;
 
-        if index == GPR::RSP {
-            return Err(NewMemoryError::RSPNotAllowedAsIndex);
+        if index.size() == Size::Bit16 {
+            return Err(NewMemoryError::InvalidBit16Address);
         }
 
         Ok(Self {
@@ -73,18 +147,25 @@ impl Memory {
         })
     }
 
+    /// Creates `[base + index * scale + offset]` memory operand.
+    ///
+    /// Both `base` and `index` have to be of the same size. 16-bit
+    /// `base` is either `BX` or `BP`, `index` is either `SI` or `DI`,
+    /// and `scale` has to be [`Scale::Scale1`].
     #[inline]
     pub fn based_scaled(base: GPR, index: GPR, scale: Scale, offset: Immediate32) -> Result<Self, NewMemoryError> {
-        if base.size() != Size::Bit64 {
-            return Err(NewMemoryError::GPRNotBit64);
-        }
+        check_address_register(base)?;
+        check_index_register(index)?;
 
-        if index.size() != Size::Bit64 {
-            return Err(NewMemoryError::GPRNotBit64);
+        if base.size() != index.size() {
+            return Err(NewMemoryError::AddressSizeMismatch);
         }
 
-        if index == GPR::RSP {
-            return Err(NewMemoryError::RSPNotAllowedAsIndex);
+        if base.size() == Size::Bit16 {
+            if scale != Scale::Scale1 {
+                return Err(NewMemoryError::InvalidBit16Address);
+            }
+            check_bit16_address(base, Some(index), offset)?;
         }
 
         Ok(Self {
@@ -97,7 +178,8 @@ impl Memory {
         })
     }
 
-    /// This will get translated to RIP-relative address.
+    /// This will get translated to RIP-relative address, or to absolute
+    /// address in 32-bit and 16-bit code.
     #[inline(always)]
     pub const fn label(label: Label) -> Self {
         Self {
@@ -105,6 +187,11 @@ impl Memory {
         }
     }
 
+    #[inline(always)]
+    pub(crate) const fn as_impl(&self) -> &MemoryImpl {
+        &self.value
+    }
+
     #[inline(always)]
     pub(crate) fn get_label(&self) -> Option<&Label> {
         match &self.value {
@@ -113,6 +200,81 @@ impl Memory {
         }
     }
 
+    /// Returns `true` if the base or index register is one of the APX
+    /// extended registers.
+    #[inline]
+    #[must_use]
+    pub fn requires_apx(&self) -> bool {
+        match &self.value {
+            MemoryImpl::Based { base, .. } => base.requires_apx(),
+            MemoryImpl::Scaled { index, .. } => index.requires_apx(),
+            MemoryImpl::BasedScaled { base, index, .. } => base.requires_apx() || index.requires_apx(),
+            MemoryImpl::Label { .. } => false,
+        }
+    }
+
+    /// Returns the size of the base and index registers, or `None`
+    /// for labels, which fit any address size.
+    #[inline]
+    pub(crate) fn address_size(&self) -> Option<Size> {
+        match &self.value {
+            MemoryImpl::Based { base, .. } | MemoryImpl::BasedScaled { base, .. } => Some(base.size()),
+            MemoryImpl::Scaled { index, .. } => Some(index.size()),
+            MemoryImpl::Label { .. } => None,
+        }
+    }
+
+    /// Returns the `ModRM` parts of the 16-bit address. Labels are encoded
+    /// as `[disp16]`, with the displacement to be patched. Returns `None`
+    /// for other address sizes.
+    pub(crate) fn as_bit16_modrm(&self) -> Option<Bit16ModRM> {
+        const DISP16_RM: u8 = 0b110;
+
+        let (base, index, offset) = match &self.value {
+            MemoryImpl::Based { base, offset } => (*base, None, *offset),
+            MemoryImpl::BasedScaled {
+                base, index, offset, ..
+            } => (*base, Some(*index), *offset),
+            MemoryImpl::Label { .. } => {
+                return Some(Bit16ModRM {
+                    modrm_mod: 0b00,
+                    rm: DISP16_RM,
+                    displacement: [0; 2],
+                    displacement_length: 2,
+                });
+            }
+            MemoryImpl::Scaled { .. } => return None,
+        };
+
+        if base.size() != Size::Bit16 {
+            return None;
+        }
+
+        let rm = bit16_rm(base, index)?;
+        let value = offset.value();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let displacement = (value as u16).to_le_bytes();
+        // [bp] with no displacement is taken by [disp16], so it is encoded as [bp + 0].
+        let (modrm_mod, displacement_length) = if value == 0 && rm != DISP16_RM {
+            (0b00, 0)
+        } else if i8::try_from(value).is_ok() {
+            (0b01, 1)
+        } else {
+            (0b10, 2)
+        };
+
+        Some(Bit16ModRM {
+            modrm_mod,
+            rm,
+            displacement,
+            displacement_length,
+        })
+    }
+
+    /// Converts to the encoder memory. Note that 32-bit address registers are
+    /// passed as their 64-bit counterparts, since the encoding is the same
+    /// when no address size override is needed. It is up to the assembler to
+    /// verify that the address size matches the code mode.
     pub(crate) fn as_enc_mem(&self) -> enc_models::Memory {
         const fn imm_to_offset(offset: Immediate32) -> enc_models::Offset {
             let val = offset.value();
@@ -129,11 +291,11 @@ impl Memory {
 
         match &self.value {
             MemoryImpl::Based { base, offset } => enc_models::Memory::Based {
-                base: base.as_enc_gpr(),
+                base: base.as_bit64().as_enc_gpr(),
                 offset: imm_to_offset(*offset),
             },
             MemoryImpl::Scaled { index, scale, offset } => enc_models::Memory::Scaled {
-                index: index.as_enc_gpr(),
+                index: index.as_bit64().as_enc_gpr(),
                 scale: scale.as_enc_scale(),
                 offset: imm_to_offset(*offset),
             },
@@ -143,8 +305,8 @@ impl Memory {
                 scale,
                 offset,
             } => enc_models::Memory::BasedScaled {
-                base: base.as_enc_gpr(),
-                index: index.as_enc_gpr(),
+                base: base.as_bit64().as_enc_gpr(),
+                index: index.as_bit64().as_enc_gpr(),
                 scale: scale.as_enc_scale(),
                 offset: imm_to_offset(*offset),
             },
@@ -5,12 +5,24 @@ pub use size::*;
 mod condition;
 pub use condition::*;
 
+mod loop_kind;
+pub use loop_kind::*;
+
 mod gpr_kind;
 pub use gpr_kind::*;
 
 mod gpr;
 pub use gpr::*;
 
+mod vector_registers;
+pub use vector_registers::*;
+
+mod x87;
+pub use x87::*;
+
+mod system_registers;
+pub use system_registers::*;
+
 mod immediate32;
 pub use immediate32::*;
 
@@ -23,9 +35,36 @@ pub use scale::*;
 mod memory;
 pub use memory::*;
 
+mod float_type;
+pub use float_type::*;
+
+mod fma;
+pub use fma::*;
+
+mod rounding_mode;
+pub use rounding_mode::*;
+
+mod packed_min_max;
+pub use packed_min_max::*;
+
+mod crypto;
+pub use crypto::*;
+
+mod alignment;
+pub use alignment::*;
+
+mod data;
+pub use data::*;
+
 mod label;
 pub use label::*;
 
+mod section;
+pub use section::*;
+
+mod symbol;
+pub use symbol::*;
+
 mod instruction;
 pub use instruction::*;
 
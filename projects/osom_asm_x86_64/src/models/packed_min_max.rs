@@ -0,0 +1,29 @@
+/// Represents the SSE4.1 packed integer minimum/maximum instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum PackedMinMaxKind {
+    /// `pminsb`: signed bytes.
+    MinSignedByte = 1,
+
+    /// `pminsd`: signed doublewords.
+    MinSignedDword,
+
+    /// `pminuw`: unsigned words.
+    MinUnsignedWord,
+
+    /// `pminud`: unsigned doublewords.
+    MinUnsignedDword,
+
+    /// `pmaxsb`: signed bytes.
+    MaxSignedByte,
+
+    /// `pmaxsd`: signed doublewords.
+    MaxSignedDword,
+
+    /// `pmaxuw`: unsigned words.
+    MaxUnsignedWord,
+
+    /// `pmaxud`: unsigned doublewords.
+    MaxUnsignedDword,
+}
@@ -0,0 +1,30 @@
+/// Represents the rounding control immediate of `round*` and
+/// `vcvtps2ph` instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even.
+    Nearest = 0b000,
+
+    /// Round towards negative infinity, i.e. floor.
+    Down = 0b001,
+
+    /// Round towards positive infinity, i.e. ceil.
+    Up = 0b010,
+
+    /// Round towards zero, i.e. truncate.
+    TowardZero = 0b011,
+
+    /// Use the rounding mode currently set in `MXCSR`.
+    Current = 0b100,
+}
+
+impl RoundingMode {
+    /// Returns the value of the encoded immediate.
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
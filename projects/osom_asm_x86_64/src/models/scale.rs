@@ -28,4 +28,16 @@ impl Scale {
     pub(crate) fn as_enc_scale(self) -> enc_models::Scale {
         self.value
     }
+
+    /// Returns the two bits stored in the `SIB` byte.
+    #[inline(always)]
+    #[must_use]
+    pub(crate) const fn sib_bits(self) -> u8 {
+        match self.value {
+            enc_models::Scale::Scale1 => 0b00,
+            enc_models::Scale::Scale2 => 0b01,
+            enc_models::Scale::Scale4 => 0b10,
+            enc_models::Scale::Scale8 => 0b11,
+        }
+    }
 }
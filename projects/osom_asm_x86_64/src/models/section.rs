@@ -0,0 +1,64 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Represents a part of the assembled code, e.g. executable code or
+/// read only data. Everything emitted to the same section ends up
+/// contiguous, regardless of the order of emission.
+///
+/// See [`Instruction::SwitchSection`][`super::Instruction::SwitchSection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(transparent)]
+pub struct Section {
+    value: u32,
+}
+
+const PREDEFINED_SECTIONS_COUNT: u32 = 3;
+
+static SECTION_COUNTER: AtomicU32 = AtomicU32::new(PREDEFINED_SECTIONS_COUNT);
+
+#[must_use]
+fn get_next_section_value() -> u32 {
+    loop {
+        let value = SECTION_COUNTER.load(Ordering::SeqCst);
+
+        assert!(value != u32::MAX, "Section counter overflow.");
+
+        if SECTION_COUNTER
+            .compare_exchange(value, value + 1, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            return value;
+        }
+    }
+}
+
+impl Section {
+    /// Executable code. This is the section the assembler starts with.
+    pub const TEXT: Self = Self { value: 0 };
+
+    /// Read only data. The constant pool is emitted here.
+    pub const RODATA: Self = Self { value: 1 };
+
+    /// Writable data.
+    pub const DATA: Self = Self { value: 2 };
+
+    /// Creates a new custom section. Note that each call produces a new
+    /// unique section, that won't be equal to any other section.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of sections overflows, see [`Label::new`][`super::Label::new`].
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            value: get_next_section_value(),
+        }
+    }
+}
+
+impl Default for Section {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,104 @@
+use super::Label;
+
+/// The kind of a [`Symbol`], like `STT_FUNC` and `STT_OBJECT` of ELF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[must_use]
+pub enum SymbolKind {
+    /// Executable code.
+    Function = 1,
+
+    /// Data, e.g. a variable or a table.
+    Object,
+}
+
+/// The size of a [`Symbol`] in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub enum SymbolSize {
+    /// The size is not known.
+    Unknown,
+
+    /// The size is given explicitly.
+    Fixed(u32),
+
+    /// The size is the distance from the symbol to the label, computed
+    /// during assembly. The label has to be set after the symbol.
+    UntilLabel(Label),
+}
+
+/// Represents a public label with a human readable name, e.g. for object
+/// files, perf maps or debugging.
+///
+/// Emitting the symbol sets its label at the current position, like
+/// [`Instruction::SetPublic_Label`][`super::Instruction::SetPublic_Label`].
+/// The resolved symbols are reported by
+/// [`EmissionData::symbols`][`crate::assembler::EmissionData::symbols`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct Symbol {
+    name: String,
+    label: Label,
+    kind: SymbolKind,
+    size: SymbolSize,
+}
+
+impl Symbol {
+    /// Creates a new symbol of unknown size.
+    #[inline]
+    pub fn new(name: impl Into<String>, label: Label, kind: SymbolKind) -> Self {
+        Self {
+            name: name.into(),
+            label,
+            kind,
+            size: SymbolSize::Unknown,
+        }
+    }
+
+    /// Same as [`Symbol::new`] with [`SymbolKind::Function`].
+    #[inline(always)]
+    pub fn function(name: impl Into<String>, label: Label) -> Self {
+        Self::new(name, label, SymbolKind::Function)
+    }
+
+    /// Same as [`Symbol::new`] with [`SymbolKind::Object`].
+    #[inline(always)]
+    pub fn object(name: impl Into<String>, label: Label) -> Self {
+        Self::new(name, label, SymbolKind::Object)
+    }
+
+    /// Sets the size of the symbol to `size` bytes.
+    #[inline(always)]
+    pub fn with_size(mut self, size: u32) -> Self {
+        self.size = SymbolSize::Fixed(size);
+        self
+    }
+
+    /// Sets the size of the symbol to the distance to the `end` label.
+    #[inline(always)]
+    pub fn with_end(mut self, end: Label) -> Self {
+        self.size = SymbolSize::UntilLabel(end);
+        self
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline(always)]
+    pub const fn label(&self) -> Label {
+        self.label
+    }
+
+    #[inline(always)]
+    pub const fn kind(&self) -> SymbolKind {
+        self.kind
+    }
+
+    #[inline(always)]
+    pub const fn size(&self) -> SymbolSize {
+        self.size
+    }
+}
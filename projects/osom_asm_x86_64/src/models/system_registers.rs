@@ -0,0 +1,105 @@
+/// Represents an error that occurs when creating a new system register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum NewSystemRegisterError {
+    /// Error when creating a new register with `index` that doesn't
+    /// correspond to an architecturally defined register.
+    InvalidIndex,
+}
+
+/// Represents an `X86_64` control register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+#[must_use]
+pub struct CR {
+    index: u8,
+}
+
+impl CR {
+    pub const CR0: Self = Self { index: 0 };
+    pub const CR2: Self = Self { index: 2 };
+    pub const CR3: Self = Self { index: 3 };
+    pub const CR4: Self = Self { index: 4 };
+    pub const CR8: Self = Self { index: 8 };
+
+    /// Creates a new control register. Only `CR0`, `CR2`, `CR3`, `CR4`
+    /// and `CR8` exist, the rest of indexes are reserved.
+    #[inline]
+    pub const fn new(index: u8) -> Result<Self, NewSystemRegisterError> {
+        match index {
+            0 | 2 | 3 | 4 | 8 => Ok(Self { index }),
+            _ => Err(NewSystemRegisterError::InvalidIndex),
+        }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self.index
+    }
+}
+
+/// Represents an `X86_64` debug register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+#[must_use]
+pub struct DR {
+    index: u8,
+}
+
+impl DR {
+    pub const DR0: Self = Self { index: 0 };
+    pub const DR1: Self = Self { index: 1 };
+    pub const DR2: Self = Self { index: 2 };
+    pub const DR3: Self = Self { index: 3 };
+    pub const DR6: Self = Self { index: 6 };
+    pub const DR7: Self = Self { index: 7 };
+
+    /// Creates a new debug register. `DR4` and `DR5` are aliases
+    /// of `DR6` and `DR7` and are not accepted.
+    #[inline]
+    pub const fn new(index: u8) -> Result<Self, NewSystemRegisterError> {
+        match index {
+            0 | 1 | 2 | 3 | 6 | 7 => Ok(Self { index }),
+            _ => Err(NewSystemRegisterError::InvalidIndex),
+        }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self.index
+    }
+}
+
+/// Represents an `X86_64` segment register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+#[must_use]
+pub struct Segment {
+    index: u8,
+}
+
+impl Segment {
+    pub const ES: Self = Self { index: 0 };
+    pub const CS: Self = Self { index: 1 };
+    pub const SS: Self = Self { index: 2 };
+    pub const DS: Self = Self { index: 3 };
+    pub const FS: Self = Self { index: 4 };
+    pub const GS: Self = Self { index: 5 };
+
+    #[inline]
+    pub const fn new(index: u8) -> Result<Self, NewSystemRegisterError> {
+        if index > 5 {
+            return Err(NewSystemRegisterError::InvalidIndex);
+        }
+
+        Ok(Self { index })
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self.index
+    }
+}
@@ -0,0 +1,74 @@
+/// Represents an error that occurs when creating a new vector register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum NewVectorRegisterError {
+    /// Error when creating a new vector register with `index` outside of the `0..=15` range.
+    IndexOutOfRange,
+}
+
+macro_rules! vector_register {
+    ($(#[$attr:meta])* $name:ident, [$($reg:ident = $index:literal),* $(,)?]) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        #[must_use]
+        pub struct $name {
+            index: u8,
+        }
+
+        impl $name {
+            $(
+                pub const $reg: Self = Self { index: $index };
+            )*
+
+            #[inline]
+            pub const fn new(index: u8) -> Result<Self, NewVectorRegisterError> {
+                if index > 15 {
+                    return Err(NewVectorRegisterError::IndexOutOfRange);
+                }
+
+                Ok(Self { index })
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn index(self) -> u8 {
+                self.index
+            }
+        }
+    };
+}
+
+vector_register!(
+    /// Represents a 128-bit `X86_64` SSE/AVX register.
+    XMM,
+    [
+        XMM0 = 0, XMM1 = 1, XMM2 = 2, XMM3 = 3, XMM4 = 4, XMM5 = 5, XMM6 = 6, XMM7 = 7,
+        XMM8 = 8, XMM9 = 9, XMM10 = 10, XMM11 = 11, XMM12 = 12, XMM13 = 13, XMM14 = 14, XMM15 = 15,
+    ]
+);
+
+vector_register!(
+    /// Represents a 256-bit `X86_64` AVX register.
+    YMM,
+    [
+        YMM0 = 0, YMM1 = 1, YMM2 = 2, YMM3 = 3, YMM4 = 4, YMM5 = 5, YMM6 = 6, YMM7 = 7,
+        YMM8 = 8, YMM9 = 9, YMM10 = 10, YMM11 = 11, YMM12 = 12, YMM13 = 13, YMM14 = 14, YMM15 = 15,
+    ]
+);
+
+impl XMM {
+    /// Returns the [`YMM`] register whose lower half is this register.
+    #[inline(always)]
+    pub const fn as_ymm(self) -> YMM {
+        YMM { index: self.index }
+    }
+}
+
+impl YMM {
+    /// Returns the [`XMM`] register aliasing the lower half of this register.
+    #[inline(always)]
+    pub const fn as_xmm(self) -> XMM {
+        XMM { index: self.index }
+    }
+}
@@ -0,0 +1,109 @@
+/// Represents an error that occurs when creating a new [`ST`] register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum NewSTError {
+    /// Error when creating a new `ST` register with `index` outside of the `0..=7` range.
+    IndexOutOfRange,
+}
+
+/// Represents an `X86_64` x87 FPU stack register, i.e. `ST(i)`.
+/// The index is relative to the current top of the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+#[must_use]
+pub struct ST {
+    index: u8,
+}
+
+impl ST {
+    pub const ST0: Self = Self { index: 0 };
+    pub const ST1: Self = Self { index: 1 };
+    pub const ST2: Self = Self { index: 2 };
+    pub const ST3: Self = Self { index: 3 };
+    pub const ST4: Self = Self { index: 4 };
+    pub const ST5: Self = Self { index: 5 };
+    pub const ST6: Self = Self { index: 6 };
+    pub const ST7: Self = Self { index: 7 };
+
+    #[inline]
+    pub const fn new(index: u8) -> Result<Self, NewSTError> {
+        if index > 7 {
+            return Err(NewSTError::IndexOutOfRange);
+        }
+
+        Ok(Self { index })
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self.index
+    }
+}
+
+/// Represents the size of a floating point value in memory
+/// accessed by x87 instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum X87FloatSize {
+    /// 32-bit single precision float.
+    Single = 1,
+
+    /// 64-bit double precision float.
+    Double,
+
+    /// 80-bit extended precision float.
+    Extended,
+}
+
+/// Represents the arithmetic performed by x87 instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum X87ArithKind {
+    /// `fadd`: `dst = dst + src`
+    Add = 1,
+
+    /// `fmul`: `dst = dst * src`
+    Mul,
+
+    /// `fsub`: `dst = dst - src`
+    Sub,
+
+    /// `fsubr`: `dst = src - dst`
+    SubReversed,
+
+    /// `fdiv`: `dst = dst / src`
+    Div,
+
+    /// `fdivr`: `dst = src / dst`
+    DivReversed,
+}
+
+/// Represents the constants that x87 can push onto its stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+#[repr(u8)]
+pub enum X87Constant {
+    /// `fld1`: `+1.0`
+    One = 1,
+
+    /// `fldz`: `+0.0`
+    Zero,
+
+    /// `fldpi`: `π`
+    Pi,
+
+    /// `fldl2t`: `log2(10)`
+    Log2Ten,
+
+    /// `fldl2e`: `log2(e)`
+    Log2E,
+
+    /// `fldlg2`: `log10(2)`
+    Log10Two,
+
+    /// `fldln2`: `ln(2)`
+    LnTwo,
+}
@@ -0,0 +1,183 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::assemble;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, EmitError, SectionLayout, X86_64AssemblerBuilder},
+    models::{Alignment, GPR, Instruction, Label, Section},
+};
+
+const FAR_ADDRESS: u64 = 0x1122_3344_5566_7788;
+const OTHER_FAR_ADDRESS: u64 = 0x7FFF_0000_1234;
+
+#[test]
+fn test_shared_veneers() {
+    let instructions = [
+        Instruction::Call_Absolute { address: FAR_ADDRESS },
+        Instruction::Call_Absolute { address: FAR_ADDRESS },
+        Instruction::Jump_Absolute { address: FAR_ADDRESS },
+        Instruction::Call_Absolute {
+            address: OTHER_FAR_ADDRESS,
+        },
+        Instruction::Ret,
+    ];
+    let final_code = assemble(X86_64AssemblerBuilder::new(), &instructions);
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xE8, 0x10, 0x00, 0x00, 0x00, 0xE8, 0x0B, 0x00, 0x00, 0x00, 0xE9, 0x06, 0x00, 0x00, 0x00, 0xE8, 0x0F, 0x00,
+            0x00, 0x00, 0xC3, 0xFF, 0x25, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0xFF,
+            0x25, 0x00, 0x00, 0x00, 0x00, 0x34, 0x12, 0x00, 0x00, 0xFF, 0x7F, 0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn test_veneers_in_text_section() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let constant = assembler.constant_u64(0x1122_3344_5566_7788);
+    assembler
+        .emit(Instruction::Call_Absolute { address: FAR_ADDRESS })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: constant,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let layout = SectionLayout::new()
+        .with_section(Section::TEXT)
+        .with_aligned_section(Section::RODATA, Alignment::new(4096).unwrap());
+    let mut final_code = Vec::new();
+    let result = assembler.assemble_with_layout(&layout, &mut final_code).unwrap();
+    assert_eq_hex!(
+        &final_code[..27],
+        &[
+            0xE8, 0x08, 0x00, 0x00, 0x00, 0x48, 0x8B, 0x05, 0xF4, 0x0F, 0x00, 0x00, 0xC3, 0xFF, 0x25, 0x00, 0x00, 0x00,
+            0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11
+        ]
+    );
+
+    let text = result.section(Section::TEXT).unwrap();
+    assert_eq!(text.offset(), 0);
+    assert_eq!(text.length(), 27);
+    let rodata = result.section(Section::RODATA).unwrap();
+    assert_eq!(rodata.offset(), 4096);
+    assert_eq!(rodata.length(), 8);
+}
+
+#[rstest]
+#[case(0x1000_1000, &[0xE8, 0xFB, 0x0F, 0x00, 0x00])]
+#[case(0x0FFF_F000, &[0xE8, 0xFB, 0xEF, 0xFF, 0xFF])]
+fn test_direct_call_with_base_address(#[case] address: u64, #[case] expected: &[u8]) {
+    let builder = X86_64AssemblerBuilder::new().with_base_address(0x1000_0000);
+    let final_code = assemble(builder, &[Instruction::Call_Absolute { address }]);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_veneer_with_base_address_out_of_range() {
+    let builder = X86_64AssemblerBuilder::new().with_base_address(0x1000_0000);
+    let final_code = assemble(
+        builder,
+        &[
+            Instruction::Jump_Absolute {
+                address: OTHER_FAR_ADDRESS,
+            },
+            Instruction::Call_Absolute { address: 0x1000_1000 },
+        ],
+    );
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xE9, 0x05, 0x00, 0x00, 0x00, 0xE8, 0xF6, 0x0F, 0x00, 0x00, 0xFF, 0x25, 0x00, 0x00, 0x00, 0x00, 0x34, 0x12,
+            0x00, 0x00, 0xFF, 0x7F, 0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn test_bit32_direct_call() {
+    let builder = X86_64AssemblerBuilder::new()
+        .with_code_mode(CodeMode::Bit32)
+        .with_base_address(0x40_0000);
+    let final_code = assemble(builder, &[Instruction::Call_Absolute { address: 0x40_1000 }]);
+    assert_eq_hex!(final_code, &[0xE8, 0xFB, 0x0F, 0x00, 0x00]);
+
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_code_mode(CodeMode::Bit32)
+        .with_base_address(0x40_0000)
+        .build();
+    let result = assembler.emit(Instruction::Call_Absolute { address: FAR_ADDRESS });
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[test]
+fn test_bit32_requires_base_address() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit32).build();
+    let result = assembler.emit(Instruction::Call_Absolute { address: 0x1000 });
+    assert!(matches!(result, Err(EmitError::BaseAddressNotSet)));
+    let result = assembler.emit(Instruction::Jump_Absolute { address: 0x1000 });
+    assert!(matches!(result, Err(EmitError::BaseAddressNotSet)));
+}
+
+#[test]
+fn test_bit16_not_supported() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit16).build();
+    let result = assembler.emit(Instruction::Jump_Absolute { address: 0x1000 });
+    assert!(matches!(result, Err(EmitError::NotSupportedInCodeMode)));
+}
+
+#[test]
+fn test_public_label_addresses() {
+    let label = Label::new();
+    let instructions = [
+        Instruction::Ret,
+        Instruction::SetPublic_Label { label },
+        Instruction::Ret,
+    ];
+
+    for (builder, expected) in [
+        (
+            X86_64AssemblerBuilder::new().with_base_address(0x4000_0000),
+            Some(0x4000_0001),
+        ),
+        (X86_64AssemblerBuilder::new(), None),
+    ] {
+        let mut assembler = builder.build();
+        for instruction in &instructions {
+            assembler.emit(instruction.clone()).unwrap();
+        }
+
+        let mut final_code = Vec::new();
+        let result = assembler.assemble(&mut final_code).unwrap();
+        assert_eq!(result.public_label_address(&label), expected);
+        assert_eq!(result.public_label_address(&Label::new()), None);
+        assert_eq!(result.base_address(), expected.map(|address| address - 1));
+    }
+}
+
+#[test]
+fn test_base_address_resolves_calls_and_label_addresses() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_base_address(0x1000_0000).build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Call_Absolute { address: 0x1000_0100 })
+        .unwrap();
+    assembler.emit(Instruction::LabelAddress64 { label }).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xE8, 0xFB, 0x00, 0x00, 0x00, 0x0D, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00
+        ]
+    );
+    assert_eq!(result.public_label_address(&label), Some(0x1000_000D));
+}
@@ -0,0 +1,134 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, X86_64AssemblerBuilder},
+    models::{AlignFill, Alignment, Instruction, Label, NewAlignmentError},
+};
+
+fn align(boundary: u32, fill: AlignFill) -> Instruction {
+    Instruction::Align {
+        boundary: Alignment::new(boundary).unwrap(),
+        fill,
+    }
+}
+
+#[rstest]
+#[case(AlignFill::Int3, &[0x01, 0x02, 0x03, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xC3])]
+#[case(AlignFill::Zero, &[0x01, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC3])]
+#[case(AlignFill::Nop, &[0x01, 0x02, 0x03, 0x0F, 0x1F, 0x44, 0x00, 0x00, 0xC3])]
+fn test_align_fill(#[case] fill: AlignFill, #[case] expected: &[u8]) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit([1, 2, 3]).unwrap();
+    assembler.emit(align(8, fill)).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_align_long_nop_fill() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit([0xC3]).unwrap();
+    assembler.emit(align(16, AlignFill::Nop)).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 17);
+    assert_eq!(result.public_labels_positions()[&label], 16);
+    assert_eq_hex!(
+        &final_code[1..16],
+        &[
+            0x66, 0x0F, 0x1F, 0x84, 0x00, 0x00, 0x00, 0x00, 0x00, 0x66, 0x0F, 0x1F, 0x44, 0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn test_align_already_aligned() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit([0xCC; 8]).unwrap();
+    assembler.emit(align(8, AlignFill::Nop)).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 9);
+}
+
+#[rstest]
+#[case(true, 2)]
+#[case(false, 5)]
+fn test_align_after_jump(#[case] with_relaxation: bool, #[case] jump_length: usize) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(with_relaxation).build();
+    let label = Label::new();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    assembler.emit(align(16, AlignFill::Int3)).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 17);
+    assert_eq!(result.public_labels_positions()[&label], 16);
+    assert!(final_code[jump_length..16].iter().all(|byte| *byte == 0xCC));
+    assert_eq!(final_code[16], 0xC3);
+}
+
+#[test]
+fn test_align_after_relaxed_jump() {
+    // The jump is short at first, and the padding has to shrink when it gets long.
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(true).build();
+    let far = Label::new();
+    let aligned = Label::new();
+    assembler.emit(Instruction::Jump_Label { dst: far }).unwrap();
+    assembler.emit(align(16, AlignFill::Int3)).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label: aligned }).unwrap();
+    assembler.emit([0x90; 200]).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label: far }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 217);
+    assert_eq!(result.public_labels_positions()[&aligned], 16);
+    assert_eq!(result.public_labels_positions()[&far], 216);
+    assert_eq_hex!(&final_code[..5], &[0xE9, 0xD3, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn test_align_relative_to_base_address() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_base_address(0x1004).build();
+    assembler.emit([0xC3]).unwrap();
+    assembler.emit(align(8, AlignFill::Zero)).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3, 0x00, 0x00, 0x00, 0xC3]);
+}
+
+#[test]
+fn test_align_nop_fill_in_bit16_code() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit16).build();
+    assembler.emit([0xC3]).unwrap();
+    assembler.emit(align(4, AlignFill::Nop)).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3, 0x90, 0x90, 0x90]);
+}
+
+#[rstest]
+#[case(0)]
+#[case(3)]
+#[case(24)]
+fn test_new_alignment(#[case] value: u32) {
+    assert_eq!(Alignment::new(value), Err(NewAlignmentError::NotPowerOfTwo));
+}
@@ -0,0 +1,140 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::{assemble_single, mem};
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{GPR, GPRKind, Immediate32, Immediate64, Instruction, Label, Memory, NewGPRError, Scale},
+};
+
+fn builder() -> X86_64AssemblerBuilder {
+    X86_64AssemblerBuilder::new().with_apx(true)
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegReg { dst: GPR::R17, src: GPR::R16 }, &[0xD5, 0x58, 0x89, 0xC1])]
+#[case(Instruction::Mov_RegReg { dst: GPR::RAX, src: GPR::R31 }, &[0xD5, 0x4C, 0x89, 0xF8])]
+#[case(Instruction::Mov_RegReg { dst: GPR::R16D, src: GPR::EAX }, &[0xD5, 0x10, 0x89, 0xC0])]
+#[case(Instruction::Mov_RegReg { dst: GPR::R16W, src: GPR::R17W }, &[0x66, 0xD5, 0x50, 0x89, 0xC8])]
+#[case(Instruction::Mov_RegReg { dst: GPR::R20B, src: GPR::SIL }, &[0xD5, 0x10, 0x88, 0xF4])]
+#[case(Instruction::Mov_RegMem { dst: GPR::R18, src: mem(GPR::R19, 8) }, &[0xD5, 0x58, 0x8B, 0x53, 0x08])]
+#[case(Instruction::Mov_MemReg { dst: mem(GPR::R20, 0), src: GPR::RAX }, &[0xD5, 0x18, 0x89, 0x04, 0x24])]
+#[case(Instruction::Mov_MemReg { dst: mem(GPR::R21, 0), src: GPR::RCX }, &[0xD5, 0x18, 0x89, 0x4D, 0x00])]
+#[case(Instruction::Mov_RegImm { dst: GPR::R16D, src: Immediate32::new(1) }, &[0xD5, 0x10, 0xB8, 0x01, 0x00, 0x00, 0x00])]
+#[case(Instruction::Mov_RegImm { dst: GPR::R16, src: Immediate32::new(-1) }, &[0xD5, 0x18, 0xC7, 0xC0, 0xFF, 0xFF, 0xFF, 0xFF])]
+#[case(Instruction::Mov_RegImm64 { dst: GPR::R31, src: Immediate64::new(0x1122_3344_5566_7788) }, &[0xD5, 0x19, 0xBF, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11])]
+fn test_rex2_mov(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_rex2_scaled_index() {
+    let src = Memory::based_scaled(GPR::RAX, GPR::R25, Scale::Scale8, Immediate32::new(0)).unwrap();
+    let final_code = assemble_single(builder(), Instruction::Mov_RegMem { dst: GPR::RDX, src });
+    assert_eq_hex!(final_code, &[0xD5, 0x2A, 0x8B, 0x14, 0xC8]);
+}
+
+#[rstest]
+#[case(Instruction::Add_RegReg { dst: GPR::R16, src: GPR::RAX }, &[0xD5, 0x18, 0x01, 0xC0])]
+#[case(Instruction::Sub_RegImm { dst: GPR::R17, src: Immediate32::new(1) }, &[0xD5, 0x18, 0x83, 0xE9, 0x01])]
+#[case(Instruction::Xor_RegImm { dst: GPR::R17D, src: Immediate32::new(0x1000) }, &[0xD5, 0x10, 0x81, 0xF1, 0x00, 0x10, 0x00, 0x00])]
+#[case(Instruction::Cmp_RegMem { dst: GPR::R16, src: mem(GPR::RAX, 0) }, &[0xD5, 0x48, 0x3B, 0x00])]
+#[case(Instruction::Add_MemImm { dst: mem(GPR::R16, 0), src: Immediate32::new(1) }, &[0xD5, 0x10, 0x80, 0x00, 0x01])]
+#[case(Instruction::Sub_MemReg { dst: mem(GPR::RAX, 0), src: GPR::R24D }, &[0xD5, 0x44, 0x29, 0x00])]
+fn test_rex2_group1(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Push_Reg { src: GPR::R16 }, &[0xD5, 0x10, 0x50])]
+#[case(Instruction::Pop_Reg { src: GPR::R31 }, &[0xD5, 0x11, 0x5F])]
+#[case(Instruction::Push_Mem { src: mem(GPR::R16, 0) }, &[0xD5, 0x10, 0xFF, 0x30])]
+#[case(Instruction::Pop_Mem { src: mem(GPR::R16, 0) }, &[0xD5, 0x10, 0x8F, 0x00])]
+#[case(Instruction::Jump_Reg { dst: GPR::R16 }, &[0xD5, 0x10, 0xFF, 0xE0])]
+#[case(Instruction::Call_Mem { dst: mem(GPR::R17, 0) }, &[0xD5, 0x10, 0xFF, 0x11])]
+fn test_rex2_push_pop_control(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Add_RegRegReg { dst: GPR::R18, src1: GPR::R16, src2: GPR::R17 }, &[0x62, 0xEC, 0xEC, 0x10, 0x01, 0xC8])]
+#[case(Instruction::Sub_RegRegReg { dst: GPR::RAX, src1: GPR::RBX, src2: GPR::RCX }, &[0x62, 0xF4, 0xFC, 0x18, 0x29, 0xCB])]
+#[case(Instruction::Xor_RegRegReg { dst: GPR::EAX, src1: GPR::ECX, src2: GPR::EDX }, &[0x62, 0xF4, 0x7C, 0x18, 0x31, 0xD1])]
+#[case(Instruction::Add_RegRegReg { dst: GPR::AX, src1: GPR::CX, src2: GPR::DX }, &[0x62, 0xF4, 0x7D, 0x18, 0x01, 0xD1])]
+#[case(Instruction::Add_RegRegReg { dst: GPR::AL, src1: GPR::CL, src2: GPR::DL }, &[0x62, 0xF4, 0x7C, 0x18, 0x00, 0xD1])]
+#[case(Instruction::Add_RegRegImm { dst: GPR::RAX, src1: GPR::RBX, src2: Immediate32::new(1) }, &[0x62, 0xF4, 0xFC, 0x18, 0x83, 0xC3, 0x01])]
+#[case(Instruction::Sub_RegRegImm { dst: GPR::R16D, src1: GPR::EAX, src2: Immediate32::new(0x1000) }, &[0x62, 0xF4, 0x7C, 0x10, 0x81, 0xE8, 0x00, 0x10, 0x00, 0x00])]
+#[case(Instruction::Add_RegRegMem { dst: GPR::RAX, src1: GPR::RBX, src2: mem(GPR::R16, 8) }, &[0x62, 0xFC, 0xFC, 0x18, 0x03, 0x58, 0x08])]
+#[case(Instruction::Xor_RegMemReg { dst: GPR::RCX, src1: mem(GPR::RAX, 0), src2: GPR::RDX }, &[0x62, 0xF4, 0xF4, 0x18, 0x31, 0x10])]
+#[case(Instruction::Add_RegMemImm { dst: GPR::EAX, src1: mem(GPR::RAX, 0), src2: Immediate32::new(5) }, &[0x62, 0xF4, 0x7C, 0x18, 0x83, 0x00, 0x05])]
+fn test_ndd(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_ndd_label_memory() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_apx(true).build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Add_RegRegMem {
+            dst: GPR::RAX,
+            src1: GPR::RAX,
+            src2: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(
+        final_code,
+        &[0x62, 0xF4, 0xFC, 0x18, 0x03, 0x05, 0x00, 0x00, 0x00, 0x00]
+    );
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegReg { dst: GPR::R16, src: GPR::RAX })]
+#[case(Instruction::Add_RegMem { dst: GPR::RAX, src: mem(GPR::R16, 0) })]
+#[case(Instruction::Push_Reg { src: GPR::R16 })]
+#[case(Instruction::Add_RegRegReg { dst: GPR::RAX, src1: GPR::RBX, src2: GPR::RCX })]
+fn test_apx_not_enabled(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::ApxNotEnabled)));
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegReg { dst: GPR::R16B, src: GPR::AH })]
+#[case(Instruction::Add_RegRegReg { dst: GPR::AH, src1: GPR::AL, src2: GPR::CL })]
+#[case(Instruction::Crc32_RegReg { dst: GPR::R16D, src: GPR::EAX })]
+fn test_incompatible_operands(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_apx(true).build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::IncompatibleOperands)));
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegReg { dst: GPR::R16, src: GPR::EAX })]
+#[case(Instruction::Add_RegRegReg { dst: GPR::RAX, src1: GPR::EBX, src2: GPR::RCX })]
+#[case(Instruction::Sub_RegRegImm { dst: GPR::AL, src1: GPR::CL, src2: Immediate32::new(0x1000) })]
+fn test_invalid_sizes(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_apx(true).build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[test]
+fn test_gpr_new() {
+    assert_eq!(GPR::new(GPRKind::Bit64, 16).unwrap(), GPR::R16);
+    assert_eq!(GPR::new(GPRKind::Bit8, 31).unwrap(), GPR::R31B);
+    assert_eq!(GPR::new(GPRKind::Bit64, 32), Err(NewGPRError::IndexOutOfRange));
+    assert!(GPR::R16W.requires_apx());
+    assert!(!GPR::R15.requires_apx());
+}
@@ -0,0 +1,161 @@
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, X86_64AssemblerBuilder},
+    models::{Condition, Data, GPR, Instruction, Label, Memory, Section, Symbol},
+};
+
+#[test]
+fn test_rollback_instructions() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit(Instruction::Ret).unwrap();
+    let checkpoint = assembler.checkpoint();
+    assembler
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::RAX,
+            src: GPR::RDI,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.rollback(&checkpoint);
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3, 0xC3]);
+    assert_eq!(result.emitted_bytes(), 2);
+}
+
+#[test]
+fn test_rollback_labels() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let start = Label::new();
+    let speculative = Label::new();
+    assembler.emit(Instruction::SetPublic_Label { label: start }).unwrap();
+    let checkpoint = assembler.checkpoint();
+    assembler
+        .emit(Instruction::SetPublic_Label { label: speculative })
+        .unwrap();
+    assembler.emit(Symbol::function("speculative", Label::new())).unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(Label::new()),
+        })
+        .unwrap();
+    assembler.rollback(&checkpoint);
+
+    assembler.emit(Instruction::Jump_Label { dst: start }).unwrap();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: speculative })
+        .unwrap();
+    assembler.emit(Symbol::function("speculative", Label::new())).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xEB, 0xFE, 0xC3]);
+    assert_eq!(result.public_labels_positions().len(), 2);
+    assert_eq!(result.public_labels_positions().get(&start), Some(&0));
+    assert_eq!(result.public_labels_positions().get(&speculative), None);
+    assert_eq!(result.symbol("speculative").unwrap().offset(), 2);
+}
+
+#[test]
+fn test_rollback_jump_to_removed_label() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    let checkpoint = assembler.checkpoint();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.rollback(&checkpoint);
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::LabelNotSet(missing)) if missing == label));
+}
+
+#[test]
+fn test_rollback_repeatedly() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    let checkpoint = assembler.checkpoint();
+    for _ in 0..3 {
+        assembler
+            .emit(Instruction::CondJump_Label {
+                condition: Condition::Equal,
+                dst: label,
+            })
+            .unwrap();
+        assembler
+            .emit(Instruction::SwitchSection { section: Section::DATA })
+            .unwrap();
+        assembler.emit(Data::U32(0x1122_3344)).unwrap();
+        assembler.rollback(&checkpoint);
+    }
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xEB, 0xFE]);
+    assert_eq!(result.sections().len(), 1);
+    assert!(result.section(Section::DATA).is_none());
+}
+
+#[test]
+fn test_rollback_aligned_data() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    let checkpoint = assembler.checkpoint();
+    assembler.emit(Data::U64(1).aligned()).unwrap();
+    assembler.rollback(&checkpoint);
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3, 0xC3]);
+    assert_eq!(result.public_labels_positions().get(&label), Some(&1));
+}
+
+#[test]
+fn test_rollback_aligned_data_after_other_emissions() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    let checkpoint = assembler.checkpoint();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: Label::new() })
+        .unwrap();
+    assembler.emit(Symbol::object("data", Label::new())).unwrap();
+    assembler.emit(Data::U64(1).aligned()).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.rollback(&checkpoint);
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3, 0xC3]);
+    assert_eq!(result.public_labels_positions().get(&label), Some(&1));
+    assert!(result.symbol("data").is_none());
+}
+
+#[test]
+fn test_rollback_keeps_labels_moved_before_checkpoint() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler.emit(Data::U64(1).aligned()).unwrap();
+    let checkpoint = assembler.checkpoint();
+    assembler.emit(Data::U64(2).aligned()).unwrap();
+    assembler.rollback(&checkpoint);
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(final_code.len(), 16);
+    assert_eq!(result.public_labels_positions().get(&label), Some(&8));
+}
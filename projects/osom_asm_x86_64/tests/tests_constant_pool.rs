@@ -0,0 +1,95 @@
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, X86_64AssemblerBuilder},
+    models::{Data, GPR, Instruction, XMM},
+};
+
+#[test]
+fn test_constant_deduplication() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let first = assembler.constant_f64(1.5);
+    let second = assembler.constant_f64(1.5);
+    let same_bits = assembler.constant_u64(1.5f64.to_bits());
+    let other = assembler.constant_f64(-1.5);
+    let narrower = assembler.constant(Data::Bytes(&1.5f64.to_le_bytes()));
+    assert_eq!(first, second);
+    assert_eq!(first, same_bits);
+    assert_ne!(first, other);
+    assert_ne!(first, narrower);
+}
+
+#[test]
+fn test_constant_pool_layout() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let byte = assembler.constant(Data::U8(0xAA));
+    let double = assembler.constant_f64(-2.0);
+    let word = assembler.constant_u32(0x1122_3344);
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::AL,
+            src: byte,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RCX,
+            src: double,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EDX,
+            src: word,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    // The f64 is placed at 24, the u32 at 32 and the u8 at 36.
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x8A, 0x05, 0x1E, 0x00, 0x00, 0x00, 0x48, 0x8B, 0x0D, 0x0B, 0x00, 0x00, 0x00, 0x8B, 0x15, 0x0D, 0x00, 0x00,
+            0x00, 0xC3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x44, 0x33, 0x22, 0x11,
+            0xAA,
+        ]
+    );
+}
+
+#[test]
+fn test_empty_constant_pool() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3]);
+}
+
+#[test]
+fn test_constant_pool_bit32() {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_code_mode(CodeMode::Bit32)
+        .with_base_address(0x1000)
+        .build();
+    let constant = assembler.constant_f32(1.0);
+    assembler
+        .emit(Instruction::Cvtss2sd_XmmMem {
+            dst: XMM::XMM0,
+            src: constant,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xF3, 0x0F, 0x5A, 0x05, 0x0C, 0x10, 0x00, 0x00, 0xC3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3F
+        ]
+    );
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, SectionLayout, X86_64AssemblerBuilder},
+    models::{AlignFill, Alignment, Condition, Data, GPR, Instruction, Label, Section},
+};
+
+#[test]
+fn test_continuation_short_jump_back() {
+    let entry = Label::new();
+    let mut first = X86_64AssemblerBuilder::new().build();
+    first.emit(Instruction::SetPublic_Label { label: entry }).unwrap();
+    first.emit(Instruction::Ret).unwrap();
+
+    let mut buffer = Vec::new();
+    let first_result = first.assemble(&mut buffer).unwrap();
+
+    let continuation = Label::new();
+    let mut second = X86_64AssemblerBuilder::new()
+        .with_start_offset(first_result.emitted_bytes())
+        .with_predefined_labels(first_result.public_labels_positions())
+        .build();
+    second
+        .emit(Instruction::SetPublic_Label { label: continuation })
+        .unwrap();
+    second.emit(Instruction::Jump_Label { dst: entry }).unwrap();
+
+    let second_result = second.assemble(&mut buffer).unwrap();
+    assert_eq_hex!(buffer, &[0xC3, 0xEB, 0xFD]);
+    assert_eq!(second_result.emitted_bytes(), 2);
+    assert_eq!(second_result.public_labels_positions().get(&continuation), Some(&1));
+    assert_eq!(second_result.public_labels_positions().get(&entry), None);
+}
+
+#[test]
+fn test_continuation_long_jump_back() {
+    let entry = Label::new();
+    let predefined_labels = HashMap::from([(entry, 0)]);
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_start_offset(300)
+        .with_predefined_labels(&predefined_labels)
+        .build();
+    assembler
+        .emit(Instruction::CondJump_Label {
+            condition: Condition::Equal,
+            dst: entry,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: entry }).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[0x0F, 0x84, 0xCE, 0xFE, 0xFF, 0xFF, 0xE9, 0xC9, 0xFE, 0xFF, 0xFF]
+    );
+}
+
+#[test]
+fn test_predefined_labels_with_layout() {
+    let entry = Label::new();
+    let predefined_labels = HashMap::from([(entry, 0)]);
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_start_offset(4)
+        .with_predefined_labels(&predefined_labels)
+        .build();
+    assembler.emit(Instruction::Jump_Label { dst: entry }).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::DATA })
+        .unwrap();
+    assembler.emit(Data::U32(0x1122_3344)).unwrap();
+
+    let layout = SectionLayout::new().with_section(Section::DATA);
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble_with_layout(&layout, &mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0x44, 0x33, 0x22, 0x11, 0xEB, 0xF6]);
+}
+
+#[test]
+fn test_continuation_alignment() {
+    let label = Label::new();
+    let mut assembler = X86_64AssemblerBuilder::new().with_start_offset(3).build();
+    assembler
+        .emit(Instruction::Align {
+            boundary: Alignment::new(16).unwrap(),
+            fill: AlignFill::Int3,
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let mut expected = vec![0xCC; 13];
+    expected.push(0xC3);
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.public_labels_positions().get(&label), Some(&16));
+}
+
+#[test]
+fn test_continuation_relocations() {
+    let label = Label::new();
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_base_address(0x1000)
+        .with_start_offset(8)
+        .build();
+    assembler
+        .emit(Instruction::Mov_RegLabel { dst: GPR::RAX, label })
+        .unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+
+    let mut buffer = vec![0x90; 8];
+    let result = assembler.assemble(&mut buffer).unwrap();
+    assert_eq_hex!(
+        &buffer[8..],
+        &[0x48, 0xB8, 0x12, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(result.public_label_address(&label), Some(0x1012));
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].offset(), 10);
+    assert_eq!(relocations[0].addend(), 18);
+
+    result.apply_relocations(&mut buffer, 0x2000).unwrap();
+    assert_eq_hex!(&buffer[10..18], &0x2012u64.to_le_bytes());
+}
+
+#[test]
+fn test_predefined_label_already_defined() {
+    let label = Label::new();
+    let predefined_labels = HashMap::from([(label, 0)]);
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_start_offset(4)
+        .with_predefined_labels(&predefined_labels)
+        .build();
+    let result = assembler.emit(Instruction::SetPrivate_Label { label });
+    assert!(matches!(result, Err(EmitError::LabelAlreadyDefined(defined)) if defined == label));
+}
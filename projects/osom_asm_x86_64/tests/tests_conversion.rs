@@ -0,0 +1,82 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::assemble_single;
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{FloatType, GPR, Immediate32, Instruction, Memory, RoundingMode, Size, XMM, YMM},
+};
+
+#[rstest]
+#[case(Instruction::Movd_XmmReg { dst: XMM::XMM0, src: GPR::EAX }, &[0x66, 0x0F, 0x6E, 0xC0])]
+#[case(Instruction::Movd_XmmReg { dst: XMM::XMM9, src: GPR::R10 }, &[0x66, 0x4D, 0x0F, 0x6E, 0xCA])]
+#[case(Instruction::Movd_XmmReg { dst: XMM::XMM3, src: GPR::R8D }, &[0x66, 0x41, 0x0F, 0x6E, 0xD8])]
+#[case(Instruction::Movd_RegXmm { dst: GPR::EAX, src: XMM::XMM1 }, &[0x66, 0x0F, 0x7E, 0xC8])]
+#[case(Instruction::Movd_RegXmm { dst: GPR::R11, src: XMM::XMM12 }, &[0x66, 0x4D, 0x0F, 0x7E, 0xE3])]
+#[case(Instruction::Movd_RegXmm { dst: GPR::RDX, src: XMM::XMM2 }, &[0x66, 0x48, 0x0F, 0x7E, 0xD2])]
+fn test_movd(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::CvtIntToFloat_XmmReg { ty: FloatType::ScalarSingle, dst: XMM::XMM0, src: GPR::EAX }, &[0xF3, 0x0F, 0x2A, 0xC0])]
+#[case(Instruction::CvtIntToFloat_XmmReg { ty: FloatType::ScalarDouble, dst: XMM::XMM1, src: GPR::RAX }, &[0xF2, 0x48, 0x0F, 0x2A, 0xC8])]
+#[case(Instruction::CvtIntToFloat_XmmReg { ty: FloatType::ScalarDouble, dst: XMM::XMM10, src: GPR::R9D }, &[0xF2, 0x45, 0x0F, 0x2A, 0xD1])]
+#[case(Instruction::CvtIntToFloat_XmmMem { ty: FloatType::ScalarSingle, dst: XMM::XMM2, src: Memory::based(GPR::RDI, Immediate32::ZERO).unwrap(), size: Size::Bit32 }, &[0xF3, 0x0F, 0x2A, 0x17])]
+#[case(Instruction::CvtIntToFloat_XmmMem { ty: FloatType::ScalarDouble, dst: XMM::XMM3, src: Memory::based(GPR::RSI, Immediate32::new(8)).unwrap(), size: Size::Bit64 }, &[0xF2, 0x48, 0x0F, 0x2A, 0x5E, 0x08])]
+#[case(Instruction::CvtFloatToInt_RegXmm { ty: FloatType::ScalarSingle, truncate: false, dst: GPR::EAX, src: XMM::XMM0 }, &[0xF3, 0x0F, 0x2D, 0xC0])]
+#[case(Instruction::CvtFloatToInt_RegXmm { ty: FloatType::ScalarDouble, truncate: true, dst: GPR::RAX, src: XMM::XMM1 }, &[0xF2, 0x48, 0x0F, 0x2C, 0xC1])]
+#[case(Instruction::CvtFloatToInt_RegXmm { ty: FloatType::ScalarDouble, truncate: false, dst: GPR::R12, src: XMM::XMM15 }, &[0xF2, 0x4D, 0x0F, 0x2D, 0xE7])]
+#[case(Instruction::CvtFloatToInt_RegXmm { ty: FloatType::ScalarSingle, truncate: true, dst: GPR::ECX, src: XMM::XMM8 }, &[0xF3, 0x41, 0x0F, 0x2C, 0xC8])]
+#[case(Instruction::CvtFloatToInt_RegMem { ty: FloatType::ScalarDouble, truncate: true, dst: GPR::EAX, src: Memory::based(GPR::RDI, Immediate32::ZERO).unwrap() }, &[0xF2, 0x0F, 0x2C, 0x07])]
+#[case(Instruction::CvtFloatToInt_RegMem { ty: FloatType::ScalarSingle, truncate: false, dst: GPR::R9, src: Memory::based(GPR::RSP, Immediate32::new(4)).unwrap() }, &[0xF3, 0x4C, 0x0F, 0x2D, 0x4C, 0x24, 0x04])]
+#[case(Instruction::Cvtss2sd_XmmXmm { dst: XMM::XMM0, src: XMM::XMM1 }, &[0xF3, 0x0F, 0x5A, 0xC1])]
+#[case(Instruction::Cvtsd2ss_XmmXmm { dst: XMM::XMM9, src: XMM::XMM3 }, &[0xF2, 0x44, 0x0F, 0x5A, 0xCB])]
+#[case(Instruction::Cvtss2sd_XmmMem { dst: XMM::XMM2, src: Memory::based(GPR::RAX, Immediate32::ZERO).unwrap() }, &[0xF3, 0x0F, 0x5A, 0x10])]
+#[case(Instruction::Cvtsd2ss_XmmMem { dst: XMM::XMM4, src: Memory::based(GPR::R13, Immediate32::ZERO).unwrap() }, &[0xF2, 0x41, 0x0F, 0x5A, 0x65, 0x00])]
+fn test_cvt(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Round_XmmXmm { ty: FloatType::ScalarSingle, mode: RoundingMode::Nearest, dst: XMM::XMM0, src: XMM::XMM1 }, &[0x66, 0x0F, 0x3A, 0x0A, 0xC1, 0x00])]
+#[case(Instruction::Round_XmmXmm { ty: FloatType::ScalarDouble, mode: RoundingMode::Down, dst: XMM::XMM2, src: XMM::XMM11 }, &[0x66, 0x41, 0x0F, 0x3A, 0x0B, 0xD3, 0x01])]
+#[case(Instruction::Round_XmmXmm { ty: FloatType::PackedSingle, mode: RoundingMode::Up, dst: XMM::XMM3, src: XMM::XMM4 }, &[0x66, 0x0F, 0x3A, 0x08, 0xDC, 0x02])]
+#[case(Instruction::Round_XmmXmm { ty: FloatType::PackedDouble, mode: RoundingMode::TowardZero, dst: XMM::XMM12, src: XMM::XMM13 }, &[0x66, 0x45, 0x0F, 0x3A, 0x09, 0xE5, 0x03])]
+#[case(Instruction::Round_XmmMem { ty: FloatType::ScalarDouble, mode: RoundingMode::Current, dst: XMM::XMM1, src: Memory::based(GPR::RBX, Immediate32::ZERO).unwrap() }, &[0x66, 0x0F, 0x3A, 0x0B, 0x0B, 0x04])]
+fn test_round(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Vcvtph2ps_XmmXmm { dst: XMM::XMM0, src: XMM::XMM1 }, &[0xC4, 0xE2, 0x79, 0x13, 0xC1])]
+#[case(Instruction::Vcvtph2ps_XmmMem { dst: XMM::XMM9, src: Memory::based(GPR::RAX, Immediate32::ZERO).unwrap() }, &[0xC4, 0x62, 0x79, 0x13, 0x08])]
+#[case(Instruction::Vcvtph2ps_YmmXmm { dst: YMM::YMM2, src: XMM::XMM12 }, &[0xC4, 0xC2, 0x7D, 0x13, 0xD4])]
+#[case(Instruction::Vcvtph2ps_YmmMem { dst: YMM::YMM3, src: Memory::based(GPR::R8, Immediate32::new(16)).unwrap() }, &[0xC4, 0xC2, 0x7D, 0x13, 0x58, 0x10])]
+#[case(Instruction::Vcvtps2ph_XmmXmm { mode: RoundingMode::Nearest, dst: XMM::XMM1, src: XMM::XMM2 }, &[0xC4, 0xE3, 0x79, 0x1D, 0xD1, 0x00])]
+#[case(Instruction::Vcvtps2ph_MemXmm { mode: RoundingMode::Current, dst: Memory::based(GPR::RDI, Immediate32::ZERO).unwrap(), src: XMM::XMM10 }, &[0xC4, 0x63, 0x79, 0x1D, 0x17, 0x04])]
+#[case(Instruction::Vcvtps2ph_XmmYmm { mode: RoundingMode::TowardZero, dst: XMM::XMM11, src: YMM::YMM3 }, &[0xC4, 0xC3, 0x7D, 0x1D, 0xDB, 0x03])]
+#[case(Instruction::Vcvtps2ph_MemYmm { mode: RoundingMode::Down, dst: Memory::based(GPR::RSP, Immediate32::ZERO).unwrap(), src: YMM::YMM4 }, &[0xC4, 0xE3, 0x7D, 0x1D, 0x24, 0x24, 0x01])]
+fn test_f16c(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Movd_XmmReg { dst: XMM::XMM0, src: GPR::AX })]
+#[case(Instruction::Movd_RegXmm { dst: GPR::AL, src: XMM::XMM0 })]
+#[case(Instruction::CvtIntToFloat_XmmReg { ty: FloatType::ScalarSingle, dst: XMM::XMM0, src: GPR::CX })]
+#[case(Instruction::CvtIntToFloat_XmmReg { ty: FloatType::PackedSingle, dst: XMM::XMM0, src: GPR::EAX })]
+#[case(Instruction::CvtIntToFloat_XmmMem { ty: FloatType::ScalarDouble, dst: XMM::XMM0, src: Memory::based(GPR::RAX, Immediate32::ZERO).unwrap(), size: Size::Bit16 })]
+#[case(Instruction::CvtFloatToInt_RegXmm { ty: FloatType::ScalarDouble, truncate: true, dst: GPR::DX, src: XMM::XMM0 })]
+#[case(Instruction::CvtFloatToInt_RegXmm { ty: FloatType::PackedDouble, truncate: false, dst: GPR::RAX, src: XMM::XMM0 })]
+fn test_invalid_operand_sizes(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
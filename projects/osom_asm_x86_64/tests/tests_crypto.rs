@@ -0,0 +1,146 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::assemble_single;
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{AesKind, GPR, Immediate32, Instruction, Label, Memory, Scale, ShaKind, Size, XMM},
+};
+
+#[rstest]
+#[case(Instruction::Aes_XmmXmm { kind: AesKind::Encrypt, dst: XMM::XMM0, src: XMM::XMM1 }, &[0x66, 0x0F, 0x38, 0xDC, 0xC1])]
+#[case(Instruction::Aes_XmmXmm { kind: AesKind::EncryptLast, dst: XMM::XMM9, src: XMM::XMM2 }, &[0x66, 0x44, 0x0F, 0x38, 0xDD, 0xCA])]
+#[case(Instruction::Aes_XmmXmm { kind: AesKind::Decrypt, dst: XMM::XMM3, src: XMM::XMM12 }, &[0x66, 0x41, 0x0F, 0x38, 0xDE, 0xDC])]
+#[case(Instruction::Aes_XmmXmm { kind: AesKind::DecryptLast, dst: XMM::XMM15, src: XMM::XMM15 }, &[0x66, 0x45, 0x0F, 0x38, 0xDF, 0xFF])]
+#[case(Instruction::Aes_XmmMem { kind: AesKind::Encrypt, dst: XMM::XMM1, src: Memory::based(GPR::RAX, Immediate32::ZERO).unwrap() }, &[0x66, 0x0F, 0x38, 0xDC, 0x08])]
+#[case(Instruction::Aes_XmmMem { kind: AesKind::Decrypt, dst: XMM::XMM10, src: Memory::based(GPR::R12, Immediate32::new(16)).unwrap() }, &[0x66, 0x45, 0x0F, 0x38, 0xDE, 0x54, 0x24, 0x10])]
+#[case(Instruction::AesImc_XmmXmm { dst: XMM::XMM2, src: XMM::XMM3 }, &[0x66, 0x0F, 0x38, 0xDB, 0xD3])]
+#[case(Instruction::AesImc_XmmMem { dst: XMM::XMM8, src: Memory::based(GPR::RSP, Immediate32::ZERO).unwrap() }, &[0x66, 0x44, 0x0F, 0x38, 0xDB, 0x04, 0x24])]
+#[case(Instruction::AesKeyGenAssist_XmmXmmImm { dst: XMM::XMM1, src: XMM::XMM2, imm: Immediate32::new(1) }, &[0x66, 0x0F, 0x3A, 0xDF, 0xCA, 0x01])]
+#[case(Instruction::AesKeyGenAssist_XmmMemImm { dst: XMM::XMM11, src: Memory::based_scaled(GPR::RBX, GPR::RCX, Scale::Scale4, Immediate32::ZERO).unwrap(), imm: Immediate32::new(0x80) }, &[0x66, 0x44, 0x0F, 0x3A, 0xDF, 0x1C, 0x8B, 0x80])]
+fn test_aes(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Pclmulqdq_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM1, imm: Immediate32::new(0x00) }, &[0x66, 0x0F, 0x3A, 0x44, 0xC1, 0x00])]
+#[case(Instruction::Pclmulqdq_XmmXmmImm { dst: XMM::XMM5, src: XMM::XMM13, imm: Immediate32::new(0x11) }, &[0x66, 0x41, 0x0F, 0x3A, 0x44, 0xED, 0x11])]
+#[case(Instruction::Pclmulqdq_XmmMemImm { dst: XMM::XMM2, src: Memory::based(GPR::R13, Immediate32::ZERO).unwrap(), imm: Immediate32::new(0x10) }, &[0x66, 0x41, 0x0F, 0x3A, 0x44, 0x55, 0x00, 0x10])]
+fn test_pclmulqdq(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Sha_XmmXmm { kind: ShaKind::Sha1NextE, dst: XMM::XMM1, src: XMM::XMM2 }, &[0x0F, 0x38, 0xC8, 0xCA])]
+#[case(Instruction::Sha_XmmXmm { kind: ShaKind::Sha1Msg1, dst: XMM::XMM3, src: XMM::XMM4 }, &[0x0F, 0x38, 0xC9, 0xDC])]
+#[case(Instruction::Sha_XmmXmm { kind: ShaKind::Sha1Msg2, dst: XMM::XMM8, src: XMM::XMM9 }, &[0x45, 0x0F, 0x38, 0xCA, 0xC1])]
+#[case(Instruction::Sha_XmmXmm { kind: ShaKind::Sha256Rnds2, dst: XMM::XMM1, src: XMM::XMM2 }, &[0x0F, 0x38, 0xCB, 0xCA])]
+#[case(Instruction::Sha_XmmXmm { kind: ShaKind::Sha256Msg1, dst: XMM::XMM5, src: XMM::XMM6 }, &[0x0F, 0x38, 0xCC, 0xEE])]
+#[case(Instruction::Sha_XmmXmm { kind: ShaKind::Sha256Msg2, dst: XMM::XMM7, src: XMM::XMM14 }, &[0x41, 0x0F, 0x38, 0xCD, 0xFE])]
+#[case(Instruction::Sha_XmmMem { kind: ShaKind::Sha256Msg1, dst: XMM::XMM2, src: Memory::based(GPR::RDI, Immediate32::new(8)).unwrap() }, &[0x0F, 0x38, 0xCC, 0x57, 0x08])]
+#[case(Instruction::Sha1Rnds4_XmmXmmImm { dst: XMM::XMM1, src: XMM::XMM2, imm: Immediate32::new(3) }, &[0x0F, 0x3A, 0xCC, 0xCA, 0x03])]
+#[case(Instruction::Sha1Rnds4_XmmMemImm { dst: XMM::XMM10, src: Memory::based(GPR::RAX, Immediate32::ZERO).unwrap(), imm: Immediate32::ZERO }, &[0x44, 0x0F, 0x3A, 0xCC, 0x10, 0x00])]
+fn test_sha(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(GPR::EAX, GPR::BL, &[0xF2, 0x0F, 0x38, 0xF0, 0xC3])]
+#[case(GPR::EAX, GPR::SIL, &[0xF2, 0x40, 0x0F, 0x38, 0xF0, 0xC6])]
+#[case(GPR::ECX, GPR::DX, &[0x66, 0xF2, 0x0F, 0x38, 0xF1, 0xCA])]
+#[case(GPR::R10D, GPR::R11W, &[0x66, 0xF2, 0x45, 0x0F, 0x38, 0xF1, 0xD3])]
+#[case(GPR::EDX, GPR::ESI, &[0xF2, 0x0F, 0x38, 0xF1, 0xD6])]
+#[case(GPR::RAX, GPR::BL, &[0xF2, 0x48, 0x0F, 0x38, 0xF0, 0xC3])]
+#[case(GPR::RAX, GPR::R15B, &[0xF2, 0x49, 0x0F, 0x38, 0xF0, 0xC7])]
+#[case(GPR::R8, GPR::RCX, &[0xF2, 0x4C, 0x0F, 0x38, 0xF1, 0xC1])]
+fn test_crc32_reg_reg(#[case] dst: GPR, #[case] src: GPR, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), Instruction::Crc32_RegReg { dst, src });
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(GPR::EAX, Memory::based(GPR::RDI, Immediate32::ZERO).unwrap(), Size::Bit8, &[0xF2, 0x0F, 0x38, 0xF0, 0x07])]
+#[case(GPR::EAX, Memory::based(GPR::RDI, Immediate32::new(2)).unwrap(), Size::Bit16, &[0x66, 0xF2, 0x0F, 0x38, 0xF1, 0x47, 0x02])]
+#[case(GPR::R12D, Memory::based_scaled(GPR::R8, GPR::RDX, Scale::Scale8, Immediate32::ZERO).unwrap(), Size::Bit32, &[0xF2, 0x45, 0x0F, 0x38, 0xF1, 0x24, 0xD0])]
+#[case(GPR::RAX, Memory::based(GPR::RSP, Immediate32::new(8)).unwrap(), Size::Bit64, &[0xF2, 0x48, 0x0F, 0x38, 0xF1, 0x44, 0x24, 0x08])]
+#[case(GPR::RAX, Memory::based(GPR::RSI, Immediate32::ZERO).unwrap(), Size::Bit8, &[0xF2, 0x48, 0x0F, 0x38, 0xF0, 0x06])]
+fn test_crc32_reg_mem(#[case] dst: GPR, #[case] src: Memory, #[case] size: Size, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Crc32_RegMem { dst, src, size },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(GPR::AX, GPR::BX)]
+#[case(GPR::AL, GPR::BL)]
+#[case(GPR::EAX, GPR::RBX)]
+#[case(GPR::RAX, GPR::EBX)]
+#[case(GPR::RAX, GPR::BX)]
+fn test_crc32_invalid_sizes(#[case] dst: GPR, #[case] src: GPR) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Crc32_RegReg { dst, src });
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[rstest]
+#[case(GPR::R9D, GPR::AH)]
+#[case(GPR::RAX, GPR::BH)]
+fn test_crc32_high_byte_requires_no_rex(#[case] dst: GPR, #[case] src: GPR) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Crc32_RegReg { dst, src });
+    assert!(matches!(result, Err(EmitError::IncompatibleOperands)));
+}
+
+#[test]
+fn test_crc32_high_byte() {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Crc32_RegReg {
+            dst: GPR::ECX,
+            src: GPR::AH,
+        },
+    );
+    assert_eq_hex!(final_code, &[0xF2, 0x0F, 0x38, 0xF0, 0xCC]);
+}
+
+#[rstest]
+#[case(Instruction::Pclmulqdq_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM1, imm: Immediate32::new(256) })]
+#[case(Instruction::AesKeyGenAssist_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM1, imm: Immediate32::new(-1) })]
+#[case(Instruction::Sha1Rnds4_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM1, imm: Immediate32::new(4) })]
+fn test_immediate_out_of_range(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[test]
+fn test_aes_label_round_key() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let round_key = Label::new();
+    assembler
+        .emit(Instruction::AesKeyGenAssist_XmmMemImm {
+            dst: XMM::XMM0,
+            src: Memory::label(round_key),
+            imm: Immediate32::new(1),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: round_key })
+        .unwrap();
+    assembler.emit([0xAAu8; 16]).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let mut expected = vec![0x66, 0x0F, 0x3A, 0xDF, 0x05, 0x01, 0x00, 0x00, 0x00, 0x01, 0xC3];
+    expected.extend_from_slice(&[0xAA; 16]);
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
@@ -0,0 +1,151 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, X86_64AssemblerBuilder},
+    models::{Alignment, Data, DataDirective, GPR, Instruction, Label, Memory},
+};
+
+fn assemble_data(data: impl Into<DataDirective<'static>>) -> Vec<u8> {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(data.into()).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    final_code
+}
+
+#[rstest]
+#[case(Data::U8(0xAB), &[0xC3, 0xAB])]
+#[case(Data::U16(0x1234), &[0xC3, 0x34, 0x12])]
+#[case(Data::U32(0x1234_5678), &[0xC3, 0x78, 0x56, 0x34, 0x12])]
+#[case(Data::U64(0x0102_0304_0506_0708), &[0xC3, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01])]
+#[case(Data::I8(-1), &[0xC3, 0xFF])]
+#[case(Data::I16(-2), &[0xC3, 0xFE, 0xFF])]
+#[case(Data::I32(-3), &[0xC3, 0xFD, 0xFF, 0xFF, 0xFF])]
+#[case(Data::I64(-4), &[0xC3, 0xFC, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])]
+#[case(Data::F32(1.5), &[0xC3, 0x00, 0x00, 0xC0, 0x3F])]
+#[case(Data::F64(-2.0), &[0xC3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0])]
+#[case(Data::Bytes(&[1, 2, 3]), &[0xC3, 0x01, 0x02, 0x03])]
+#[case(Data::NulTerminatedString("hi"), &[0xC3, b'h', b'i', 0x00])]
+#[case(Data::LengthPrefixedString("hi"), &[0xC3, 0x02, 0x00, 0x00, 0x00, b'h', b'i'])]
+#[case(Data::NulTerminatedString(""), &[0xC3, 0x00])]
+fn test_data_encoding(#[case] data: Data<'static>, #[case] expected: &[u8]) {
+    let final_code = assemble_data(data);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_vector_data() {
+    assert_eq!(
+        Data::u32x4([1, 2, 3, 4]),
+        Data::Vector128([1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0])
+    );
+    assert_eq!(
+        Data::f64x2([1.0, -2.0]),
+        Data::Vector128([0, 0, 0, 0, 0, 0, 0xF0, 0x3F, 0, 0, 0, 0, 0, 0, 0, 0xC0])
+    );
+
+    let Data::Vector256(bytes) = Data::u64x4([1, 2, 3, 4]) else {
+        panic!("Expected 256-bit vector.");
+    };
+    assert_eq_hex!(
+        &bytes[..],
+        &[
+            1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0
+        ]
+    );
+}
+
+#[rstest]
+#[case(Data::U8(1).times(3), &[0xC3, 0x01, 0x01, 0x01])]
+#[case(Data::U16(0x0102).times(2), &[0xC3, 0x02, 0x01, 0x02, 0x01])]
+#[case(Data::NulTerminatedString("a").times(2), &[0xC3, b'a', 0x00, b'a', 0x00])]
+#[case(Data::U64(1).times(0), &[0xC3])]
+fn test_data_times(#[case] directive: DataDirective<'static>, #[case] expected: &[u8]) {
+    let final_code = assemble_data(directive);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Data::U8(1).aligned(), 1)]
+#[case(Data::U16(1).aligned(), 2)]
+#[case(Data::F32(1.0).aligned(), 4)]
+#[case(Data::LengthPrefixedString("abc").aligned(), 4)]
+#[case(Data::I64(1).aligned(), 8)]
+#[case(Data::u32x4([1, 2, 3, 4]).aligned(), 16)]
+#[case(Data::f32x8([1.0; 8]).aligned(), 32)]
+#[case(Data::U8(1).aligned().aligned_to(Alignment::new(64).unwrap()), 64)]
+fn test_data_alignment(#[case] directive: DataDirective<'static>, #[case] expected_offset: usize) {
+    let final_code = assemble_data(directive);
+    assert!(final_code[1..expected_offset].iter().all(|byte| *byte == 0));
+
+    let mut data = Vec::new();
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit(*directive.data()).unwrap();
+    let _ = assembler.assemble(&mut data).unwrap();
+    assert_eq_hex!(&final_code[expected_offset..], &data[..]);
+}
+
+#[test]
+fn test_aligned_data_label() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler.emit(Data::U64(0x1122_3344_5566_7788).aligned()).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 16);
+    assert_eq!(result.public_labels_positions()[&label], 8);
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x48, 0x8B, 0x05, 0x01, 0x00, 0x00, 0x00, 0xC3, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11
+        ]
+    );
+}
+
+#[test]
+fn test_label_before_aligned_data() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler.emit(Data::U32(0xAABB_CCDD).aligned()).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.public_labels_positions()[&label], 8);
+    assert_eq_hex!(
+        final_code,
+        &[0x8B, 0x05, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xDD, 0xCC, 0xBB, 0xAA]
+    );
+}
+
+#[test]
+fn test_aligned_data_uses_base_address() {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_code_mode(CodeMode::Bit16)
+        .with_base_address(0x7C01)
+        .build();
+    assembler.emit(Data::U32(0xAABB_CCDD).aligned()).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0x00, 0x00, 0x00, 0xDD, 0xCC, 0xBB, 0xAA]);
+}
@@ -1,9 +1,12 @@
 #![cfg(target_arch = "x86_64")]
-use std::{collections::HashMap, io::Write};
+use std::{collections::HashMap, io::Write, num::NonZero};
 
 use osom_asm_x86_64::{
-    assembler::X86_64AssemblerBuilder,
-    models::{Condition, GPR, Immediate32, Immediate64, Instruction, Label, Memory},
+    assembler::{Linker, SectionLayout, X86_64AssemblerBuilder},
+    models::{
+        Alignment, Condition, Data, FloatType, FmaKind, FmaOrder, GPR, Immediate32, Immediate64, Instruction, Label,
+        LoopKind, Memory, RoundingMode, ST, Section, Size, X87ArithKind, X87FloatSize, XMM,
+    },
 };
 
 use osom_tools_dev::macros::{convert_to_fn, convert_to_fn_with_offset};
@@ -490,3 +493,747 @@ fn test_jump_backwards_to_predefined_label() {
         assert_eq!(unsafe { fn_ptr(i) }, i);
     }
 }
+
+#[rstest]
+#[case(1.5, 2.0, 0.25)]
+#[case(-3.0, 0.5, 10.0)]
+#[case(0.0, 123.0, -7.5)]
+fn test_fma_polynomial_step(#[case] acc: f64, #[case] x: f64, #[case] coefficient: f64) {
+    if !std::arch::is_x86_feature_detected!("fma") {
+        return;
+    }
+
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let constant = Label::new();
+
+    // xmm0 = xmm1 * xmm0 + [constant]
+    assembler
+        .emit(Instruction::Fma_XmmXmmMem {
+            kind: FmaKind::MulAdd,
+            order: FmaOrder::Order213,
+            ty: FloatType::ScalarDouble,
+            dst: XMM::XMM0,
+            src1: XMM::XMM1,
+            src2: Memory::label(constant),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: constant })
+        .unwrap();
+    assembler.emit(coefficient.to_le_bytes()).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(f64, f64) -> f64);
+    assert_eq!(unsafe { fn_ptr(acc, x) }, acc.mul_add(x, coefficient));
+}
+
+fn crc32c_u64(mut crc: u32, value: u64) -> u32 {
+    for byte in value.to_le_bytes() {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0x82F6_3B78
+            };
+        }
+    }
+    crc
+}
+
+#[rstest]
+#[case(0, 0)]
+#[case(0xFFFF_FFFF, 0x0123_4567_89AB_CDEF)]
+#[case(0x1234_5678, u64::MAX)]
+fn test_crc32_qword(#[case] crc: u32, #[case] value: u64) {
+    if !std::arch::is_x86_feature_detected!("sse4.2") {
+        return;
+    }
+
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::Crc32_RegReg {
+            dst: GPR::RDI,
+            src: GPR::RSI,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::RAX,
+            src: GPR::RDI,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(u64, u64) -> u64);
+    assert_eq!(
+        unsafe { fn_ptr(u64::from(crc), value) },
+        u64::from(crc32c_u64(crc, value))
+    );
+}
+
+#[rstest]
+#[case(0)]
+#[case(-7)]
+#[case(1 << 40)]
+#[case(i64::MIN)]
+fn test_int_float_round_trip(#[case] value: i64) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::CvtIntToFloat_XmmReg {
+            ty: FloatType::ScalarDouble,
+            dst: XMM::XMM0,
+            src: GPR::RDI,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Cvtsd2ss_XmmXmm {
+            dst: XMM::XMM1,
+            src: XMM::XMM0,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Cvtss2sd_XmmXmm {
+            dst: XMM::XMM1,
+            src: XMM::XMM1,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::CvtFloatToInt_RegXmm {
+            ty: FloatType::ScalarDouble,
+            truncate: true,
+            dst: GPR::RAX,
+            src: XMM::XMM1,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(i64) -> i64);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    let expected = f64::from(value as f64 as f32) as i64;
+    assert_eq!(unsafe { fn_ptr(value) }, expected);
+}
+
+#[rstest]
+#[case(RoundingMode::Nearest, 2.5, 2.0)]
+#[case(RoundingMode::Down, -1.5, -2.0)]
+#[case(RoundingMode::Up, 1.25, 2.0)]
+#[case(RoundingMode::TowardZero, -1.75, -1.0)]
+fn test_roundsd(#[case] mode: RoundingMode, #[case] value: f64, #[case] expected: f64) {
+    if !std::arch::is_x86_feature_detected!("sse4.1") {
+        return;
+    }
+
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::Round_XmmXmm {
+            ty: FloatType::ScalarDouble,
+            mode,
+            dst: XMM::XMM0,
+            src: XMM::XMM0,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(f64) -> f64);
+    assert_eq!(unsafe { fn_ptr(value) }, expected);
+}
+
+#[rstest]
+#[case(b"hello, world!\0\0\0", 5)]
+#[case(b"no separators\0\0\0", 16)]
+#[case(b",leading comma\0\0", 0)]
+fn test_pcmpistri_find_comma(#[case] haystack: &[u8; 16], #[case] expected: u64) {
+    if !std::arch::is_x86_feature_detected!("sse4.2") {
+        return;
+    }
+
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let needle = Label::new();
+
+    // Equal any, unsigned bytes, least significant index. The index ends in ECX.
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(needle),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Movd_XmmReg {
+            dst: XMM::XMM0,
+            src: GPR::RAX,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Pcmpistri_XmmMemImm {
+            dst: XMM::XMM0,
+            src: Memory::based(GPR::RDI, Immediate32::ZERO).unwrap(),
+            imm: Immediate32::new(0x00),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::EAX,
+            src: GPR::ECX,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: needle }).unwrap();
+    assembler.emit(u64::from(b',').to_le_bytes()).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(*const u8) -> u64);
+    assert_eq!(unsafe { fn_ptr(haystack.as_ptr()) }, expected);
+}
+
+#[rstest]
+#[case(3.0, 4.0)]
+#[case(0.5, 1.25)]
+fn test_x87_hypot(#[case] x: f64, #[case] y: f64) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let based = |base: GPR| Memory::based(base, Immediate32::ZERO).unwrap();
+    let red_zone = Memory::based(GPR::RSP, Immediate32::new(-8)).unwrap();
+
+    // st(0) = x*x + y*y computed in extended precision, then sqrt.
+    for base in [GPR::RDI, GPR::RSI] {
+        assembler
+            .emit(Instruction::Fld_Mem {
+                size: X87FloatSize::Double,
+                src: based(base),
+            })
+            .unwrap();
+        assembler.emit(Instruction::Fld_St { src: ST::ST0 }).unwrap();
+        assembler
+            .emit(Instruction::X87Arith_StSt0 {
+                kind: X87ArithKind::Mul,
+                dst: ST::ST1,
+                pop: true,
+            })
+            .unwrap();
+    }
+    assembler
+        .emit(Instruction::X87Arith_StSt0 {
+            kind: X87ArithKind::Add,
+            dst: ST::ST1,
+            pop: true,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Fsqrt).unwrap();
+    assembler
+        .emit(Instruction::Fstp_Mem {
+            size: X87FloatSize::Double,
+            dst: red_zone.clone(),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: red_zone,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Movd_XmmReg {
+            dst: XMM::XMM0,
+            src: GPR::RAX,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(*const f64, *const f64) -> f64);
+    assert_eq!(unsafe { fn_ptr(&raw const x, &raw const y) }, x.hypot(y));
+}
+
+#[rstest]
+#[case(7, 2, 3)]
+#[case(-9, 4, -2)]
+fn test_x87_integer_division(#[case] dividend: i64, #[case] divisor: i64, #[case] expected: i64) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let based = |base: GPR| Memory::based(base, Immediate32::ZERO).unwrap();
+    let control_word = Memory::based(GPR::RSP, Immediate32::new(-8)).unwrap();
+    let truncating_control_word = Memory::based(GPR::RSP, Immediate32::new(-16)).unwrap();
+    let result = Memory::based(GPR::RSP, Immediate32::new(-24)).unwrap();
+
+    // Switch rounding control to truncation for fistp and restore it afterwards.
+    assembler
+        .emit(Instruction::Fnstcw_Mem {
+            dst: control_word.clone(),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: control_word.clone(),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Xor_RegImm {
+            dst: GPR::EAX,
+            src: Immediate32::new(0x0C00),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_MemReg {
+            dst: truncating_control_word.clone(),
+            src: GPR::EAX,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Fldcw_Mem {
+            src: truncating_control_word,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Fild_Mem {
+            size: Size::Bit64,
+            src: based(GPR::RDI),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Fild_Mem {
+            size: Size::Bit64,
+            src: based(GPR::RSI),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::X87Arith_StSt0 {
+            kind: X87ArithKind::Div,
+            dst: ST::ST1,
+            pop: true,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Fistp_Mem {
+            size: Size::Bit64,
+            dst: result.clone(),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Fldcw_Mem { src: control_word }).unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: result,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(*const i64, *const i64) -> i64);
+    assert_eq!(unsafe { fn_ptr(&raw const dividend, &raw const divisor) }, expected);
+}
+
+#[rstest]
+#[case(false, 0)]
+#[case(false, 200)]
+#[case(true, 0)]
+#[case(true, 200)]
+fn test_loop_sum(#[case] with_relaxation: bool, #[case] padding: u32) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(with_relaxation).build();
+    let head = Label::new();
+    let done = Label::new();
+
+    assembler
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::RCX,
+            src: GPR::RDI,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Xor_RegReg {
+            dst: GPR::EAX,
+            src: GPR::EAX,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Jrcxz_Label { dst: done }).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: head }).unwrap();
+    assembler
+        .emit(Instruction::Add_RegReg {
+            dst: GPR::RAX,
+            src: GPR::RCX,
+        })
+        .unwrap();
+    if let Some(length) = NonZero::new(padding) {
+        assembler.emit(Instruction::Nop { length }).unwrap();
+    }
+    assembler
+        .emit(Instruction::Loop_Label {
+            kind: LoopKind::Loop,
+            dst: head,
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: done }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(u64) -> u64);
+    for n in 0..50 {
+        assert_eq!(unsafe { fn_ptr(n) }, n * (n + 1) / 2);
+    }
+}
+
+extern "sysv64" fn add_one(value: i64) -> i64 {
+    value + 1
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn test_call_absolute(#[case] tail_call: bool) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let ptr: extern "sysv64" fn(i64) -> i64 = add_one;
+    let address = ptr as usize as u64;
+
+    if tail_call {
+        assembler.emit(Instruction::Jump_Absolute { address }).unwrap();
+    } else {
+        assembler
+            .emit(Instruction::Sub_RegImm {
+                dst: GPR::RSP,
+                src: Immediate32::new(8),
+            })
+            .unwrap();
+        assembler.emit(Instruction::Call_Absolute { address }).unwrap();
+        assembler.emit(Instruction::Call_Absolute { address }).unwrap();
+        assembler
+            .emit(Instruction::Add_RegImm {
+                dst: GPR::RSP,
+                src: Immediate32::new(8),
+            })
+            .unwrap();
+        assembler.emit(Instruction::Ret).unwrap();
+    }
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(i64) -> i64);
+    let expected_increment = if tail_call { 1 } else { 2 };
+    for value in [-5, 0, 41] {
+        assert_eq!(unsafe { fn_ptr(value) }, value + expected_increment);
+    }
+}
+
+#[test]
+fn test_load_aligned_data() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Cvtss2sd_XmmMem {
+            dst: XMM::XMM0,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Data::F32(1.5).aligned()).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn() -> f64);
+    assert_eq!(unsafe { fn_ptr() }, 1.5);
+}
+
+#[test]
+fn test_constant_pool() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let mask = assembler.constant_u64(0xFFFF_0000_FFFF_0000);
+    let increment = assembler.constant(Data::I64(3));
+    assembler
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::RAX,
+            src: GPR::RDI,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Xor_RegMem {
+            dst: GPR::RAX,
+            src: mask.clone(),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Add_RegMem {
+            dst: GPR::RAX,
+            src: increment,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Xor_RegMem {
+            dst: GPR::RAX,
+            src: mask,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(u64) -> u64);
+    for value in [0u64, 5, 0x1234_5678_9ABC_DEF0] {
+        let expected = ((value ^ 0xFFFF_0000_FFFF_0000).wrapping_add(3)) ^ 0xFFFF_0000_FFFF_0000;
+        assert_eq!(unsafe { fn_ptr(value) }, expected);
+    }
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn test_jump_table(#[case] with_relaxation: bool) {
+    const RESULTS: [i32; 4] = [10, 20, 30, 40];
+
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(with_relaxation).build();
+    let table = Label::new();
+    let end = Label::new();
+    let cases: Vec<Label> = RESULTS.iter().map(|_| Label::new()).collect();
+
+    assembler
+        .emit(Instruction::JumpTable_Dispatch {
+            table,
+            index: GPR::RDI,
+            base: GPR::RCX,
+        })
+        .unwrap();
+    for (case, result) in cases.iter().zip(RESULTS) {
+        assembler.emit(Instruction::SetPrivate_Label { label: *case }).unwrap();
+        assembler
+            .emit(Instruction::Mov_RegImm {
+                dst: GPR::EAX,
+                src: Immediate32::new(result),
+            })
+            .unwrap();
+        assembler.emit(Instruction::Jump_Label { dst: end }).unwrap();
+    }
+    assembler.emit(Instruction::SetPrivate_Label { label: end }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: table }).unwrap();
+    for case in cases.iter().rev() {
+        assembler
+            .emit(Instruction::LabelDifference32 {
+                target: *case,
+                base: table,
+            })
+            .unwrap();
+    }
+
+    let mut stream = RegionStream::new();
+    let _ = assembler.assemble(&mut stream).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(u64) -> i32);
+    for (index, result) in RESULTS.iter().rev().enumerate() {
+        assert_eq!(unsafe { fn_ptr(index as u64) }, *result);
+    }
+}
+
+#[test]
+fn test_apply_relocations() {
+    const MAGIC: u64 = 0x0123_4567_89AB_CDEF;
+
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let data = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegLabel {
+            dst: GPR::RCX,
+            label: data,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::based(GPR::RCX, Immediate32::ZERO).unwrap(),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: data }).unwrap();
+    assembler.emit(Data::U64(MAGIC).aligned()).unwrap();
+
+    let mut stream = RegionStream::new();
+    let result = assembler.assemble(&mut stream).unwrap();
+    let base_address = stream.as_slice().as_ptr() as u64;
+    result.apply_relocations(stream.as_mut_slice(), base_address).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn() -> u64);
+    assert_eq!(unsafe { fn_ptr() }, MAGIC);
+}
+
+#[test]
+fn test_base_address_of_region() {
+    let mut stream = RegionStream::new();
+    let base_address = unsafe { osom_tools_dev::traits::Pointerable::as_ptr(&stream) } as u64;
+    let mut assembler = X86_64AssemblerBuilder::new().with_base_address(base_address).build();
+    let label = Label::new();
+    let ptr: extern "sysv64" fn(i64) -> i64 = add_one;
+    assembler
+        .emit(Instruction::Sub_RegImm {
+            dst: GPR::RSP,
+            src: Immediate32::new(8),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Call_Absolute {
+            address: ptr as usize as u64,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Add_RegImm {
+            dst: GPR::RSP,
+            src: Immediate32::new(8),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegLabel { dst: GPR::RCX, label })
+        .unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+    assembler
+        .emit(Instruction::Add_RegReg {
+            dst: GPR::RAX,
+            src: GPR::RCX,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let result = assembler.assemble(&mut stream).unwrap();
+    let label_address = result.public_label_address(&label).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn(i64) -> i64);
+    assert_eq!(unsafe { fn_ptr(5) }, 6 + label_address as i64);
+}
+
+#[test]
+fn test_sections() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let counter = Label::new();
+    let step = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(counter),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::SwitchSection {
+            section: Section::RODATA,
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: step }).unwrap();
+    assembler.emit(Data::U64(5)).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::TEXT })
+        .unwrap();
+    assembler
+        .emit(Instruction::Add_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(step),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_MemReg {
+            dst: Memory::label(counter),
+            src: GPR::RAX,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::DATA })
+        .unwrap();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: counter })
+        .unwrap();
+    assembler.emit(Data::U64(10)).unwrap();
+
+    let layout = SectionLayout::new()
+        .with_section(Section::DATA)
+        .with_section(Section::RODATA)
+        .with_aligned_section(Section::TEXT, Alignment::new(16).unwrap());
+    let mut stream = RegionStream::new();
+    let result = assembler.assemble_with_layout(&layout, &mut stream).unwrap();
+    let offset = result.section(Section::TEXT).unwrap().offset();
+    assert_eq!(offset, 16);
+    let fn_ptr = convert_to_fn_with_offset!("sysv64", stream, offset, fn() -> u64);
+    assert_eq!(unsafe { fn_ptr() }, 15);
+    assert_eq!(unsafe { fn_ptr() }, 20);
+}
+
+#[test]
+fn test_linker() {
+    let helper = Label::new();
+    let mut first = X86_64AssemblerBuilder::new().build();
+    first.declare_external(helper);
+    first.emit(Instruction::Call_Label { dst: helper }).unwrap();
+    first
+        .emit(Instruction::Add_RegImm {
+            dst: GPR::RAX,
+            src: Immediate32::new(2),
+        })
+        .unwrap();
+    first.emit(Instruction::Ret).unwrap();
+
+    let mut second = X86_64AssemblerBuilder::new().build();
+    second.emit(Instruction::SetPublic_Label { label: helper }).unwrap();
+    second
+        .emit(Instruction::Mov_RegImm {
+            dst: GPR::RAX,
+            src: Immediate32::new(40),
+        })
+        .unwrap();
+    second.emit(Instruction::Ret).unwrap();
+
+    let mut linker = Linker::new().with_alignment(Alignment::new(16).unwrap());
+    linker.add(first).unwrap();
+    linker.add(second).unwrap();
+
+    let mut stream = RegionStream::new();
+    let _ = linker.link(&mut stream).unwrap();
+    let fn_ptr = convert_to_fn!("sysv64", stream, fn() -> u64);
+    assert_eq!(unsafe { fn_ptr() }, 42);
+}
+
+#[test]
+fn test_continuation() {
+    let entry = Label::new();
+    let mut first = X86_64AssemblerBuilder::new().build();
+    first.emit(Instruction::SetPublic_Label { label: entry }).unwrap();
+    first
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::EAX,
+            src: GPR::EDI,
+        })
+        .unwrap();
+    first.emit(Instruction::Ret).unwrap();
+
+    let mut stream = RegionStream::new();
+    let first_result = first.assemble(&mut stream).unwrap();
+
+    let continuation = Label::new();
+    let mut second = X86_64AssemblerBuilder::new()
+        .with_start_offset(first_result.emitted_bytes())
+        .with_predefined_labels(first_result.public_labels_positions())
+        .build();
+    second
+        .emit(Instruction::SetPublic_Label { label: continuation })
+        .unwrap();
+    second
+        .emit(Instruction::Add_RegImm {
+            dst: GPR::EDI,
+            src: Immediate32::new(1),
+        })
+        .unwrap();
+    second.emit(Instruction::Jump_Label { dst: entry }).unwrap();
+
+    let second_result = second.assemble(&mut stream).unwrap();
+    let offset = second_result.public_labels_positions()[&continuation];
+    let fn_ptr = convert_to_fn_with_offset!("sysv64", stream, offset, fn(i32) -> i32);
+    assert_eq!(unsafe { fn_ptr(41) }, 42);
+}
@@ -0,0 +1,146 @@
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, RelocationKind, X86_64AssemblerBuilder},
+    models::{Condition, GPR, Instruction, Label},
+};
+
+#[test]
+fn test_call_external() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let external = Label::new();
+    assembler.declare_external(external);
+    assembler.emit(Instruction::Call_Label { dst: external }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xE8, 0x00, 0x00, 0x00, 0x00, 0xC3]);
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].offset(), 1);
+    assert_eq!(relocations[0].kind(), RelocationKind::Relative32);
+    assert_eq!(relocations[0].target(), external);
+    assert_eq!(relocations[0].addend(), -4);
+    assert!(relocations[0].is_external());
+}
+
+#[test]
+fn test_jumps_to_external_are_long() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(true).build();
+    let external = Label::new();
+    let local = Label::new();
+    assembler.declare_external(external);
+    assembler.emit(Instruction::SetPrivate_Label { label: local }).unwrap();
+    assembler
+        .emit(Instruction::CondJump_Label {
+            condition: Condition::Equal,
+            dst: external,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: external }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: local }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x0F, 0x84, 0x00, 0x00, 0x00, 0x00, 0xE9, 0x00, 0x00, 0x00, 0x00, 0xEB, 0xF3
+        ]
+    );
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 2);
+    assert_eq!(relocations[0].offset(), 2);
+    assert_eq!(relocations[0].kind(), RelocationKind::Relative32);
+    assert_eq!(relocations[0].addend(), -4);
+    assert_eq!(relocations[1].offset(), 7);
+    assert_eq!(relocations[1].kind(), RelocationKind::Relative32);
+    assert_eq!(relocations[1].addend(), -4);
+}
+
+#[test]
+fn test_undefined_labels_as_external() {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_undefined_labels_as_external(true)
+        .build();
+    let function = Label::new();
+    let table = Label::new();
+    assembler.emit(Instruction::Call_Label { dst: function }).unwrap();
+    assembler
+        .emit(Instruction::Mov_RegLabel {
+            dst: GPR::RAX,
+            label: table,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xE8, 0x00, 0x00, 0x00, 0x00, 0x48, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC3
+        ]
+    );
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 2);
+    assert_eq!(relocations[0].target(), function);
+    assert_eq!(relocations[0].kind(), RelocationKind::Relative32);
+    assert_eq!(relocations[1].offset(), 7);
+    assert_eq!(relocations[1].kind(), RelocationKind::Absolute64);
+    assert_eq!(relocations[1].target(), table);
+    assert_eq!(relocations[1].addend(), 0);
+    assert!(relocations[1].is_external());
+
+    let mut moved_code = final_code.clone();
+    result.apply_relocations(&mut moved_code, 0x1000).unwrap();
+    assert_eq_hex!(moved_code, final_code);
+}
+
+#[test]
+fn test_set_label_is_not_external() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.declare_external(label);
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xEB, 0x00]);
+    assert!(result.relocations().is_empty());
+}
+
+#[test]
+fn test_undeclared_label_not_set() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let external = Label::new();
+    let missing = Label::new();
+    assembler.declare_external(external);
+    assembler.emit(Instruction::Call_Label { dst: external }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: missing }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::LabelNotSet(label)) if label == missing));
+}
+
+#[test]
+fn test_external_label_difference() {
+    let external = Label::new();
+    let local = Label::new();
+    for (target, base) in [(external, local), (local, external)] {
+        let mut assembler = X86_64AssemblerBuilder::new().build();
+        assembler.declare_external(external);
+        assembler.emit(Instruction::SetPrivate_Label { label: local }).unwrap();
+        assembler.emit(Instruction::LabelDifference32 { target, base }).unwrap();
+
+        let mut final_code = Vec::new();
+        let result = assembler.assemble(&mut final_code);
+        assert!(matches!(result, Err(AssembleError::ExternalLabelDifference(label)) if label == external));
+    }
+}
@@ -0,0 +1,165 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{FloatType, FmaKind, FmaOrder, GPR, Immediate32, Instruction, Label, Memory, Scale, XMM, YMM},
+};
+
+#[rstest]
+#[case(FmaKind::MulAdd, FmaOrder::Order132, FloatType::PackedSingle, XMM::XMM0, XMM::XMM1, XMM::XMM2, &[0xC4, 0xE2, 0x71, 0x98, 0xC2])]
+#[case(FmaKind::MulAdd, FmaOrder::Order213, FloatType::PackedDouble, XMM::XMM3, XMM::XMM4, XMM::XMM5, &[0xC4, 0xE2, 0xD9, 0xA8, 0xDD])]
+#[case(FmaKind::MulSub, FmaOrder::Order231, FloatType::ScalarSingle, XMM::XMM8, XMM::XMM9, XMM::XMM10, &[0xC4, 0x42, 0x31, 0xBB, 0xC2])]
+#[case(FmaKind::NegMulAdd, FmaOrder::Order132, FloatType::ScalarDouble, XMM::XMM15, XMM::XMM0, XMM::XMM7, &[0xC4, 0x62, 0xF9, 0x9D, 0xFF])]
+fn test_fma_xmm(
+    #[case] kind: FmaKind,
+    #[case] order: FmaOrder,
+    #[case] ty: FloatType,
+    #[case] dst: XMM,
+    #[case] src1: XMM,
+    #[case] src2: XMM,
+    #[case] expected: &[u8],
+) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::Fma_XmmXmmXmm {
+            kind,
+            order,
+            ty,
+            dst,
+            src1,
+            src2,
+        })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
+
+#[rstest]
+#[case(FmaKind::NegMulSub, FmaOrder::Order231, FloatType::PackedSingle, YMM::YMM1, YMM::YMM2, YMM::YMM3, &[0xC4, 0xE2, 0x6D, 0xBE, 0xCB])]
+#[case(FmaKind::MulAdd, FmaOrder::Order213, FloatType::PackedDouble, YMM::YMM12, YMM::YMM13, YMM::YMM14, &[0xC4, 0x42, 0x95, 0xA8, 0xE6])]
+fn test_fma_ymm(
+    #[case] kind: FmaKind,
+    #[case] order: FmaOrder,
+    #[case] ty: FloatType,
+    #[case] dst: YMM,
+    #[case] src1: YMM,
+    #[case] src2: YMM,
+    #[case] expected: &[u8],
+) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::Fma_YmmYmmYmm {
+            kind,
+            order,
+            ty,
+            dst,
+            src1,
+            src2,
+        })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
+
+#[rstest]
+#[case(FmaKind::MulAdd, FmaOrder::Order231, FloatType::PackedSingle, XMM::XMM0, XMM::XMM1, Memory::based(GPR::RAX, Immediate32::ZERO).unwrap(), &[0xC4, 0xE2, 0x71, 0xB8, 0x00])]
+#[case(FmaKind::NegMulSub, FmaOrder::Order213, FloatType::ScalarDouble, XMM::XMM2, XMM::XMM3, Memory::based(GPR::R12, Immediate32::new(8)).unwrap(), &[0xC4, 0xC2, 0xE1, 0xAF, 0x54, 0x24, 0x08])]
+#[case(FmaKind::NegMulAdd, FmaOrder::Order231, FloatType::ScalarSingle, XMM::XMM9, XMM::XMM10, Memory::based(GPR::R13, Immediate32::ZERO).unwrap(), &[0xC4, 0x42, 0x29, 0xBD, 0x4D, 0x00])]
+#[case(FmaKind::MulAdd, FmaOrder::Order231, FloatType::PackedSingle, XMM::XMM0, XMM::XMM1, Memory::scaled(GPR::R9, Scale::Scale8, Immediate32::new(16)).unwrap(), &[0xC4, 0xA2, 0x71, 0xB8, 0x04, 0xCD, 0x10, 0x00, 0x00, 0x00])]
+fn test_fma_xmm_mem(
+    #[case] kind: FmaKind,
+    #[case] order: FmaOrder,
+    #[case] ty: FloatType,
+    #[case] dst: XMM,
+    #[case] src1: XMM,
+    #[case] src2: Memory,
+    #[case] expected: &[u8],
+) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::Fma_XmmXmmMem {
+            kind,
+            order,
+            ty,
+            dst,
+            src1,
+            src2,
+        })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
+
+#[test]
+fn test_fma_ymm_mem() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::Fma_YmmYmmMem {
+            kind: FmaKind::MulSub,
+            order: FmaOrder::Order132,
+            ty: FloatType::PackedDouble,
+            dst: YMM::YMM4,
+            src1: YMM::YMM5,
+            src2: Memory::based_scaled(GPR::RBP, GPR::RCX, Scale::Scale4, Immediate32::new(0x100)).unwrap(),
+        })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let expected = &[0xC4, 0xE2, 0xD5, 0x9A, 0xA4, 0x8D, 0x00, 0x01, 0x00, 0x00];
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
+
+#[test]
+fn test_fma_label_constant() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let constant = Label::new();
+    assembler
+        .emit(Instruction::Fma_XmmXmmMem {
+            kind: FmaKind::MulAdd,
+            order: FmaOrder::Order231,
+            ty: FloatType::ScalarDouble,
+            dst: XMM::XMM0,
+            src1: XMM::XMM1,
+            src2: Memory::label(constant),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: constant })
+        .unwrap();
+    assembler.emit(1.5f64.to_le_bytes()).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let expected = &[
+        0xC4, 0xE2, 0xF1, 0xB9, 0x05, 0x01, 0x00, 0x00, 0x00, 0xC3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF8, 0x3F,
+    ];
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
+
+#[test]
+fn test_fma_ymm_rejects_scalar() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Fma_YmmYmmYmm {
+        kind: FmaKind::MulAdd,
+        order: FmaOrder::Order132,
+        ty: FloatType::ScalarSingle,
+        dst: YMM::YMM0,
+        src1: YMM::YMM1,
+        src2: YMM::YMM2,
+    });
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
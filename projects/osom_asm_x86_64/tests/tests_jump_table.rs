@@ -0,0 +1,150 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, CodeMode, EmitError, X86_64AssemblerBuilder},
+    models::{GPR, Instruction, Label},
+};
+
+#[rstest]
+#[case(GPR::RAX, GPR::RBX, &[0x48, 0x8D, 0x1D, 0x0E, 0x00, 0x00, 0x00, 0x48, 0x63, 0x04, 0x83, 0x48, 0x01, 0xC3, 0xFF, 0xE3])]
+#[case(GPR::R10, GPR::R11, &[0x4C, 0x8D, 0x1D, 0x0F, 0x00, 0x00, 0x00, 0x4F, 0x63, 0x14, 0x93, 0x4D, 0x01, 0xD3, 0x41, 0xFF, 0xE3])]
+#[case(GPR::RAX, GPR::RBP, &[0x48, 0x8D, 0x2D, 0x0F, 0x00, 0x00, 0x00, 0x48, 0x63, 0x44, 0x85, 0x00, 0x48, 0x01, 0xC5, 0xFF, 0xE5])]
+fn test_jump_table_dispatch_encoding(#[case] index: GPR, #[case] base: GPR, #[case] expected: &[u8]) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let table = Label::new();
+    assembler
+        .emit(Instruction::JumpTable_Dispatch { table, index, base })
+        .unwrap();
+    assembler.emit([0xCC; 5]).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: table }).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(&final_code[..expected.len()], expected);
+}
+
+#[test]
+fn test_label_difference() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let first = Label::new();
+    let second = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label: first }).unwrap();
+    assembler.emit([0xC3; 3]).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: second }).unwrap();
+    assembler
+        .emit(Instruction::LabelDifference32 {
+            target: second,
+            base: first,
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::LabelDifference32 {
+            target: first,
+            base: second,
+        })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 11);
+    assert_eq_hex!(
+        final_code,
+        &[0xC3, 0xC3, 0xC3, 0x03, 0x00, 0x00, 0x00, 0xFD, 0xFF, 0xFF, 0xFF]
+    );
+}
+
+#[test]
+fn test_label_difference_after_relaxation() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let start = Label::new();
+    let end = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label: start }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: end }).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: end }).unwrap();
+    assembler
+        .emit(Instruction::LabelDifference32 {
+            target: end,
+            base: start,
+        })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xEB, 0x00, 0x02, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn test_label_difference_base_not_set() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let target = Label::new();
+    let base = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label: target }).unwrap();
+    assembler.emit(Instruction::LabelDifference32 { target, base }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::LabelNotSet(label)) if label == base));
+}
+
+#[rstest]
+#[case(CodeMode::Bit64, 0, &[0xC3, 0x01, 0x00, 0x00, 0x00])]
+#[case(CodeMode::Bit32, 0x40_0000, &[0xC3, 0x01, 0x00, 0x40, 0x00])]
+#[case(CodeMode::Bit16, 0x7C00, &[0xC3, 0x01, 0x7C, 0x00, 0x00])]
+fn test_label_address(#[case] mode: CodeMode, #[case] base_address: u64, #[case] expected: &[u8]) {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_code_mode(mode)
+        .with_base_address(base_address)
+        .build();
+    let label = Label::new();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::LabelAddress32 { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_label_address_out_of_range() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_base_address(0x1_0000_0000).build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::LabelAddress32 { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::AddressOutOfRange(out_of_range)) if out_of_range == label));
+}
+
+#[rstest]
+#[case(GPR::EAX, GPR::RBX, EmitError::OperandSizeMismatch)]
+#[case(GPR::RAX, GPR::BX, EmitError::OperandSizeMismatch)]
+#[case(GPR::RAX, GPR::RAX, EmitError::IncompatibleOperands)]
+#[case(GPR::RSP, GPR::RAX, EmitError::IncompatibleOperands)]
+fn test_jump_table_dispatch_invalid_operands(#[case] index: GPR, #[case] base: GPR, #[case] expected: EmitError) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::JumpTable_Dispatch {
+        table: Label::new(),
+        index,
+        base,
+    });
+    assert_eq!(
+        std::mem::discriminant(&result.unwrap_err()),
+        std::mem::discriminant(&expected)
+    );
+}
+
+#[rstest]
+#[case(CodeMode::Bit32)]
+#[case(CodeMode::Bit16)]
+fn test_jump_table_dispatch_not_supported(#[case] mode: CodeMode) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(mode).build();
+    let result = assembler.emit(Instruction::JumpTable_Dispatch {
+        table: Label::new(),
+        index: GPR::RAX,
+        base: GPR::RBX,
+    });
+    assert!(matches!(result, Err(EmitError::NotSupportedInCodeMode)));
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{Condition, Instruction, Label, LabelAllocator},
+};
+
+#[test]
+fn test_label_allocator() {
+    let mut allocator = LabelAllocator::new();
+    let first = allocator.allocate();
+    let second = allocator.allocate();
+    assert_ne!(first, second);
+    assert_eq!(allocator.allocated(), 2);
+
+    let mut other = LabelAllocator::new();
+    assert_eq!(other.allocate(), first);
+    assert_ne!(Label::new(), first);
+}
+
+#[test]
+fn test_assembler_labels() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let start = assembler.new_label();
+    let end = assembler.new_label();
+    let global = Label::new();
+    assert_eq!(assembler.label_allocator().allocated(), 2);
+
+    assembler.emit(Instruction::SetPrivate_Label { label: start }).unwrap();
+    assembler
+        .emit(Instruction::CondJump_Label {
+            condition: Condition::Equal,
+            dst: end,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: global }).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label: global }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: start }).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label: end }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0x74, 0x04, 0xEB, 0x00, 0xEB, 0xFA, 0xC3]);
+    assert_eq!(result.public_labels_positions().get(&global), Some(&4));
+    assert_eq!(result.public_labels_positions().get(&end), Some(&6));
+}
+
+#[test]
+fn test_allocated_label_already_defined() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = assembler.new_label();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    let result = assembler.emit(Instruction::SetPrivate_Label { label });
+    assert!(matches!(result, Err(EmitError::LabelAlreadyDefined(defined)) if defined == label));
+}
+
+#[test]
+fn test_shared_label_allocator() {
+    let mut first = X86_64AssemblerBuilder::new().build();
+    let entry = first.new_label();
+    first.emit(Instruction::SetPublic_Label { label: entry }).unwrap();
+    first.emit(Instruction::Ret).unwrap();
+    let allocator = first.label_allocator().clone();
+
+    let mut final_code = Vec::new();
+    let result = first.assemble(&mut final_code).unwrap();
+    let entry_position = result.public_labels_positions()[&entry];
+
+    let predefined_labels = HashMap::from([(entry, entry_position - final_code.len() as i32)]);
+    let mut second = X86_64AssemblerBuilder::new()
+        .with_label_allocator(allocator)
+        .with_predefined_labels(&predefined_labels)
+        .build();
+    let local = second.new_label();
+    assert_ne!(local, entry);
+    second.emit(Instruction::SetPrivate_Label { label: local }).unwrap();
+    second.emit(Instruction::Jump_Label { dst: entry }).unwrap();
+    second.emit(Instruction::Jump_Label { dst: local }).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = second.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xEB, 0xFD, 0xEB, 0xFC]);
+}
+
+#[test]
+fn test_labels_set_out_of_allocation_order() {
+    let mut allocator = LabelAllocator::new();
+    for _ in 0..1000 {
+        let _ = allocator.allocate();
+    }
+    let mut assembler = X86_64AssemblerBuilder::new().with_label_allocator(allocator).build();
+    let early = assembler.new_label();
+    let late = assembler.new_label();
+    assembler.emit(Instruction::SetPrivate_Label { label: late }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: early }).unwrap();
+    assembler.emit(Instruction::SetPublic_Label { label: early }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: late }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xEB, 0x00, 0xEB, 0xFC]);
+    assert_eq!(result.public_labels_positions().get(&early), Some(&2));
+}
@@ -0,0 +1,202 @@
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, Linker, RelocationKind, X86_64AssemblerBuilder},
+    models::{Alignment, Data, GPR, Instruction, Label, Memory, Symbol},
+};
+
+#[test]
+fn test_link_call() {
+    let main = Label::new();
+    let helper = Label::new();
+
+    let mut first = X86_64AssemblerBuilder::new().build();
+    first.declare_external(helper);
+    first.emit(Symbol::function("main", main)).unwrap();
+    first.emit(Instruction::Call_Label { dst: helper }).unwrap();
+    first.emit(Instruction::Ret).unwrap();
+
+    let mut second = X86_64AssemblerBuilder::new().build();
+    second.emit(Symbol::function("helper", helper)).unwrap();
+    second.emit(Instruction::Ret).unwrap();
+
+    let mut linker = Linker::new().with_alignment(Alignment::new(16).unwrap());
+    linker.add(first).unwrap();
+    linker.add(second).unwrap();
+    assert_eq!(linker.modules_count(), 2);
+
+    let mut final_code = Vec::new();
+    let result = linker.link(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xE8, 0x0B, 0x00, 0x00, 0x00, 0xC3, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xC3
+        ]
+    );
+    assert_eq!(result.emitted_bytes(), 17);
+    assert!(result.relocations().is_empty());
+    assert_eq!(result.public_labels_positions().get(&main), Some(&0));
+    assert_eq!(result.public_labels_positions().get(&helper), Some(&16));
+    assert_eq!(result.symbol("main").unwrap().offset(), 0);
+    assert_eq!(result.symbol("helper").unwrap().offset(), 16);
+}
+
+#[test]
+fn test_link_data() {
+    let data = Label::new();
+
+    let mut first = X86_64AssemblerBuilder::new().build();
+    first.declare_external(data);
+    first
+        .emit(Instruction::Mov_RegLabel {
+            dst: GPR::RAX,
+            label: data,
+        })
+        .unwrap();
+    first
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(data),
+        })
+        .unwrap();
+    first.emit(Instruction::Ret).unwrap();
+
+    let mut second = X86_64AssemblerBuilder::new().build();
+    second.emit(Instruction::SetPublic_Label { label: data }).unwrap();
+    second.emit(Data::U64(0x1122_3344_5566_7788)).unwrap();
+
+    let mut linker = Linker::new().with_base_address(0x1000);
+    linker.add(first).unwrap();
+    linker.add(second).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = linker.link(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x48, 0xB8, 0x12, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x48, 0x8B, 0x05, 0x01, 0x00, 0x00, 0x00, 0xC3,
+            0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11
+        ]
+    );
+    assert_eq!(result.base_address(), Some(0x1000));
+    assert_eq!(result.public_label_address(&data), Some(0x1012));
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].offset(), 2);
+    assert_eq!(relocations[0].kind(), RelocationKind::Absolute64);
+    assert_eq!(relocations[0].target(), data);
+    assert_eq!(relocations[0].addend(), 0x12);
+    assert!(!relocations[0].is_external());
+}
+
+#[test]
+fn test_link_internal_relocations() {
+    let mut first = X86_64AssemblerBuilder::new().build();
+    first.emit(Instruction::Ret).unwrap();
+
+    let mut second = X86_64AssemblerBuilder::new().build();
+    let local = Label::new();
+    second
+        .emit(Instruction::Mov_RegLabel {
+            dst: GPR::RAX,
+            label: local,
+        })
+        .unwrap();
+    second.emit(Instruction::SetPrivate_Label { label: local }).unwrap();
+
+    let mut linker = Linker::new().with_base_address(0x2000);
+    linker.add(first).unwrap();
+    linker.add(second).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = linker.link(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[0xC3, 0x48, 0xB8, 0x0B, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].offset(), 3);
+    assert_eq!(relocations[0].addend(), 11);
+}
+
+#[test]
+fn test_link_unresolved_external() {
+    let main = Label::new();
+    let missing = Label::new();
+
+    let mut first = X86_64AssemblerBuilder::new()
+        .with_undefined_labels_as_external(true)
+        .build();
+    first.emit(Instruction::SetPublic_Label { label: main }).unwrap();
+    first.emit(Instruction::Jump_Label { dst: missing }).unwrap();
+
+    let mut second = X86_64AssemblerBuilder::new().build();
+    second.declare_external(main);
+    second.emit(Instruction::Call_Label { dst: main }).unwrap();
+
+    let mut linker = Linker::new();
+    linker.add(first).unwrap();
+    linker.add(second).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = linker.link(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[0xE9, 0x00, 0x00, 0x00, 0x00, 0xE8, 0xF6, 0xFF, 0xFF, 0xFF]
+    );
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].offset(), 1);
+    assert_eq!(relocations[0].kind(), RelocationKind::Relative32);
+    assert_eq!(relocations[0].target(), missing);
+    assert_eq!(relocations[0].addend(), -4);
+    assert!(relocations[0].is_external());
+}
+
+#[test]
+fn test_link_duplicate_label() {
+    let label = Label::new();
+    let mut linker = Linker::new();
+    for _ in 0..2 {
+        let mut assembler = X86_64AssemblerBuilder::new().build();
+        assembler.emit(Instruction::SetPublic_Label { label }).unwrap();
+        assembler.emit(Instruction::Ret).unwrap();
+        linker.add(assembler).unwrap();
+    }
+
+    let mut final_code = Vec::new();
+    let result = linker.link(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::LabelAlreadyDefined(defined)) if defined == label));
+    assert!(final_code.is_empty());
+}
+
+#[test]
+fn test_link_duplicate_symbol() {
+    let mut linker = Linker::new();
+    for _ in 0..2 {
+        let mut assembler = X86_64AssemblerBuilder::new().build();
+        assembler.emit(Symbol::function("main", Label::new())).unwrap();
+        assembler.emit(Instruction::Ret).unwrap();
+        linker.add(assembler).unwrap();
+    }
+
+    let mut final_code = Vec::new();
+    let result = linker.link(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::SymbolAlreadyDefined(name)) if name == "main"));
+}
+
+#[test]
+fn test_link_module_with_base_address() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_base_address(0x1000_0000).build();
+    assembler
+        .emit(Instruction::Call_Absolute { address: 0x1000_1000 })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut linker = Linker::new();
+    let result = linker.add(assembler);
+    assert!(matches!(result, Err(AssembleError::ModuleWithBaseAddress)));
+    assert_eq!(linker.modules_count(), 0);
+}
@@ -0,0 +1,99 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::assemble;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, X86_64AssemblerBuilder},
+    models::{Instruction, Label, LoopKind},
+};
+
+fn counter_jump(kind: Option<LoopKind>, dst: Label) -> Instruction {
+    match kind {
+        Some(kind) => Instruction::Loop_Label { kind, dst },
+        None => Instruction::Jrcxz_Label { dst },
+    }
+}
+
+#[rstest]
+#[case(Some(LoopKind::Loop), true, &[0xE2, 0xFE])]
+#[case(Some(LoopKind::LoopEqual), true, &[0xE1, 0xFE])]
+#[case(Some(LoopKind::LoopNotEqual), true, &[0xE0, 0xFE])]
+#[case(None, true, &[0xE3, 0xFE])]
+#[case(Some(LoopKind::Loop), false, &[0xE2, 0x02, 0xEB, 0x05, 0xE9, 0xF7, 0xFF, 0xFF, 0xFF])]
+#[case(Some(LoopKind::LoopEqual), false, &[0xE1, 0x02, 0xEB, 0x05, 0xE9, 0xF7, 0xFF, 0xFF, 0xFF])]
+#[case(Some(LoopKind::LoopNotEqual), false, &[0xE0, 0x02, 0xEB, 0x05, 0xE9, 0xF7, 0xFF, 0xFF, 0xFF])]
+#[case(None, false, &[0xE3, 0x02, 0xEB, 0x05, 0xE9, 0xF7, 0xFF, 0xFF, 0xFF])]
+fn test_counter_jump_backward(#[case] kind: Option<LoopKind>, #[case] with_relaxation: bool, #[case] expected: &[u8]) {
+    let label = Label::new();
+    let instructions = [Instruction::SetPrivate_Label { label }, counter_jump(kind, label)];
+    let builder = X86_64AssemblerBuilder::new().with_relaxation(with_relaxation);
+    let final_code = assemble(builder, &instructions);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Some(LoopKind::Loop), &[0xE2, 0x02, 0xEB, 0x05, 0xE9, 0xC8, 0x00, 0x00, 0x00])]
+#[case(None, &[0xE3, 0x02, 0xEB, 0x05, 0xE9, 0xC8, 0x00, 0x00, 0x00])]
+fn test_counter_jump_out_of_range(#[case] kind: Option<LoopKind>, #[case] expected: &[u8]) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(true).build();
+    let label = Label::new();
+    assembler.emit(counter_jump(kind, label)).unwrap();
+    assembler.emit([0xCC; 200]).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 209);
+    assert_eq_hex!(&final_code[..9], expected);
+}
+
+#[test]
+fn test_counter_jump_expanded_by_other_relaxation() {
+    // The loop is in range only until the jump in between is relaxed.
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(true).build();
+    let head = Label::new();
+    let far = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label: head }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: far }).unwrap();
+    assembler.emit([0xCC; 123]).unwrap();
+    assembler
+        .emit(Instruction::Loop_Label {
+            kind: LoopKind::Loop,
+            dst: head,
+        })
+        .unwrap();
+    assembler.emit([0xCC; 130]).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: far }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 5 + 123 + 9 + 130);
+    assert_eq_hex!(&final_code[..5], &[0xE9, 0x06, 0x01, 0x00, 0x00]);
+    assert_eq_hex!(
+        &final_code[128..137],
+        &[0xE2, 0x02, 0xEB, 0x05, 0xE9, 0x77, 0xFF, 0xFF, 0xFF]
+    );
+}
+
+#[rstest]
+#[case(CodeMode::Bit32, true, &[0xE2, 0xFE])]
+#[case(CodeMode::Bit32, false, &[0xE2, 0x02, 0xEB, 0x05, 0xE9, 0xF7, 0xFF, 0xFF, 0xFF])]
+#[case(CodeMode::Bit16, true, &[0xE2, 0xFE])]
+#[case(CodeMode::Bit16, false, &[0xE2, 0x02, 0xEB, 0x03, 0xE9, 0xF9, 0xFF])]
+fn test_loop_in_code_mode(#[case] mode: CodeMode, #[case] with_relaxation: bool, #[case] expected: &[u8]) {
+    let label = Label::new();
+    let instructions = [
+        Instruction::SetPrivate_Label { label },
+        Instruction::Loop_Label {
+            kind: LoopKind::Loop,
+            dst: label,
+        },
+    ];
+    let builder = X86_64AssemblerBuilder::new()
+        .with_code_mode(mode)
+        .with_relaxation(with_relaxation);
+    let final_code = assemble(builder, &instructions);
+    assert_eq_hex!(final_code, expected);
+}
@@ -0,0 +1,209 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::{assemble_single, mem};
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, CodeMode, EmitError, X86_64AssemblerBuilder},
+    models::{
+        CR, Condition, FloatType, FmaKind, FmaOrder, GPR, Immediate32, Instruction, Label, Memory, NewMemoryError,
+        Scale, Segment, Size, XMM,
+    },
+};
+
+fn builder() -> X86_64AssemblerBuilder {
+    X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit16)
+}
+
+fn mem_indexed(base: GPR, index: GPR, offset: i32) -> Memory {
+    Memory::based_scaled(base, index, Scale::Scale1, Immediate32::new(offset)).unwrap()
+}
+
+#[rstest]
+#[case(Instruction::Push_Reg { src: GPR::AX }, &[0x50])]
+#[case(Instruction::Push_Reg { src: GPR::EAX }, &[0x66, 0x50])]
+#[case(Instruction::Pop_Reg { src: GPR::DI }, &[0x5F])]
+#[case(Instruction::Xor_RegReg { dst: GPR::AX, src: GPR::AX }, &[0x31, 0xC0])]
+#[case(Instruction::Mov_RegImm { dst: GPR::SP, src: Immediate32::new(0x7C00) }, &[0xBC, 0x00, 0x7C])]
+#[case(Instruction::Mov_RegImm { dst: GPR::ESP, src: Immediate32::new(0x7C00) }, &[0x66, 0xBC, 0x00, 0x7C, 0x00, 0x00])]
+#[case(Instruction::Add_RegImm { dst: GPR::BX, src: Immediate32::new(1) }, &[0x83, 0xC3, 0x01])]
+#[case(Instruction::Push_Imm { src: Immediate32::new(0x1234) }, &[0x68, 0x34, 0x12])]
+#[case(Instruction::Mov_SegReg { dst: Segment::DS, src: GPR::AX }, &[0x8E, 0xD8])]
+#[case(Instruction::Mov_CrReg { dst: CR::CR0, src: GPR::EAX }, &[0x0F, 0x22, 0xC0])]
+#[case(Instruction::Mov_RegCr { dst: GPR::EAX, src: CR::CR0 }, &[0x0F, 0x20, 0xC0])]
+#[case(Instruction::In_RegReg { dst: GPR::AL, port: GPR::DX }, &[0xEC])]
+#[case(Instruction::In_RegReg { dst: GPR::EAX, port: GPR::DX }, &[0x66, 0xED])]
+#[case(Instruction::Out_RegReg { port: GPR::DX, src: GPR::AX }, &[0xEF])]
+#[case(Instruction::Jump_Reg { dst: GPR::AX }, &[0xFF, 0xE0])]
+#[case(Instruction::Call_Reg { dst: GPR::EAX }, &[0x66, 0xFF, 0xD0])]
+#[case(Instruction::Int_Imm { src: Immediate32::new(0x10) }, &[0xCD, 0x10])]
+#[case(Instruction::FarRet { size: Size::Bit16 }, &[0xCB])]
+#[case(Instruction::FarRet { size: Size::Bit32 }, &[0x66, 0xCB])]
+#[case(Instruction::Cli, &[0xFA])]
+#[case(Instruction::Nop { length: 3.try_into().unwrap() }, &[0x90, 0x90, 0x90])]
+fn test_bit16_encoding(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegMem { dst: GPR::AX, src: mem_indexed(GPR::BX, GPR::SI, 8) }, &[0x8B, 0x40, 0x08])]
+#[case(Instruction::Mov_RegMem { dst: GPR::AL, src: mem(GPR::BP, 0) }, &[0x8A, 0x46, 0x00])]
+#[case(Instruction::Mov_MemReg { dst: mem(GPR::BP, 2), src: GPR::AX }, &[0x89, 0x46, 0x02])]
+#[case(Instruction::Mov_MemImm { dst: mem(GPR::DI, 0), src: Immediate32::new(0x1234) }, &[0xC7, 0x05, 0x34, 0x12])]
+#[case(Instruction::Add_RegMem { dst: GPR::EAX, src: mem_indexed(GPR::BX, GPR::DI, 0x1234) }, &[0x66, 0x03, 0x81, 0x34, 0x12])]
+#[case(Instruction::Cmp_MemImm { dst: mem(GPR::SI, -1), src: Immediate32::new(0) }, &[0x80, 0x7C, 0xFF, 0x00])]
+#[case(Instruction::Push_Mem { src: mem(GPR::BX, 0) }, &[0xFF, 0x37])]
+#[case(Instruction::Lgdt_Mem { src: mem(GPR::BX, 0) }, &[0x0F, 0x01, 0x17])]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::BX, 0), size: Size::Bit16 }, &[0xFF, 0x2F])]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::BX, 0), size: Size::Bit32 }, &[0x66, 0xFF, 0x2F])]
+fn test_bit16_addressing(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_bit32_addressing() {
+    let src = Memory::based_scaled(GPR::EBX, GPR::ECX, Scale::Scale4, Immediate32::new(0)).unwrap();
+    let final_code = assemble_single(builder(), Instruction::Mov_RegMem { dst: GPR::EAX, src });
+    assert_eq_hex!(final_code, &[0x67, 0x66, 0x8B, 0x04, 0x8B]);
+}
+
+#[test]
+fn test_absolute_label_address() {
+    let mut assembler = builder().with_base_address(0x7C00).build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_MemImm {
+            dst: Memory::label(label),
+            src: Immediate32::new(0x1234),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(
+        final_code,
+        &[0x66, 0x8B, 0x06, 0x0C, 0x7C, 0xC7, 0x06, 0x0C, 0x7C, 0x34, 0x12, 0xC3]
+    );
+}
+
+#[test]
+fn test_absolute_label_address_out_of_range() {
+    let mut assembler = builder().with_base_address(0xFFFF).build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::AX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::AddressOutOfRange(out_of_range)) if out_of_range == label));
+}
+
+#[test]
+fn test_rel16_jumps() {
+    let mut assembler = builder().with_relaxation(false).build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    assembler
+        .emit(Instruction::CondJump_Label {
+            condition: Condition::Equal,
+            dst: label,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Call_Label { dst: label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(
+        final_code,
+        &[0xE9, 0xFD, 0xFF, 0x0F, 0x84, 0xF9, 0xFF, 0xE8, 0xF6, 0xFF]
+    );
+}
+
+#[test]
+fn test_relaxation_to_rel16() {
+    let mut assembler = builder().build();
+    let label = Label::new();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    assembler.emit([0xCC; 200]).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 203);
+    assert_eq_hex!(&final_code[..3], &[0xE9, 0xC8, 0x00]);
+}
+
+#[rstest]
+#[case(Instruction::Syscall)]
+#[case(Instruction::Fma_XmmXmmXmm { kind: FmaKind::MulAdd, order: FmaOrder::Order132, ty: FloatType::PackedSingle, dst: XMM::XMM1, src1: XMM::XMM2, src2: XMM::XMM3 })]
+fn test_not_supported(#[case] instruction: Instruction) {
+    let mut assembler = builder().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::NotSupportedInCodeMode)));
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegReg { dst: GPR::RAX, src: GPR::RCX }, EmitError::IncompatibleOperands)]
+#[case(Instruction::Mov_RegReg { dst: GPR::R8W, src: GPR::CX }, EmitError::IncompatibleOperands)]
+#[case(Instruction::Push_Reg { src: GPR::AL }, EmitError::OperandSizeMismatch)]
+#[case(Instruction::FarRet { size: Size::Bit64 }, EmitError::OperandSizeMismatch)]
+#[case(Instruction::Mov_RegMem { dst: GPR::AX, src: mem(GPR::RAX, 0) }, EmitError::AddressSizeMismatch)]
+fn test_invalid_operands(#[case] instruction: Instruction, #[case] expected: EmitError) {
+    let mut assembler = builder().build();
+    let result = assembler.emit(instruction);
+    assert_eq!(
+        std::mem::discriminant(&result.unwrap_err()),
+        std::mem::discriminant(&expected)
+    );
+}
+
+#[test]
+fn test_bit16_address_outside_of_bit16_code() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Mov_RegMem {
+        dst: GPR::AX,
+        src: mem(GPR::BX, 0),
+    });
+    assert!(matches!(result, Err(EmitError::AddressSizeMismatch)));
+}
+
+#[test]
+fn test_new_bit16_memory() {
+    let offset = Immediate32::new(0);
+    assert_eq!(Memory::based(GPR::AX, offset), Err(NewMemoryError::InvalidBit16Address));
+    assert_eq!(
+        Memory::based(GPR::BX, Immediate32::new(0x10000)),
+        Err(NewMemoryError::InvalidBit16Address)
+    );
+    assert_eq!(
+        Memory::based_scaled(GPR::SI, GPR::BX, Scale::Scale1, offset),
+        Err(NewMemoryError::InvalidBit16Address)
+    );
+    assert_eq!(
+        Memory::based_scaled(GPR::BX, GPR::SI, Scale::Scale2, offset),
+        Err(NewMemoryError::InvalidBit16Address)
+    );
+    assert_eq!(
+        Memory::scaled(GPR::SI, Scale::Scale1, offset),
+        Err(NewMemoryError::InvalidBit16Address)
+    );
+}
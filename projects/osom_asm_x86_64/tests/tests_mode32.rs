@@ -0,0 +1,164 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::{assemble_single, mem};
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, EmitError, X86_64AssemblerBuilder},
+    models::{
+        CR, Condition, DR, FloatType, FmaKind, FmaOrder, GPR, Immediate32, Immediate64, Instruction, Label, Memory,
+        NewMemoryError, Scale, Size, X87FloatSize, XMM,
+    },
+};
+
+fn builder() -> X86_64AssemblerBuilder {
+    X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit32)
+}
+
+#[rstest]
+#[case(Instruction::Push_Reg { src: GPR::EAX }, &[0x50])]
+#[case(Instruction::Pop_Reg { src: GPR::EDI }, &[0x5F])]
+#[case(Instruction::Jump_Reg { dst: GPR::EAX }, &[0xFF, 0xE0])]
+#[case(Instruction::Call_Mem { dst: mem(GPR::EAX, 0) }, &[0xFF, 0x10])]
+#[case(Instruction::Push_Mem { src: mem(GPR::ECX, 0) }, &[0xFF, 0x31])]
+#[case(Instruction::Mov_RegImm { dst: GPR::EAX, src: Immediate32::new(1) }, &[0xB8, 0x01, 0x00, 0x00, 0x00])]
+#[case(Instruction::Xor_RegReg { dst: GPR::EDX, src: GPR::EDX }, &[0x31, 0xD2])]
+#[case(Instruction::Sub_RegImm { dst: GPR::ESP, src: Immediate32::new(16) }, &[0x83, 0xEC, 0x10])]
+#[case(Instruction::Mov_MemImm { dst: mem(GPR::EAX, 0), src: Immediate32::new(5) }, &[0xC6, 0x00, 0x05])]
+#[case(Instruction::Add_RegMem { dst: GPR::ECX, src: mem(GPR::ESP, 0) }, &[0x03, 0x0C, 0x24])]
+#[case(Instruction::Mov_RegMem { dst: GPR::AX, src: mem(GPR::EBP, 0) }, &[0x66, 0x8B, 0x45, 0x00])]
+#[case(Instruction::Mov_RegMem { dst: GPR::AL, src: mem(GPR::ESI, 1) }, &[0x8A, 0x46, 0x01])]
+#[case(Instruction::Mov_CrReg { dst: CR::CR3, src: GPR::EAX }, &[0x0F, 0x22, 0xD8])]
+#[case(Instruction::Mov_RegCr { dst: GPR::EAX, src: CR::CR0 }, &[0x0F, 0x20, 0xC0])]
+#[case(Instruction::Mov_DrReg { dst: DR::DR7, src: GPR::ECX }, &[0x0F, 0x23, 0xF9])]
+#[case(Instruction::Lgdt_Mem { src: mem(GPR::EAX, 0) }, &[0x0F, 0x01, 0x10])]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::EAX, 0), size: Size::Bit32 }, &[0xFF, 0x28])]
+#[case(Instruction::Fld_Mem { size: X87FloatSize::Double, src: mem(GPR::EAX, 0) }, &[0xDD, 0x00])]
+#[case(Instruction::Crc32_RegMem { dst: GPR::EAX, src: mem(GPR::ECX, 0), size: Size::Bit8 }, &[0xF2, 0x0F, 0x38, 0xF0, 0x01])]
+#[case(Instruction::Fma_XmmXmmXmm { kind: FmaKind::MulAdd, order: FmaOrder::Order132, ty: FloatType::PackedSingle, dst: XMM::XMM1, src1: XMM::XMM2, src2: XMM::XMM3 }, &[0xC4, 0xE2, 0x69, 0x98, 0xCB])]
+fn test_bit32_encoding(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(builder(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_bit32_scaled_memory() {
+    let src = Memory::based_scaled(GPR::EBX, GPR::ECX, Scale::Scale4, Immediate32::new(8)).unwrap();
+    let final_code = assemble_single(builder(), Instruction::Mov_RegMem { dst: GPR::EAX, src });
+    assert_eq_hex!(final_code, &[0x8B, 0x44, 0x8B, 0x08]);
+
+    let src = Memory::scaled(GPR::EAX, Scale::Scale4, Immediate32::new(8)).unwrap();
+    let final_code = assemble_single(builder(), Instruction::Mov_RegMem { dst: GPR::ECX, src });
+    assert_eq_hex!(final_code, &[0x8B, 0x0C, 0x85, 0x08, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn test_bit32_absolute_label_address() {
+    let mut assembler = builder().build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_MemImm {
+            dst: Memory::label(label),
+            src: Immediate32::new(1),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit([1, 2, 3, 4]).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x8B, 0x05, 0x0E, 0x00, 0x00, 0x00, 0xC6, 0x05, 0x0E, 0x00, 0x00, 0x00, 0x01, 0xC3, 0x01, 0x02, 0x03, 0x04
+        ]
+    );
+}
+
+#[test]
+fn test_bit32_relative_jumps() {
+    let mut assembler = builder().build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::Call_Label { dst: label }).unwrap();
+    assembler
+        .emit(Instruction::CondJump_Label {
+            condition: Condition::Equal,
+            dst: label,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(final_code, &[0xE8, 0xFB, 0xFF, 0xFF, 0xFF, 0x74, 0xF9, 0xEB, 0xF7]);
+}
+
+#[rstest]
+#[case(Instruction::Mov_RegReg { dst: GPR::RAX, src: GPR::RCX })]
+#[case(Instruction::Mov_RegReg { dst: GPR::R8D, src: GPR::ECX })]
+#[case(Instruction::Mov_RegReg { dst: GPR::SIL, src: GPR::AL })]
+#[case(Instruction::Mov_RegImm64 { dst: GPR::RAX, src: Immediate64::new(1) })]
+#[case(Instruction::Mov_CrReg { dst: CR::CR8, src: GPR::EAX })]
+#[case(Instruction::Iretq)]
+#[case(Instruction::Swapgs)]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::EAX, 0), size: Size::Bit64 })]
+#[case(Instruction::Fma_XmmXmmXmm { kind: FmaKind::MulAdd, order: FmaOrder::Order132, ty: FloatType::PackedSingle, dst: XMM::XMM8, src1: XMM::XMM2, src2: XMM::XMM3 })]
+#[case(Instruction::Fma_XmmXmmXmm { kind: FmaKind::MulAdd, order: FmaOrder::Order132, ty: FloatType::PackedSingle, dst: XMM::XMM1, src1: XMM::XMM9, src2: XMM::XMM3 })]
+fn test_bit32_incompatible_operands(#[case] instruction: Instruction) {
+    let mut assembler = builder().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::IncompatibleOperands)));
+}
+
+#[rstest]
+#[case(Instruction::Push_Reg { src: GPR::RAX })]
+#[case(Instruction::Pop_Reg { src: GPR::AX })]
+#[case(Instruction::Call_Reg { dst: GPR::RAX })]
+#[case(Instruction::Mov_RegCr { dst: GPR::RAX, src: CR::CR0 })]
+fn test_bit32_invalid_sizes(#[case] instruction: Instruction) {
+    let mut assembler = builder().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[test]
+fn test_address_size_mismatch() {
+    let mut assembler = builder().build();
+    let result = assembler.emit(Instruction::Mov_RegMem {
+        dst: GPR::EAX,
+        src: mem(GPR::RAX, 0),
+    });
+    assert!(matches!(result, Err(EmitError::AddressSizeMismatch)));
+
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Mov_RegMem {
+        dst: GPR::EAX,
+        src: mem(GPR::EAX, 0),
+    });
+    assert!(matches!(result, Err(EmitError::AddressSizeMismatch)));
+}
+
+#[test]
+fn test_new_memory() {
+    let offset = Immediate32::new(0);
+    assert_eq!(Memory::based(GPR::AL, offset), Err(NewMemoryError::GPRNotBit64));
+    assert_eq!(
+        Memory::scaled(GPR::ESP, Scale::Scale1, offset),
+        Err(NewMemoryError::RSPNotAllowedAsIndex)
+    );
+    assert_eq!(
+        Memory::based_scaled(GPR::EAX, GPR::RCX, Scale::Scale1, offset),
+        Err(NewMemoryError::AddressSizeMismatch)
+    );
+}
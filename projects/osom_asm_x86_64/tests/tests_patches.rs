@@ -57,3 +57,24 @@ fn test_patchable_mov_forward() {
     assert_eq_hex!(final_code, expected);
     assert_eq!(result.emitted_bytes(), expected.len() as i32);
 }
+
+#[test]
+fn test_patchable_mov_after_jump() {
+    let expected = &[0xEB, 0xFE, 0x48, 0x8B, 0x15, 0xF7, 0xFF, 0xFF, 0xFF, 0xC3];
+    let mut assembler = X86_64AssemblerBuilder::new().with_relaxation(true).build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RDX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, expected);
+    assert_eq!(result.emitted_bytes(), expected.len() as i32);
+}
@@ -0,0 +1,195 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, CodeMode, EmitError, RelocationKind, X86_64AssemblerBuilder},
+    models::{GPR, Immediate32, Instruction, Label, Memory},
+};
+
+#[test]
+fn test_mov_reg_label() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegLabel { dst: GPR::RAX, label })
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegLabel { dst: GPR::R9D, label })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x48, 0xB8, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x41, 0xB9, 0x10, 0x00, 0x00, 0x00, 0xC3
+        ]
+    );
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 2);
+    assert_eq!(relocations[0].offset(), 2);
+    assert_eq!(relocations[0].kind(), RelocationKind::Absolute64);
+    assert_eq!(relocations[0].target(), label);
+    assert_eq!(relocations[0].addend(), 0x10);
+    assert_eq!(relocations[1].offset(), 12);
+    assert_eq!(relocations[1].kind(), RelocationKind::Absolute32);
+    assert_eq!(relocations[1].addend(), 0x10);
+}
+
+#[test]
+fn test_mov_reg_label_apx() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_apx(true).build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler
+        .emit(Instruction::Mov_RegLabel { dst: GPR::R16, label })
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xD5, 0x18, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(result.relocations()[0].offset(), 3);
+}
+
+#[test]
+fn test_vtable_with_base_address() {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_base_address(0x1234_5678_0000)
+        .build();
+    let first = Label::new();
+    let second = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label: first }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: second }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Instruction::LabelAddress64 { label: first }).unwrap();
+    assembler.emit(Instruction::LabelAddress64 { label: second }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xC3, 0xC3, 0x00, 0x00, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x01, 0x00, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00
+        ]
+    );
+
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 2);
+    assert_eq!((relocations[0].offset(), relocations[0].addend()), (2, 0));
+    assert_eq!((relocations[1].offset(), relocations[1].addend()), (10, 1));
+
+    result.apply_relocations(&mut final_code, 0x1000).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[
+            0xC3, 0xC3, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+        ]
+    );
+}
+
+#[rstest]
+#[case(CodeMode::Bit32, RelocationKind::Absolute32, 2)]
+#[case(CodeMode::Bit16, RelocationKind::Absolute16, 2)]
+fn test_memory_label_relocations(#[case] mode: CodeMode, #[case] kind: RelocationKind, #[case] offset: i32) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(mode).build();
+    let label = Label::new();
+    let dst = if mode == CodeMode::Bit32 { GPR::ECX } else { GPR::CX };
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let relocations = result.relocations();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].kind(), kind);
+    assert_eq!(relocations[0].offset(), offset);
+    assert_eq!(relocations[0].addend(), i64::from(result.emitted_bytes()));
+
+    result.apply_relocations(&mut final_code, 0x7C00).unwrap();
+    let offset = offset as usize;
+    assert_eq_hex!(
+        &final_code[offset..offset + 2],
+        &(0x7C00 + result.emitted_bytes() as u16).to_le_bytes()
+    );
+}
+
+#[test]
+fn test_no_relocations_in_rip_relative_code() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::RAX,
+            src: Memory::label(label),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Jump_Label { dst: label }).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert!(result.relocations().is_empty());
+}
+
+#[test]
+fn test_apply_relocations_out_of_range() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit32).build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    assembler.emit(Instruction::LabelAddress32 { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let error = result.apply_relocations(&mut final_code, 0x1_0000_0000);
+    assert!(matches!(error, Err(AssembleError::AddressOutOfRange(out_of_range)) if out_of_range == label));
+}
+
+#[rstest]
+#[case(CodeMode::Bit64, GPR::AX, EmitError::OperandSizeMismatch)]
+#[case(CodeMode::Bit64, GPR::AL, EmitError::OperandSizeMismatch)]
+#[case(CodeMode::Bit64, GPR::R16, EmitError::ApxNotEnabled)]
+#[case(CodeMode::Bit32, GPR::RAX, EmitError::IncompatibleOperands)]
+#[case(CodeMode::Bit16, GPR::EAX, EmitError::NotSupportedInCodeMode)]
+fn test_mov_reg_label_invalid(#[case] mode: CodeMode, #[case] dst: GPR, #[case] expected: EmitError) {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(mode).build();
+    let result = assembler.emit(Instruction::Mov_RegLabel {
+        dst,
+        label: Label::new(),
+    });
+    assert_eq!(
+        std::mem::discriminant(&result.unwrap_err()),
+        std::mem::discriminant(&expected)
+    );
+}
+
+#[test]
+fn test_mov_reg_label_bit32() {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_code_mode(CodeMode::Bit32)
+        .with_base_address(0x40_0000)
+        .build();
+    let label = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegLabel { dst: GPR::EDX, label })
+        .unwrap();
+    assembler
+        .emit(Instruction::Add_RegImm {
+            dst: GPR::EDX,
+            src: Immediate32::new(1),
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xBA, 0x08, 0x00, 0x40, 0x00, 0x83, 0xC2, 0x01]);
+}
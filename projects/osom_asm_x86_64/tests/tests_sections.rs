@@ -0,0 +1,185 @@
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, SectionLayout, X86_64AssemblerBuilder},
+    models::{Alignment, Data, GPR, Instruction, Label, Memory, Section},
+};
+
+#[test]
+fn test_sections_are_contiguous() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let value = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: Memory::label(value),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::SwitchSection {
+            section: Section::RODATA,
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: value }).unwrap();
+    assembler.emit(Data::U32(0x1122_3344)).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::TEXT })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection {
+            section: Section::RODATA,
+        })
+        .unwrap();
+    assembler.emit(Data::U8(0xAA)).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(
+        final_code,
+        &[0x8B, 0x05, 0x01, 0x00, 0x00, 0x00, 0xC3, 0x44, 0x33, 0x22, 0x11, 0xAA]
+    );
+
+    let sections = result.sections();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].section(), Section::TEXT);
+    assert_eq!((sections[0].offset(), sections[0].length()), (0, 7));
+    assert_eq!(sections[1].section(), Section::RODATA);
+    assert_eq!((sections[1].offset(), sections[1].length()), (7, 5));
+}
+
+#[test]
+fn test_layout_order_and_alignment() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let value = Label::new();
+    let entry = Label::new();
+    assembler.emit(Instruction::SetPublic_Label { label: entry }).unwrap();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: Memory::label(value),
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection {
+            section: Section::RODATA,
+        })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: value }).unwrap();
+    assembler.emit(Data::U32(0x1122_3344)).unwrap();
+
+    let layout = SectionLayout::new()
+        .with_section(Section::RODATA)
+        .with_aligned_section(Section::TEXT, Alignment::new(16).unwrap());
+    let mut final_code = Vec::new();
+    let result = assembler.assemble_with_layout(&layout, &mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    assert_eq_hex!(
+        final_code,
+        &[
+            0x44, 0x33, 0x22, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x8B, 0x05,
+            0xEA, 0xFF, 0xFF, 0xFF, 0xC3
+        ]
+    );
+    assert_eq!(result.public_labels_positions().get(&entry), Some(&16));
+
+    let text = result.section(Section::TEXT).unwrap();
+    assert_eq!((text.offset(), text.length()), (16, 7));
+    let rodata = result.section(Section::RODATA).unwrap();
+    assert_eq!((rodata.offset(), rodata.length()), (0, 4));
+}
+
+#[test]
+fn test_relaxation_across_sections() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let target = Label::new();
+    let custom = Section::new();
+    assembler.emit(Instruction::Jump_Label { dst: target }).unwrap();
+    assembler.emit(Instruction::SwitchSection { section: custom }).unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: target }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::DATA })
+        .unwrap();
+    assembler.emit(Data::U8(0xCC).times(200)).unwrap();
+
+    let layout = SectionLayout::new()
+        .with_section(Section::TEXT)
+        .with_section(Section::DATA);
+    let mut final_code = Vec::new();
+    let result = assembler.assemble_with_layout(&layout, &mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), 206);
+    assert_eq_hex!(&final_code[..5], &[0xE9, 0xC8, 0x00, 0x00, 0x00]);
+    assert_eq!(final_code[205], 0xC3);
+    assert_eq!(result.section(custom).unwrap().offset(), 205);
+}
+
+#[test]
+fn test_unused_sections() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::TEXT })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let layout = SectionLayout::new()
+        .with_aligned_section(Section::DATA, Alignment::new(4096).unwrap())
+        .with_section(Section::TEXT);
+    let mut final_code = Vec::new();
+    let result = assembler.assemble_with_layout(&layout, &mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0xC3]);
+    assert_eq!(result.sections().len(), 1);
+    assert!(result.section(Section::DATA).is_none());
+}
+
+#[test]
+fn test_constant_pool_in_rodata() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let constant = assembler.constant_u32(0x1122_3344);
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::EAX,
+            src: constant,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let layout = SectionLayout::new().with_section(Section::RODATA);
+    let mut final_code = Vec::new();
+    let result = assembler.assemble_with_layout(&layout, &mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[0x44, 0x33, 0x22, 0x11, 0x8B, 0x05, 0xF6, 0xFF, 0xFF, 0xFF, 0xC3]
+    );
+    assert_eq!(result.section(Section::TEXT).unwrap().offset(), 4);
+}
+
+#[test]
+fn test_sections_in_bit16_code() {
+    let mut assembler = X86_64AssemblerBuilder::new()
+        .with_code_mode(CodeMode::Bit16)
+        .with_base_address(0x7C00)
+        .build();
+    let value = Label::new();
+    assembler
+        .emit(Instruction::Mov_RegMem {
+            dst: GPR::AX,
+            src: Memory::label(value),
+        })
+        .unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::DATA })
+        .unwrap();
+    assembler.emit(Instruction::SetPrivate_Label { label: value }).unwrap();
+    assembler.emit(Data::U16(0x1234)).unwrap();
+    assembler
+        .emit(Instruction::SwitchSection { section: Section::TEXT })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let _ = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(final_code, &[0x8B, 0x06, 0x05, 0x7C, 0xC3, 0x34, 0x12]);
+}
@@ -0,0 +1,202 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::{assemble_single, mem};
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{GPR, Immediate32, Instruction, Memory, PackedMinMaxKind, Size, XMM},
+};
+
+#[rstest]
+#[case(Instruction::Pcmpistri_XmmXmmImm { dst: XMM::XMM1, src: XMM::XMM2, imm: Immediate32::new(0x0C) }, &[0x66, 0x0F, 0x3A, 0x63, 0xCA, 0x0C])]
+#[case(Instruction::Pcmpistri_XmmMemImm { dst: XMM::XMM9, src: mem(GPR::RDI, 0), imm: Immediate32::new(0x18) }, &[0x66, 0x44, 0x0F, 0x3A, 0x63, 0x0F, 0x18])]
+#[case(Instruction::Pcmpestri_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM11, imm: Immediate32::new(0x44) }, &[0x66, 0x41, 0x0F, 0x3A, 0x61, 0xC3, 0x44])]
+#[case(Instruction::Pcmpestri_XmmMemImm { dst: XMM::XMM3, src: mem(GPR::RSI, 16), imm: Immediate32::ZERO }, &[0x66, 0x0F, 0x3A, 0x61, 0x5E, 0x10, 0x00])]
+#[case(Instruction::Ptest_XmmXmm { dst: XMM::XMM0, src: XMM::XMM1 }, &[0x66, 0x0F, 0x38, 0x17, 0xC1])]
+#[case(Instruction::Ptest_XmmMem { dst: XMM::XMM12, src: mem(GPR::RAX, 0) }, &[0x66, 0x44, 0x0F, 0x38, 0x17, 0x20])]
+#[case(Instruction::Pblendvb_XmmXmm { dst: XMM::XMM1, src: XMM::XMM2, mask: XMM::XMM0 }, &[0x66, 0x0F, 0x38, 0x10, 0xCA])]
+#[case(Instruction::Pblendvb_XmmMem { dst: XMM::XMM10, src: mem(GPR::RBX, 8), mask: XMM::XMM0 }, &[0x66, 0x44, 0x0F, 0x38, 0x10, 0x53, 0x08])]
+#[case(Instruction::Blendps_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM1, imm: Immediate32::new(5) }, &[0x66, 0x0F, 0x3A, 0x0C, 0xC1, 0x05])]
+#[case(Instruction::Blendps_XmmMemImm { dst: XMM::XMM2, src: mem(GPR::RCX, 0), imm: Immediate32::new(15) }, &[0x66, 0x0F, 0x3A, 0x0C, 0x11, 0x0F])]
+fn test_string_compare_test_and_blend(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(PackedMinMaxKind::MinSignedByte, XMM::XMM0, XMM::XMM1, &[0x66, 0x0F, 0x38, 0x38, 0xC1])]
+#[case(PackedMinMaxKind::MinSignedDword, XMM::XMM2, XMM::XMM3, &[0x66, 0x0F, 0x38, 0x39, 0xD3])]
+#[case(PackedMinMaxKind::MinUnsignedWord, XMM::XMM4, XMM::XMM5, &[0x66, 0x0F, 0x38, 0x3A, 0xE5])]
+#[case(PackedMinMaxKind::MinUnsignedDword, XMM::XMM8, XMM::XMM9, &[0x66, 0x45, 0x0F, 0x38, 0x3B, 0xC1])]
+#[case(PackedMinMaxKind::MaxSignedByte, XMM::XMM6, XMM::XMM7, &[0x66, 0x0F, 0x38, 0x3C, 0xF7])]
+#[case(PackedMinMaxKind::MaxSignedDword, XMM::XMM1, XMM::XMM14, &[0x66, 0x41, 0x0F, 0x38, 0x3D, 0xCE])]
+#[case(PackedMinMaxKind::MaxUnsignedWord, XMM::XMM0, XMM::XMM0, &[0x66, 0x0F, 0x38, 0x3E, 0xC0])]
+fn test_packed_min_max(#[case] kind: PackedMinMaxKind, #[case] dst: XMM, #[case] src: XMM, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::PackedMinMax_XmmXmm { kind, dst, src },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_packed_min_max_mem() {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::PackedMinMax_XmmMem {
+            kind: PackedMinMaxKind::MaxUnsignedDword,
+            dst: XMM::XMM2,
+            src: mem(GPR::RDX, 0),
+        },
+    );
+    assert_eq_hex!(final_code, &[0x66, 0x0F, 0x38, 0x3F, 0x12]);
+}
+
+#[rstest]
+#[case(Size::Bit8, XMM::XMM0, GPR::EAX, 15, &[0x66, 0x0F, 0x3A, 0x20, 0xC0, 0x0F])]
+#[case(Size::Bit16, XMM::XMM1, GPR::ECX, 7, &[0x66, 0x0F, 0xC4, 0xC9, 0x07])]
+#[case(Size::Bit32, XMM::XMM9, GPR::R8D, 3, &[0x66, 0x45, 0x0F, 0x3A, 0x22, 0xC8, 0x03])]
+#[case(Size::Bit64, XMM::XMM2, GPR::R10, 1, &[0x66, 0x49, 0x0F, 0x3A, 0x22, 0xD2, 0x01])]
+fn test_pinsr_reg(#[case] size: Size, #[case] dst: XMM, #[case] src: GPR, #[case] imm: i32, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Pinsr_XmmRegImm {
+            size,
+            dst,
+            src,
+            imm: Immediate32::new(imm),
+        },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Size::Bit8, 1, &[0x66, 0x0F, 0x3A, 0x20, 0x1F, 0x01])]
+#[case(Size::Bit16, 2, &[0x66, 0x0F, 0xC4, 0x1F, 0x02])]
+#[case(Size::Bit32, 2, &[0x66, 0x0F, 0x3A, 0x22, 0x1F, 0x02])]
+#[case(Size::Bit64, 0, &[0x66, 0x48, 0x0F, 0x3A, 0x22, 0x1F, 0x00])]
+fn test_pinsr_mem(#[case] size: Size, #[case] imm: i32, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Pinsr_XmmMemImm {
+            size,
+            dst: XMM::XMM3,
+            src: mem(GPR::RDI, 0),
+            imm: Immediate32::new(imm),
+        },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Size::Bit8, GPR::EAX, XMM::XMM0, 15, &[0x66, 0x0F, 0x3A, 0x14, 0xC0, 0x0F])]
+#[case(Size::Bit16, GPR::ECX, XMM::XMM1, 7, &[0x66, 0x0F, 0xC5, 0xC9, 0x07])]
+#[case(Size::Bit16, GPR::R9D, XMM::XMM12, 1, &[0x66, 0x45, 0x0F, 0xC5, 0xCC, 0x01])]
+#[case(Size::Bit32, GPR::R8D, XMM::XMM9, 3, &[0x66, 0x45, 0x0F, 0x3A, 0x16, 0xC8, 0x03])]
+#[case(Size::Bit64, GPR::R10, XMM::XMM2, 1, &[0x66, 0x49, 0x0F, 0x3A, 0x16, 0xD2, 0x01])]
+fn test_pextr_reg(#[case] size: Size, #[case] dst: GPR, #[case] src: XMM, #[case] imm: i32, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Pextr_RegXmmImm {
+            size,
+            dst,
+            src,
+            imm: Immediate32::new(imm),
+        },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Size::Bit8, XMM::XMM3, mem(GPR::RDI, 0), 1, &[0x66, 0x0F, 0x3A, 0x14, 0x1F, 0x01])]
+#[case(Size::Bit16, XMM::XMM3, mem(GPR::RDI, 0), 2, &[0x66, 0x0F, 0x3A, 0x15, 0x1F, 0x02])]
+#[case(Size::Bit32, XMM::XMM3, mem(GPR::RDI, 0), 2, &[0x66, 0x0F, 0x3A, 0x16, 0x1F, 0x02])]
+#[case(Size::Bit64, XMM::XMM11, mem(GPR::RSP, 0), 0, &[0x66, 0x4C, 0x0F, 0x3A, 0x16, 0x1C, 0x24, 0x00])]
+fn test_pextr_mem(
+    #[case] size: Size,
+    #[case] src: XMM,
+    #[case] dst: Memory,
+    #[case] imm: i32,
+    #[case] expected: &[u8],
+) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Pextr_MemXmmImm {
+            size,
+            src,
+            dst,
+            imm: Immediate32::new(imm),
+        },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(false, Size::Bit8, Size::Bit16, XMM::XMM0, XMM::XMM1, &[0x66, 0x0F, 0x38, 0x30, 0xC1])]
+#[case(true, Size::Bit8, Size::Bit32, XMM::XMM2, XMM::XMM3, &[0x66, 0x0F, 0x38, 0x21, 0xD3])]
+#[case(false, Size::Bit8, Size::Bit64, XMM::XMM4, XMM::XMM5, &[0x66, 0x0F, 0x38, 0x32, 0xE5])]
+#[case(true, Size::Bit16, Size::Bit32, XMM::XMM8, XMM::XMM9, &[0x66, 0x45, 0x0F, 0x38, 0x23, 0xC1])]
+#[case(false, Size::Bit16, Size::Bit64, XMM::XMM6, XMM::XMM15, &[0x66, 0x41, 0x0F, 0x38, 0x34, 0xF7])]
+#[case(true, Size::Bit32, Size::Bit64, XMM::XMM1, XMM::XMM2, &[0x66, 0x0F, 0x38, 0x25, 0xCA])]
+fn test_pmovx(
+    #[case] signed: bool,
+    #[case] from: Size,
+    #[case] to: Size,
+    #[case] dst: XMM,
+    #[case] src: XMM,
+    #[case] expected: &[u8],
+) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Pmovx_XmmXmm {
+            signed,
+            from,
+            to,
+            dst,
+            src,
+        },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[test]
+fn test_pmovx_mem() {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::Pmovx_XmmMem {
+            signed: false,
+            from: Size::Bit32,
+            to: Size::Bit64,
+            dst: XMM::XMM1,
+            src: mem(GPR::RAX, 0),
+        },
+    );
+    assert_eq_hex!(final_code, &[0x66, 0x0F, 0x38, 0x35, 0x08]);
+}
+
+#[rstest]
+#[case(Instruction::Pblendvb_XmmXmm { dst: XMM::XMM1, src: XMM::XMM2, mask: XMM::XMM3 })]
+#[case(Instruction::Pblendvb_XmmMem { dst: XMM::XMM1, src: mem(GPR::RAX, 0), mask: XMM::XMM1 })]
+fn test_pblendvb_requires_xmm0(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::InvalidImplicitOperand)));
+}
+
+#[rstest]
+#[case(Instruction::Pinsr_XmmRegImm { size: Size::Bit8, dst: XMM::XMM0, src: GPR::AL, imm: Immediate32::ZERO })]
+#[case(Instruction::Pinsr_XmmRegImm { size: Size::Bit32, dst: XMM::XMM0, src: GPR::RAX, imm: Immediate32::ZERO })]
+#[case(Instruction::Pinsr_XmmRegImm { size: Size::Bit64, dst: XMM::XMM0, src: GPR::EAX, imm: Immediate32::ZERO })]
+#[case(Instruction::Pinsr_XmmRegImm { size: Size::Bit32, dst: XMM::XMM0, src: GPR::EAX, imm: Immediate32::new(4) })]
+#[case(Instruction::Pextr_RegXmmImm { size: Size::Bit16, dst: GPR::AX, src: XMM::XMM0, imm: Immediate32::ZERO })]
+#[case(Instruction::Pextr_MemXmmImm { size: Size::Bit64, src: XMM::XMM0, dst: mem(GPR::RAX, 0), imm: Immediate32::new(2) })]
+#[case(Instruction::Blendps_XmmXmmImm { dst: XMM::XMM0, src: XMM::XMM1, imm: Immediate32::new(16) })]
+#[case(Instruction::Pmovx_XmmXmm { signed: true, from: Size::Bit32, to: Size::Bit16, dst: XMM::XMM0, src: XMM::XMM1 })]
+#[case(Instruction::Pmovx_XmmXmm { signed: false, from: Size::Bit64, to: Size::Bit64, dst: XMM::XMM0, src: XMM::XMM1 })]
+fn test_invalid_operands(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
@@ -0,0 +1,125 @@
+use osom_tools_dev::macros::assert_eq_hex;
+
+use osom_asm_x86_64::{
+    assembler::{AssembleError, EmitError, X86_64AssemblerBuilder},
+    models::{Data, GPR, Instruction, Label, Symbol, SymbolKind, SymbolSize},
+};
+
+#[test]
+fn test_symbol_table() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let function = Label::new();
+    let function_end = Label::new();
+    let table = Label::new();
+    assembler
+        .emit(Symbol::function("add_one", function).with_end(function_end))
+        .unwrap();
+    assembler
+        .emit(Instruction::Mov_RegReg {
+            dst: GPR::RAX,
+            src: GPR::RDI,
+        })
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Instruction::SetPrivate_Label { label: function_end })
+        .unwrap();
+    assembler.emit(Symbol::object("table", table).with_size(8)).unwrap();
+    assembler.emit(Data::U64(0x1122_3344_5566_7788)).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq_hex!(
+        final_code,
+        &[0x48, 0x89, 0xF8, 0xC3, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]
+    );
+
+    assert_eq!(result.symbols().len(), 2);
+    let add_one = result.symbol("add_one").unwrap();
+    assert_eq!(add_one.label(), function);
+    assert_eq!(add_one.kind(), SymbolKind::Function);
+    assert_eq!(add_one.offset(), 0);
+    assert_eq!(add_one.size(), Some(4));
+
+    let table_symbol = result.symbol("table").unwrap();
+    assert_eq!(table_symbol.kind(), SymbolKind::Object);
+    assert_eq!(table_symbol.offset(), 4);
+    assert_eq!(table_symbol.size(), Some(8));
+
+    assert!(result.symbol("missing").is_none());
+    assert_eq!(result.public_labels_positions().get(&function), Some(&0));
+    assert_eq!(result.public_labels_positions().get(&table), Some(&4));
+}
+
+#[test]
+fn test_symbol_properties() {
+    let label = Label::new();
+    let end = Label::new();
+    let symbol = Symbol::new("main", label, SymbolKind::Function);
+    assert_eq!(symbol.name(), "main");
+    assert_eq!(symbol.label(), label);
+    assert_eq!(symbol.size(), SymbolSize::Unknown);
+    assert_eq!(symbol.clone().with_size(16).size(), SymbolSize::Fixed(16));
+    assert_eq!(symbol.with_end(end).size(), SymbolSize::UntilLabel(end));
+}
+
+#[test]
+fn test_unknown_symbol_size() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler.emit(Symbol::function("tail", Label::new())).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    let tail = result.symbol("tail").unwrap();
+    assert_eq!(tail.offset(), 1);
+    assert_eq!(tail.size(), None);
+}
+
+#[test]
+fn test_duplicate_symbol_name() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    assembler.emit(Symbol::function("main", Label::new())).unwrap();
+    let result = assembler.emit(Symbol::function("main", Label::new()));
+    assert!(matches!(result, Err(EmitError::SymbolAlreadyDefined(name)) if name == "main"));
+}
+
+#[test]
+fn test_symbol_label_already_defined() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let label = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label }).unwrap();
+    let result = assembler.emit(Symbol::function("main", label));
+    assert!(matches!(result, Err(EmitError::LabelAlreadyDefined(defined)) if defined == label));
+}
+
+#[test]
+fn test_symbol_end_before_start() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let end = Label::new();
+    assembler.emit(Instruction::SetPrivate_Label { label: end }).unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+    assembler
+        .emit(Symbol::function("main", Label::new()).with_end(end))
+        .unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::InvalidSymbolSize(label)) if label == end));
+    assert!(final_code.is_empty());
+}
+
+#[test]
+fn test_symbol_end_not_set() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let end = Label::new();
+    assembler
+        .emit(Symbol::function("main", Label::new()).with_end(end))
+        .unwrap();
+    assembler.emit(Instruction::Ret).unwrap();
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code);
+    assert!(matches!(result, Err(AssembleError::LabelNotSet(label)) if label == end));
+}
@@ -0,0 +1,129 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::{assemble_single, mem};
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{CR, DR, GPR, Immediate32, Instruction, NewSystemRegisterError, Segment, Size},
+};
+
+#[rstest]
+#[case(Instruction::Mov_CrReg { dst: CR::CR0, src: GPR::RAX }, &[0x0F, 0x22, 0xC0])]
+#[case(Instruction::Mov_CrReg { dst: CR::CR8, src: GPR::RAX }, &[0x44, 0x0F, 0x22, 0xC0])]
+#[case(Instruction::Mov_RegCr { dst: GPR::RCX, src: CR::CR2 }, &[0x0F, 0x20, 0xD1])]
+#[case(Instruction::Mov_RegCr { dst: GPR::RAX, src: CR::CR3 }, &[0x0F, 0x20, 0xD8])]
+#[case(Instruction::Mov_RegCr { dst: GPR::R10, src: CR::CR4 }, &[0x41, 0x0F, 0x20, 0xE2])]
+#[case(Instruction::Mov_DrReg { dst: DR::DR0, src: GPR::RDX }, &[0x0F, 0x23, 0xC2])]
+#[case(Instruction::Mov_DrReg { dst: DR::DR7, src: GPR::R9 }, &[0x41, 0x0F, 0x23, 0xF9])]
+#[case(Instruction::Mov_RegDr { dst: GPR::RAX, src: DR::DR6 }, &[0x0F, 0x21, 0xF0])]
+#[case(Instruction::Mov_SegReg { dst: Segment::DS, src: GPR::AX }, &[0x66, 0x8E, 0xD8])]
+#[case(Instruction::Mov_SegReg { dst: Segment::DS, src: GPR::EAX }, &[0x8E, 0xD8])]
+#[case(Instruction::Mov_SegReg { dst: Segment::DS, src: GPR::RAX }, &[0x48, 0x8E, 0xD8])]
+#[case(Instruction::Mov_SegReg { dst: Segment::ES, src: GPR::R8D }, &[0x41, 0x8E, 0xC0])]
+#[case(Instruction::Mov_RegSeg { dst: GPR::EAX, src: Segment::FS }, &[0x8C, 0xE0])]
+#[case(Instruction::Mov_RegSeg { dst: GPR::RBX, src: Segment::GS }, &[0x48, 0x8C, 0xEB])]
+fn test_mov_system_registers(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Lgdt_Mem { src: mem(GPR::RDI, 0) }, &[0x0F, 0x01, 0x17])]
+#[case(Instruction::Lidt_Mem { src: mem(GPR::RDI, 0) }, &[0x0F, 0x01, 0x1F])]
+#[case(Instruction::Sgdt_Mem { dst: mem(GPR::RSP, 0) }, &[0x0F, 0x01, 0x04, 0x24])]
+#[case(Instruction::Sidt_Mem { dst: mem(GPR::R12, 0) }, &[0x41, 0x0F, 0x01, 0x0C, 0x24])]
+#[case(Instruction::Ltr_Reg { src: GPR::AX }, &[0x0F, 0x00, 0xD8])]
+#[case(Instruction::Ltr_Reg { src: GPR::R9W }, &[0x41, 0x0F, 0x00, 0xD9])]
+#[case(Instruction::Ltr_Mem { src: mem(GPR::RAX, 0) }, &[0x0F, 0x00, 0x18])]
+#[case(Instruction::Invlpg_Mem { src: mem(GPR::RAX, 8) }, &[0x0F, 0x01, 0x78, 0x08])]
+#[case(Instruction::Xsave_Mem { dst: mem(GPR::RCX, 0), is_64: false }, &[0x0F, 0xAE, 0x21])]
+#[case(Instruction::Xsave_Mem { dst: mem(GPR::RCX, 0), is_64: true }, &[0x48, 0x0F, 0xAE, 0x21])]
+#[case(Instruction::Xrstor_Mem { src: mem(GPR::RCX, 0), is_64: false }, &[0x0F, 0xAE, 0x29])]
+#[case(Instruction::Xrstor_Mem { src: mem(GPR::R15, 0), is_64: true }, &[0x49, 0x0F, 0xAE, 0x2F])]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::RAX, 0), size: Size::Bit32 }, &[0xFF, 0x28])]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::RDX, 16), size: Size::Bit64 }, &[0x48, 0xFF, 0x6A, 0x10])]
+#[case(Instruction::FarCall_Mem { dst: mem(GPR::RAX, 0), size: Size::Bit32 }, &[0xFF, 0x18])]
+#[case(Instruction::FarCall_Mem { dst: mem(GPR::RAX, 0), size: Size::Bit64 }, &[0x48, 0xFF, 0x18])]
+fn test_memory_operand(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Rdmsr, &[0x0F, 0x32])]
+#[case(Instruction::Wrmsr, &[0x0F, 0x30])]
+#[case(Instruction::Iretq, &[0x48, 0xCF])]
+#[case(Instruction::Swapgs, &[0x0F, 0x01, 0xF8])]
+#[case(Instruction::Sysret, &[0x48, 0x0F, 0x07])]
+#[case(Instruction::Cli, &[0xFA])]
+#[case(Instruction::Sti, &[0xFB])]
+#[case(Instruction::Hlt, &[0xF4])]
+#[case(Instruction::Xgetbv, &[0x0F, 0x01, 0xD0])]
+#[case(Instruction::Xsetbv, &[0x0F, 0x01, 0xD1])]
+#[case(Instruction::FarRet { size: Size::Bit32 }, &[0xCB])]
+#[case(Instruction::FarRet { size: Size::Bit64 }, &[0x48, 0xCB])]
+fn test_no_operands(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::In_RegImm { dst: GPR::AL, port: Immediate32::new(0x60) }, &[0xE4, 0x60])]
+#[case(Instruction::In_RegImm { dst: GPR::EAX, port: Immediate32::new(0x71) }, &[0xE5, 0x71])]
+#[case(Instruction::In_RegReg { dst: GPR::AL, port: GPR::DX }, &[0xEC])]
+#[case(Instruction::In_RegReg { dst: GPR::AX, port: GPR::DX }, &[0x66, 0xED])]
+#[case(Instruction::Out_ImmReg { port: Immediate32::new(0x80), src: GPR::AL }, &[0xE6, 0x80])]
+#[case(Instruction::Out_ImmReg { port: Immediate32::new(0x80), src: GPR::EAX }, &[0xE7, 0x80])]
+#[case(Instruction::Out_RegReg { port: GPR::DX, src: GPR::AL }, &[0xEE])]
+#[case(Instruction::Out_RegReg { port: GPR::DX, src: GPR::AX }, &[0x66, 0xEF])]
+fn test_port_io(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::In_RegImm { dst: GPR::BL, port: Immediate32::new(0x60) })]
+#[case(Instruction::In_RegImm { dst: GPR::RAX, port: Immediate32::new(0x60) })]
+#[case(Instruction::In_RegReg { dst: GPR::AL, port: GPR::CX })]
+#[case(Instruction::Out_RegReg { port: GPR::EDX, src: GPR::AL })]
+fn test_invalid_implicit_operands(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::InvalidImplicitOperand)));
+}
+
+#[rstest]
+#[case(Instruction::Mov_CrReg { dst: CR::CR0, src: GPR::EAX })]
+#[case(Instruction::Mov_RegDr { dst: GPR::AX, src: DR::DR7 })]
+#[case(Instruction::Mov_SegReg { dst: Segment::DS, src: GPR::AL })]
+#[case(Instruction::Ltr_Reg { src: GPR::EAX })]
+#[case(Instruction::In_RegImm { dst: GPR::AL, port: Immediate32::new(0x100) })]
+#[case(Instruction::FarJump_Mem { dst: mem(GPR::RAX, 0), size: Size::Bit16 })]
+#[case(Instruction::FarRet { size: Size::Bit8 })]
+fn test_invalid_sizes(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[test]
+fn test_mov_to_cs() {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Mov_SegReg {
+        dst: Segment::CS,
+        src: GPR::AX,
+    });
+    assert!(matches!(result, Err(EmitError::IncompatibleOperands)));
+}
+
+#[test]
+fn test_system_registers_new() {
+    assert_eq!(CR::new(8).unwrap(), CR::CR8);
+    assert_eq!(CR::new(1), Err(NewSystemRegisterError::InvalidIndex));
+    assert_eq!(DR::new(3).unwrap(), DR::DR3);
+    assert_eq!(DR::new(5), Err(NewSystemRegisterError::InvalidIndex));
+    assert_eq!(Segment::new(5).unwrap(), Segment::GS);
+    assert_eq!(Segment::new(6), Err(NewSystemRegisterError::InvalidIndex));
+}
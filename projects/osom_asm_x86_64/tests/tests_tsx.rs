@@ -0,0 +1,81 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::assemble;
+
+use osom_asm_x86_64::{
+    assembler::{CodeMode, EmitError, X86_64AssemblerBuilder},
+    models::{Immediate32, Instruction, Label},
+};
+
+#[rstest]
+#[case(Instruction::Xend, &[0x0F, 0x01, 0xD5])]
+#[case(Instruction::Xtest, &[0x0F, 0x01, 0xD6])]
+#[case(Instruction::Xabort_Imm { src: Immediate32::new(0) }, &[0xC6, 0xF8, 0x00])]
+#[case(Instruction::Xabort_Imm { src: Immediate32::new(0xFF) }, &[0xC6, 0xF8, 0xFF])]
+fn test_tsx_encoding(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble(X86_64AssemblerBuilder::new(), &[instruction]);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(-1)]
+#[case(0x100)]
+fn test_xabort_invalid_imm(#[case] value: i32) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(Instruction::Xabort_Imm {
+        src: Immediate32::new(value),
+    });
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[rstest]
+#[case(CodeMode::Bit64)]
+#[case(CodeMode::Bit32)]
+fn test_xbegin_backward(#[case] mode: CodeMode) {
+    let label = Label::new();
+    let instructions = [
+        Instruction::SetPrivate_Label { label },
+        Instruction::Xbegin_Label { dst: label },
+    ];
+    let final_code = assemble(X86_64AssemblerBuilder::new().with_code_mode(mode), &instructions);
+    assert_eq_hex!(final_code, &[0xC7, 0xF8, 0xFA, 0xFF, 0xFF, 0xFF]);
+}
+
+#[test]
+fn test_xbegin_forward() {
+    let fallback = Label::new();
+    let instructions = [
+        Instruction::Xbegin_Label { dst: fallback },
+        Instruction::Xend,
+        Instruction::Ret,
+        Instruction::SetPrivate_Label { label: fallback },
+        Instruction::Ret,
+    ];
+    let final_code = assemble(X86_64AssemblerBuilder::new(), &instructions);
+    assert_eq_hex!(
+        final_code,
+        &[0xC7, 0xF8, 0x04, 0x00, 0x00, 0x00, 0x0F, 0x01, 0xD5, 0xC3, 0xC3]
+    );
+}
+
+#[test]
+fn test_xbegin_after_relaxed_jump() {
+    let label = Label::new();
+    let instructions = [
+        Instruction::Jump_Label { dst: label },
+        Instruction::Xbegin_Label { dst: label },
+        Instruction::SetPrivate_Label { label },
+        Instruction::Ret,
+    ];
+    let final_code = assemble(X86_64AssemblerBuilder::new().with_relaxation(true), &instructions);
+    assert_eq_hex!(final_code, &[0xEB, 0x06, 0xC7, 0xF8, 0x00, 0x00, 0x00, 0x00, 0xC3]);
+}
+
+#[test]
+fn test_xbegin_in_bit16_code() {
+    let mut assembler = X86_64AssemblerBuilder::new().with_code_mode(CodeMode::Bit16).build();
+    let result = assembler.emit(Instruction::Xbegin_Label { dst: Label::new() });
+    assert!(matches!(result, Err(EmitError::NotSupportedInCodeMode)));
+}
@@ -0,0 +1,129 @@
+use osom_tools_dev::macros::assert_eq_hex;
+use rstest::rstest;
+
+mod utils;
+use utils::{assemble_single, mem};
+
+use osom_asm_x86_64::{
+    assembler::{EmitError, X86_64AssemblerBuilder},
+    models::{GPR, Instruction, NewSTError, ST, Size, X87ArithKind, X87Constant, X87FloatSize},
+};
+
+#[rstest]
+#[case(Instruction::Fld_Mem { size: X87FloatSize::Single, src: mem(GPR::RAX, 0) }, &[0xD9, 0x00])]
+#[case(Instruction::Fld_Mem { size: X87FloatSize::Double, src: mem(GPR::RSP, 8) }, &[0xDD, 0x44, 0x24, 0x08])]
+#[case(Instruction::Fld_Mem { size: X87FloatSize::Extended, src: mem(GPR::R12, 0) }, &[0x41, 0xDB, 0x2C, 0x24])]
+#[case(Instruction::Fld_St { src: ST::ST3 }, &[0xD9, 0xC3])]
+#[case(Instruction::Fst_Mem { size: X87FloatSize::Single, dst: mem(GPR::RDI, 0) }, &[0xD9, 0x17])]
+#[case(Instruction::Fst_Mem { size: X87FloatSize::Double, dst: mem(GPR::R9, 0) }, &[0x41, 0xDD, 0x11])]
+#[case(Instruction::Fst_St { dst: ST::ST2 }, &[0xDD, 0xD2])]
+#[case(Instruction::Fstp_Mem { size: X87FloatSize::Single, dst: mem(GPR::RDI, 0) }, &[0xD9, 0x1F])]
+#[case(Instruction::Fstp_Mem { size: X87FloatSize::Double, dst: mem(GPR::RDI, 0) }, &[0xDD, 0x1F])]
+#[case(Instruction::Fstp_Mem { size: X87FloatSize::Extended, dst: mem(GPR::RDI, 0) }, &[0xDB, 0x3F])]
+#[case(Instruction::Fstp_St { dst: ST::ST1 }, &[0xDD, 0xD9])]
+#[case(Instruction::Fild_Mem { size: Size::Bit16, src: mem(GPR::RAX, 0) }, &[0xDF, 0x00])]
+#[case(Instruction::Fild_Mem { size: Size::Bit32, src: mem(GPR::RAX, 0) }, &[0xDB, 0x00])]
+#[case(Instruction::Fild_Mem { size: Size::Bit64, src: mem(GPR::RAX, 0) }, &[0xDF, 0x28])]
+#[case(Instruction::Fistp_Mem { size: Size::Bit16, dst: mem(GPR::RBX, 0) }, &[0xDF, 0x1B])]
+#[case(Instruction::Fistp_Mem { size: Size::Bit32, dst: mem(GPR::RBX, 0) }, &[0xDB, 0x1B])]
+#[case(Instruction::Fistp_Mem { size: Size::Bit64, dst: mem(GPR::R13, 0) }, &[0x41, 0xDF, 0x7D, 0x00])]
+fn test_load_store(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(X87ArithKind::Add, X87FloatSize::Single, &[0xD8, 0x00])]
+#[case(X87ArithKind::Mul, X87FloatSize::Double, &[0xDC, 0x08])]
+#[case(X87ArithKind::Sub, X87FloatSize::Single, &[0xD8, 0x20])]
+#[case(X87ArithKind::SubReversed, X87FloatSize::Double, &[0xDC, 0x28])]
+#[case(X87ArithKind::Div, X87FloatSize::Single, &[0xD8, 0x30])]
+#[case(X87ArithKind::DivReversed, X87FloatSize::Double, &[0xDC, 0x38])]
+fn test_arith_mem(#[case] kind: X87ArithKind, #[case] size: X87FloatSize, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::X87Arith_Mem {
+            kind,
+            size,
+            src: mem(GPR::RAX, 0),
+        },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(X87ArithKind::Add, ST::ST1, &[0xD8, 0xC1])]
+#[case(X87ArithKind::Mul, ST::ST2, &[0xD8, 0xCA])]
+#[case(X87ArithKind::Sub, ST::ST3, &[0xD8, 0xE3])]
+#[case(X87ArithKind::SubReversed, ST::ST4, &[0xD8, 0xEC])]
+#[case(X87ArithKind::Div, ST::ST5, &[0xD8, 0xF5])]
+#[case(X87ArithKind::DivReversed, ST::ST6, &[0xD8, 0xFE])]
+fn test_arith_st0_st(#[case] kind: X87ArithKind, #[case] src: ST, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), Instruction::X87Arith_St0St { kind, src });
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(X87ArithKind::Add, ST::ST1, false, &[0xDC, 0xC1])]
+#[case(X87ArithKind::Mul, ST::ST2, false, &[0xDC, 0xCA])]
+#[case(X87ArithKind::Sub, ST::ST3, false, &[0xDC, 0xEB])]
+#[case(X87ArithKind::SubReversed, ST::ST4, false, &[0xDC, 0xE4])]
+#[case(X87ArithKind::Div, ST::ST5, false, &[0xDC, 0xFD])]
+#[case(X87ArithKind::DivReversed, ST::ST6, false, &[0xDC, 0xF6])]
+#[case(X87ArithKind::Add, ST::ST1, true, &[0xDE, 0xC1])]
+#[case(X87ArithKind::Mul, ST::ST2, true, &[0xDE, 0xCA])]
+#[case(X87ArithKind::Sub, ST::ST3, true, &[0xDE, 0xEB])]
+#[case(X87ArithKind::SubReversed, ST::ST4, true, &[0xDE, 0xE4])]
+#[case(X87ArithKind::Div, ST::ST5, true, &[0xDE, 0xFD])]
+#[case(X87ArithKind::DivReversed, ST::ST6, true, &[0xDE, 0xF6])]
+fn test_arith_st_st0(#[case] kind: X87ArithKind, #[case] dst: ST, #[case] pop: bool, #[case] expected: &[u8]) {
+    let final_code = assemble_single(
+        X86_64AssemblerBuilder::new(),
+        Instruction::X87Arith_StSt0 { kind, dst, pop },
+    );
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Fxch_St { src: ST::ST1 }, &[0xD9, 0xC9])]
+#[case(Instruction::Fcomi_St { src: ST::ST1, unordered: false, pop: false }, &[0xDB, 0xF1])]
+#[case(Instruction::Fcomi_St { src: ST::ST2, unordered: false, pop: true }, &[0xDF, 0xF2])]
+#[case(Instruction::Fcomi_St { src: ST::ST3, unordered: true, pop: false }, &[0xDB, 0xEB])]
+#[case(Instruction::Fcomi_St { src: ST::ST7, unordered: true, pop: true }, &[0xDF, 0xEF])]
+#[case(Instruction::Fldcw_Mem { src: mem(GPR::RSP, 0) }, &[0xD9, 0x2C, 0x24])]
+#[case(Instruction::Fnstcw_Mem { dst: mem(GPR::RSP, 2) }, &[0xD9, 0x7C, 0x24, 0x02])]
+#[case(Instruction::Fsqrt, &[0xD9, 0xFA])]
+fn test_misc(#[case] instruction: Instruction, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), instruction);
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(X87Constant::One, &[0xD9, 0xE8])]
+#[case(X87Constant::Zero, &[0xD9, 0xEE])]
+#[case(X87Constant::Pi, &[0xD9, 0xEB])]
+#[case(X87Constant::Log2Ten, &[0xD9, 0xE9])]
+#[case(X87Constant::Log2E, &[0xD9, 0xEA])]
+#[case(X87Constant::Log10Two, &[0xD9, 0xEC])]
+#[case(X87Constant::LnTwo, &[0xD9, 0xED])]
+fn test_constants(#[case] constant: X87Constant, #[case] expected: &[u8]) {
+    let final_code = assemble_single(X86_64AssemblerBuilder::new(), Instruction::FldConst { constant });
+    assert_eq_hex!(final_code, expected);
+}
+
+#[rstest]
+#[case(Instruction::Fst_Mem { size: X87FloatSize::Extended, dst: mem(GPR::RAX, 0) })]
+#[case(Instruction::Fild_Mem { size: Size::Bit8, src: mem(GPR::RAX, 0) })]
+#[case(Instruction::Fistp_Mem { size: Size::Bit8, dst: mem(GPR::RAX, 0) })]
+#[case(Instruction::X87Arith_Mem { kind: X87ArithKind::Add, size: X87FloatSize::Extended, src: mem(GPR::RAX, 0) })]
+fn test_invalid_sizes(#[case] instruction: Instruction) {
+    let mut assembler = X86_64AssemblerBuilder::new().build();
+    let result = assembler.emit(instruction);
+    assert!(matches!(result, Err(EmitError::OperandSizeMismatch)));
+}
+
+#[test]
+fn test_st_new() {
+    assert_eq!(ST::new(5).unwrap(), ST::ST5);
+    assert_eq!(ST::new(8), Err(NewSTError::IndexOutOfRange));
+}
@@ -1,4 +1,32 @@
-#![allow(unused_imports)]
+#![allow(unused_imports, dead_code)]
 
 #[cfg(target_arch = "x86_64")]
 pub mod region_stream;
+
+use osom_asm_x86_64::{
+    assembler::X86_64AssemblerBuilder,
+    models::{GPR, Immediate32, Instruction, Memory},
+};
+
+/// Assembles the `instructions` with an assembler built by `builder`.
+pub fn assemble(builder: X86_64AssemblerBuilder, instructions: &[Instruction]) -> Vec<u8> {
+    let mut assembler = builder.build();
+    for instruction in instructions {
+        assembler.emit(instruction.clone()).unwrap();
+    }
+
+    let mut final_code = Vec::new();
+    let result = assembler.assemble(&mut final_code).unwrap();
+    assert_eq!(result.emitted_bytes(), final_code.len() as i32);
+    final_code
+}
+
+/// Assembles a single `instruction` with an assembler built by `builder`.
+pub fn assemble_single(builder: X86_64AssemblerBuilder, instruction: Instruction) -> Vec<u8> {
+    assemble(builder, &[instruction])
+}
+
+/// Creates a `[base + offset]` memory operand.
+pub fn mem(base: GPR, offset: i32) -> Memory {
+    Memory::based(base, Immediate32::new(offset)).unwrap()
+}
@@ -26,6 +26,10 @@ impl RegionStream {
     pub fn as_slice(&self) -> &[u8] {
         &as_slice(&self.allocated_space)[0..self.length]
     }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut as_mut_slice(&mut self.allocated_space)[0..self.length]
+    }
 }
 
 impl Write for RegionStream {